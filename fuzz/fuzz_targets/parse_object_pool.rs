@@ -0,0 +1,18 @@
+//! Parse arbitrary bytes as an object pool, run with `cargo fuzz run parse_object_pool`.
+//!
+//! Parsing must never panic or allocate based on unchecked length fields. Anything that parses
+//! must serialize again.
+#![no_main]
+
+use ag_iso_stack::object_pool::{ObjectPool, ParseOptions};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = ObjectPool::scan_headers(data);
+
+    if let Ok((pool, _)) = ObjectPool::from_iop_with(data.iter().copied(), ParseOptions::default())
+    {
+        assert_eq!(pool.size(), pool.as_iop().len());
+    }
+    let _ = ObjectPool::from_iop_with(data.iter().copied(), ParseOptions { strict: true });
+});
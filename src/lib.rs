@@ -3,5 +3,8 @@
 #![allow(clippy::needless_return)]
 #![allow(clippy::module_inception)]
 
+extern crate alloc;
+
 pub mod driver;
 pub mod network_management;
+pub mod object_pool;
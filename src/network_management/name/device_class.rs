@@ -89,6 +89,29 @@ pub enum DeviceClass {
     IndustrialProcessControlStationary,
 }
 
+impl DeviceClass {
+    /// Get all Device Classes of an Industry Group, ordered by their value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ag_iso_stack::network_management::name::{IndustryGroup, DeviceClass};
+    /// let device_classes = DeviceClass::all_for(IndustryGroup::OnHighwayEquipment);
+    ///
+    /// assert_eq!(vec![
+    ///     DeviceClass::NonSpecificSystem(IndustryGroup::OnHighwayEquipment),
+    ///     DeviceClass::Tractor(IndustryGroup::OnHighwayEquipment),
+    ///     DeviceClass::Trailer,
+    /// ], device_classes);
+    /// ```
+    pub fn all_for(industry_group: IndustryGroup) -> Vec<DeviceClass> {
+        (0..0x7F)
+            .map(|value| DeviceClass::from((value, industry_group)))
+            .filter(|&device_class| device_class != DeviceClass::NotAvailable)
+            .collect()
+    }
+}
+
 /// Display the Device Class name.
 ///
 /// # Examples
@@ -357,3 +380,33 @@ impl From<DeviceClass> for IndustryGroup {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_for_round_trip() {
+        for industry_group in IndustryGroup::all() {
+            let device_classes = DeviceClass::all_for(industry_group);
+            assert!(!device_classes.is_empty());
+
+            for device_class in device_classes {
+                assert_eq!(industry_group, IndustryGroup::from(device_class));
+                assert_eq!(
+                    device_class,
+                    DeviceClass::from((u8::from(device_class), industry_group))
+                );
+            }
+        }
+
+        assert_eq!(
+            27,
+            DeviceClass::all_for(IndustryGroup::AgriculturalAndForestryEquipment).len()
+        );
+        assert_eq!(
+            vec![DeviceClass::IndustrialProcessControlStationary],
+            DeviceClass::all_for(IndustryGroup::IndustrialProcessControl)
+        );
+    }
+}
@@ -27,6 +27,40 @@ pub enum IndustryGroup {
     ReservedForSAE2 = 7,
 }
 
+impl IndustryGroup {
+    /// Get all Industry Groups, ordered by their value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ag_iso_stack::network_management::name::IndustryGroup;
+    /// let industry_groups = IndustryGroup::all();
+    ///
+    /// assert_eq!(IndustryGroup::Global, industry_groups[0]);
+    /// assert_eq!(2, industry_groups.iter().filter(|ig| ig.is_reserved()).count());
+    /// ```
+    pub fn all() -> [IndustryGroup; 8] {
+        [
+            IndustryGroup::Global,
+            IndustryGroup::OnHighwayEquipment,
+            IndustryGroup::AgriculturalAndForestryEquipment,
+            IndustryGroup::ConstructionEquipment,
+            IndustryGroup::MarineEquipment,
+            IndustryGroup::IndustrialProcessControl,
+            IndustryGroup::ReservedForSAE1,
+            IndustryGroup::ReservedForSAE2,
+        ]
+    }
+
+    /// Returns true for the Industry Groups reserved by SAE.
+    pub fn is_reserved(&self) -> bool {
+        matches!(
+            self,
+            IndustryGroup::ReservedForSAE1 | IndustryGroup::ReservedForSAE2
+        )
+    }
+}
+
 /// Display the Industry Group name.
 ///
 /// # Examples
@@ -85,3 +119,18 @@ impl From<u8> for IndustryGroup {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all() {
+        let industry_groups = IndustryGroup::all();
+        assert_eq!(8, industry_groups.len());
+        for (value, industry_group) in industry_groups.iter().enumerate() {
+            assert_eq!(value as u8, u8::from(*industry_group));
+            assert_eq!(value >= 6, industry_group.is_reserved());
+        }
+    }
+}
@@ -9,12 +9,21 @@ pub use device_class::DeviceClass;
 mod function_code;
 pub use function_code::FunctionCode;
 
-#[derive(Default, Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq)]
 pub struct NAME {
     raw_name: u64,
 }
 
 impl NAME {
+    /// A NAME with every field zero, except for the function code and device class which are
+    /// [`FunctionCode::NotAvailable`] and [`DeviceClass::NotAvailable`].
+    ///
+    /// The industry group is [`IndustryGroup::Global`]. This is the same NAME an empty
+    /// [`NameBuilder`] builds.
+    pub const NULL: NAME = NAME {
+        raw_name: 0x00FE_7F00_0000_0000,
+    };
+
     pub fn new(raw_name: u64) -> Self {
         Self { raw_name }
     }
@@ -32,6 +41,14 @@ impl NAME {
             .all(|name_filter| name_filter.match_filter(self))
     }
 
+    /// Check whether two control functions with these NAMEs would collide on the bus.
+    ///
+    /// Every field must match for a conflict, except for the self-configurable address bit.
+    pub fn conflicts_with(&self, other: &NAME) -> bool {
+        const MASK: u64 = !0x8000000000000000;
+        self.raw_name & MASK == other.raw_name & MASK
+    }
+
     /// Raven specific
     pub fn short_identity_number(&self) -> u16 {
         (self.raw_name & 0x0000FFFF) as u16
@@ -138,6 +155,12 @@ impl NAME {
     }
 }
 
+impl Default for NAME {
+    fn default() -> Self {
+        NAME::NULL
+    }
+}
+
 impl From<NAME> for u64 {
     fn from(name: NAME) -> Self {
         name.raw_name
@@ -181,6 +204,43 @@ impl core::fmt::Debug for NAME {
     }
 }
 
+/// A NAME field that does not fit in its bit width.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NameError {
+    /// The identity number is wider than 21 bits
+    IdentityNumber(u32),
+    /// The manufacturer code is wider than 11 bits
+    ManufacturerCode(u16),
+    /// The ECU instance is wider than 3 bits
+    EcuInstance(u8),
+    /// The function instance is wider than 5 bits
+    FunctionInstance(u8),
+    /// The device class instance is wider than 4 bits
+    DeviceClassInstance(u8),
+}
+
+impl core::fmt::Display for NameError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            NameError::IdentityNumber(val) => {
+                write!(f, "identity number {val} exceeds the maximum of 0x1FFFFF")
+            }
+            NameError::ManufacturerCode(val) => {
+                write!(f, "manufacturer code {val} exceeds the maximum of 0x7FF")
+            }
+            NameError::EcuInstance(val) => {
+                write!(f, "ECU instance {val} exceeds the maximum of 7")
+            }
+            NameError::FunctionInstance(val) => {
+                write!(f, "function instance {val} exceeds the maximum of 31")
+            }
+            NameError::DeviceClassInstance(val) => {
+                write!(f, "device class instance {val} exceeds the maximum of 15")
+            }
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct NameBuilder {
     identity_number: u32,
@@ -213,6 +273,28 @@ impl NameBuilder {
         name
     }
 
+    /// Build the NAME, failing on the first field that does not fit in its bit width.
+    ///
+    /// [`NameBuilder::build`] silently truncates such fields instead.
+    pub fn try_build(&self) -> Result<NAME, NameError> {
+        if self.identity_number > 0x1FFFFF {
+            return Err(NameError::IdentityNumber(self.identity_number));
+        }
+        if self.manufacturer_code > 0x7FF {
+            return Err(NameError::ManufacturerCode(self.manufacturer_code));
+        }
+        if self.ecu_instance > 0x07 {
+            return Err(NameError::EcuInstance(self.ecu_instance));
+        }
+        if self.function_instance > 0x1F {
+            return Err(NameError::FunctionInstance(self.function_instance));
+        }
+        if self.device_class_instance > 0x0F {
+            return Err(NameError::DeviceClassInstance(self.device_class_instance));
+        }
+        Ok(self.build())
+    }
+
     /// Raven specific
     pub fn short_identity_number(&mut self, value: u16) -> &mut NameBuilder {
         self.identity_number &= !0x0000FFFF;
@@ -328,6 +410,54 @@ mod tests {
         assert_eq!(10881826125818888196_u64, name_under_test.into());
     }
 
+    #[test]
+    fn test_name_builder_try_build() {
+        assert!(NAME::builder()
+            .identity_number(0x1FFFFF)
+            .manufacturer_code(0x7FF)
+            .ecu_instance(7)
+            .function_instance(31)
+            .device_class_instance(15)
+            .try_build()
+            .is_ok());
+
+        assert_eq!(
+            Err(NameError::IdentityNumber(0x200000)),
+            NAME::builder().identity_number(0x200000).try_build()
+        );
+        assert_eq!(
+            Err(NameError::ManufacturerCode(0x800)),
+            NAME::builder().manufacturer_code(0x800).try_build()
+        );
+        assert_eq!(
+            Err(NameError::EcuInstance(8)),
+            NAME::builder().ecu_instance(8).try_build()
+        );
+        assert_eq!(
+            Err(NameError::FunctionInstance(32)),
+            NAME::builder().function_instance(32).try_build()
+        );
+        assert_eq!(
+            Err(NameError::DeviceClassInstance(16)),
+            NAME::builder().device_class_instance(16).try_build()
+        );
+    }
+
+    #[test]
+    fn test_name_default() {
+        let name_under_test = NAME::default();
+
+        assert_eq!(NAME::NULL, name_under_test);
+        assert_eq!(NameBuilder::new().build(), name_under_test);
+        assert_eq!(0x00FE7F0000000000_u64, name_under_test.into());
+        assert_eq!(IndustryGroup::Global, name_under_test.industry_group());
+        assert_eq!(DeviceClass::NotAvailable, name_under_test.device_class());
+        assert_eq!(FunctionCode::NotAvailable, name_under_test.function_code());
+        assert_eq!(0, name_under_test.identity_number());
+        assert_eq!(0, name_under_test.manufacturer_code());
+        assert_eq!(false, name_under_test.self_configurable_address());
+    }
+
     #[test]
     fn test_out_of_range_properties() {
         let mut name_under_test = NAME::new(0);
@@ -356,6 +486,31 @@ mod tests {
         assert_eq!(name_under_test1, name_under_test2);
     }
 
+    #[test]
+    fn test_name_conflicts_with() {
+        let name1 = NAME::builder()
+            .identity_number(1)
+            .manufacturer_code(64)
+            .function_code(FunctionCode::VirtualTerminal)
+            .build();
+        let mut name2 = NAME::builder()
+            .identity_number(2)
+            .manufacturer_code(64)
+            .function_code(FunctionCode::VirtualTerminal)
+            .build();
+
+        assert!(name1.conflicts_with(&name1));
+        assert!(!name1.conflicts_with(&name2));
+
+        name2.set_identity_number(1);
+        name2.set_self_configurable_address(true);
+        assert!(name1.conflicts_with(&name2));
+        assert!(name2.conflicts_with(&name1));
+
+        name2.set_ecu_instance(1);
+        assert!(!name1.conflicts_with(&name2));
+    }
+
     #[test]
     fn test_filter_matching() {
         let mut test_name = NAME::new(0);
@@ -0,0 +1,543 @@
+use super::*;
+
+/// An object attribute that can be changed with the VT Change Attribute command and read back
+/// with the Get Attribute Value command.
+///
+/// The command carries every value as a `u32`, smaller values in the low bytes. Signed values
+/// are sent as their two's complement and floats as their IEEE 754 bits.
+trait Attribute: Sized {
+    fn from_u32(value: u32) -> Option<Self>;
+    fn to_u32(&self) -> u32;
+}
+
+impl Attribute for u8 {
+    fn from_u32(value: u32) -> Option<Self> {
+        value.try_into().ok()
+    }
+
+    fn to_u32(&self) -> u32 {
+        u32::from(*self)
+    }
+}
+
+impl Attribute for u16 {
+    fn from_u32(value: u32) -> Option<Self> {
+        value.try_into().ok()
+    }
+
+    fn to_u32(&self) -> u32 {
+        u32::from(*self)
+    }
+}
+
+impl Attribute for u32 {
+    fn from_u32(value: u32) -> Option<Self> {
+        Some(value)
+    }
+
+    fn to_u32(&self) -> u32 {
+        *self
+    }
+}
+
+impl Attribute for i16 {
+    fn from_u32(value: u32) -> Option<Self> {
+        u16::try_from(value).ok().map(|v| v as i16)
+    }
+
+    fn to_u32(&self) -> u32 {
+        u32::from(*self as u16)
+    }
+}
+
+impl Attribute for i32 {
+    fn from_u32(value: u32) -> Option<Self> {
+        Some(value as i32)
+    }
+
+    fn to_u32(&self) -> u32 {
+        *self as u32
+    }
+}
+
+impl Attribute for f32 {
+    fn from_u32(value: u32) -> Option<Self> {
+        Some(f32::from_bits(value))
+    }
+
+    fn to_u32(&self) -> u32 {
+        self.to_bits()
+    }
+}
+
+impl Attribute for bool {
+    fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    fn to_u32(&self) -> u32 {
+        u32::from(*self)
+    }
+}
+
+impl Attribute for ObjectId {
+    fn from_u32(value: u32) -> Option<Self> {
+        u16::try_from(value).ok().map(ObjectId::from)
+    }
+
+    fn to_u32(&self) -> u32 {
+        u32::from(u16::from(*self))
+    }
+}
+
+/// Map the attribute ids (AID) of each object type to the struct fields.
+///
+/// Value attributes without an AID of their own are left out, they are changed with the
+/// Change Numeric Value and Change String Value commands. Attributes marked `[read_only]` can be
+/// read but not set.
+macro_rules! attribute_table {
+    ($($object:ident { $($aid:literal => $field:ident $([$ro:ident])?),* $(,)? }),* $(,)?) => {
+        impl Object {
+            /// Set an attribute by its attribute id (AID), like the VT Change Attribute command.
+            ///
+            /// Read-only attributes are rejected with [`VtError::InvalidAttributeId`].
+            pub fn set_attribute(&mut self, aid: u8, value: u32) -> Result<(), VtError> {
+                match self {
+                    $(
+                        Object::$object(o) => match aid {
+                            $(
+                                $aid if !read_only!($($ro)?) => {
+                                    o.$field =
+                                        Attribute::from_u32(value).ok_or(VtError::InvalidValue)?;
+                                }
+                            )*
+                            _ => return Err(VtError::InvalidAttributeId),
+                        },
+                    )*
+                    _ => return Err(VtError::InvalidAttributeId),
+                }
+                Ok(())
+            }
+
+            /// Get an attribute by its attribute id (AID), like the VT Get Attribute Value command.
+            pub fn attribute(&self, aid: u8) -> Option<u32> {
+                match self {
+                    $(
+                        Object::$object(o) => match aid {
+                            $($aid => Some(Attribute::to_u32(&o.$field)),)*
+                            _ => None,
+                        },
+                    )*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+macro_rules! read_only {
+    () => {
+        false
+    };
+    (read_only) => {
+        true
+    };
+}
+
+attribute_table! {
+    WorkingSet {
+        1 => background_colour,
+        2 => selectable,
+        3 => active_mask,
+    },
+    DataMask {
+        1 => background_colour,
+        2 => soft_key_mask,
+    },
+    AlarmMask {
+        1 => background_colour,
+        2 => soft_key_mask,
+        3 => priority,
+        4 => acoustic_signal,
+    },
+    Container {
+        1 => width,
+        2 => height,
+        3 => hidden [read_only],
+    },
+    SoftKeyMask {
+        1 => background_colour,
+    },
+    Key {
+        1 => background_colour,
+        2 => key_code,
+    },
+    Button {
+        1 => width,
+        2 => height,
+        3 => background_colour,
+        4 => border_colour,
+        5 => key_code,
+        6 => options,
+    },
+    InputBoolean {
+        1 => background_colour,
+        2 => width,
+        3 => foreground_colour,
+        4 => variable_reference,
+    },
+    InputString {
+        1 => width,
+        2 => height,
+        3 => background_colour,
+        4 => font_attributes,
+        5 => input_attributes,
+        6 => options,
+        7 => variable_reference,
+        8 => justification,
+    },
+    InputNumber {
+        1 => width,
+        2 => height,
+        3 => background_colour,
+        4 => font_attributes,
+        5 => options,
+        6 => variable_reference,
+        7 => min_value,
+        8 => max_value,
+        9 => offset,
+        10 => scale,
+        11 => nr_of_decimals,
+        12 => format,
+        13 => justification,
+        14 => options2,
+    },
+    InputList {
+        1 => width,
+        2 => height,
+        3 => variable_reference,
+        4 => options,
+    },
+    OutputString {
+        1 => width,
+        2 => height,
+        3 => background_colour,
+        4 => font_attributes,
+        5 => options,
+        6 => variable_reference,
+        7 => justification,
+    },
+    OutputNumber {
+        1 => width,
+        2 => height,
+        3 => background_colour,
+        4 => font_attributes,
+        5 => options,
+        6 => variable_reference,
+        7 => offset,
+        8 => scale,
+        9 => nr_of_decimals,
+        10 => format,
+        11 => justification,
+    },
+    OutputList {
+        1 => width,
+        2 => height,
+        3 => variable_reference,
+    },
+    OutputLine {
+        1 => line_attributes,
+        2 => width,
+        3 => height,
+        4 => line_direction,
+    },
+    OutputRectangle {
+        1 => line_attributes,
+        2 => width,
+        3 => height,
+        4 => line_suppression,
+        5 => fill_attributes,
+    },
+    OutputEllipse {
+        1 => line_attributes,
+        2 => width,
+        3 => height,
+        4 => ellipse_type,
+        5 => start_angle,
+        6 => end_angle,
+        7 => fill_attributes,
+    },
+    OutputPolygon {
+        1 => width,
+        2 => height,
+        3 => line_attributes,
+        4 => fill_attributes,
+        5 => polygon_type,
+    },
+    OutputMeter {
+        1 => width,
+        2 => needle_colour,
+        3 => border_colour,
+        4 => arc_and_tick_colour,
+        5 => options,
+        6 => nr_of_ticks,
+        7 => start_angle,
+        8 => end_angle,
+        9 => min_value,
+        10 => max_value,
+        11 => variable_reference,
+    },
+    OutputLinearBarGraph {
+        1 => width,
+        2 => height,
+        3 => colour,
+        4 => target_line_colour,
+        5 => options,
+        6 => nr_of_ticks,
+        7 => min_value,
+        8 => max_value,
+        9 => variable_reference,
+        10 => target_value_variable_reference,
+        11 => target_value,
+    },
+    OutputArchedBarGraph {
+        1 => width,
+        2 => height,
+        3 => colour,
+        4 => target_line_colour,
+        5 => options,
+        6 => start_angle,
+        7 => end_angle,
+        8 => bar_graph_width,
+        9 => min_value,
+        10 => max_value,
+        11 => variable_reference,
+        12 => target_value_variable_reference,
+        13 => target_value,
+    },
+    PictureGraphic {
+        1 => width,
+        2 => options,
+        3 => transparency_colour,
+        4 => actual_width [read_only],
+        5 => actual_height [read_only],
+        6 => format [read_only],
+    },
+    FontAttributes {
+        1 => font_colour,
+        2 => font_size,
+        3 => font_type,
+        4 => font_style,
+    },
+    LineAttributes {
+        1 => line_colour,
+        2 => line_width,
+        3 => line_art,
+    },
+    FillAttributes {
+        1 => fill_type,
+        2 => fill_colour,
+        3 => fill_pattern,
+    },
+    InputAttributes {
+        1 => validation_type,
+    },
+    ExtendedInputAttributes {
+        1 => validation_type,
+    },
+    AuxiliaryFunctionType2 {
+        1 => background_colour,
+        2 => function_attributes,
+    },
+    AuxiliaryInputType2 {
+        1 => background_colour,
+        2 => function_attributes,
+    },
+    AuxiliaryControlDesignatorType2 {
+        1 => pointer_type,
+        2 => auxiliary_object_id,
+    },
+    GraphicsContext {
+        1 => viewport_width,
+        2 => viewport_height,
+        3 => viewport_x,
+        4 => viewport_y,
+        5 => canvas_width,
+        6 => canvas_height,
+        7 => viewport_zoom,
+        8 => graphics_cursor_x,
+        9 => graphics_cursor_y,
+        10 => foreground_colour,
+        11 => background_colour,
+        12 => font_attributes_object,
+        13 => line_attributes_object,
+        14 => fill_attributes_object,
+        15 => format,
+        16 => options,
+        17 => transparency_colour,
+    },
+    WindowMask {
+        1 => background_colour,
+        2 => options,
+        3 => name,
+        4 => window_title,
+        5 => window_icon,
+    },
+    KeyGroup {
+        1 => options,
+        2 => name,
+        3 => key_group_icon,
+    },
+    ExternalObjectPointer {
+        1 => default_object_id,
+        2 => external_reference_name_id,
+        3 => external_object_id,
+    },
+    Animation {
+        1 => width,
+        2 => height,
+        3 => refresh_interval,
+        4 => value,
+        5 => enabled,
+        6 => first_child_index,
+        7 => last_child_index,
+        8 => default_child_index,
+        9 => options,
+    },
+    ColourPalette {
+        1 => options,
+    },
+    ScalesGraphic {
+        1 => width,
+        2 => height,
+        3 => scale_type,
+        4 => options,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_attribute() {
+        let mut obj = Object::OutputNumber(OutputNumber {
+            id: 12000.into(),
+            width: 50,
+            height: 20,
+            background_colour: 1,
+            font_attributes: 23000.into(),
+            options: 0,
+            variable_reference: ObjectId::NULL,
+            value: 0,
+            offset: 0,
+            scale: 1.0,
+            nr_of_decimals: 0,
+            format: false,
+            justification: 0,
+            macro_refs: Vec::new(),
+        });
+
+        assert_eq!(Ok(()), obj.set_attribute(1, 80));
+        assert_eq!(Ok(()), obj.set_attribute(6, 21000));
+        assert_eq!(Ok(()), obj.set_attribute(7, (-10i32) as u32));
+        assert_eq!(Ok(()), obj.set_attribute(8, 0.5f32.to_bits()));
+        assert_eq!(Ok(()), obj.set_attribute(10, 1));
+        assert_eq!(Err(VtError::InvalidValue), obj.set_attribute(3, 256));
+        assert_eq!(Err(VtError::InvalidValue), obj.set_attribute(10, 2));
+        assert_eq!(Err(VtError::InvalidAttributeId), obj.set_attribute(12, 0));
+
+        match &obj {
+            Object::OutputNumber(o) => {
+                assert_eq!(80, o.width);
+                assert_eq!(ObjectId::from(21000), o.variable_reference);
+                assert_eq!(-10, o.offset);
+                assert_eq!(0.5, o.scale);
+                assert!(o.format);
+                assert_eq!(1, o.background_colour);
+            }
+            _ => unreachable!(),
+        }
+
+        assert_eq!(Some(80), obj.attribute(1));
+        assert_eq!(Some((-10i32) as u32), obj.attribute(7));
+        assert_eq!(Some(0.5f32.to_bits()), obj.attribute(8));
+        assert_eq!(None, obj.attribute(12));
+
+        let mut obj = Object::NumberVariable(NumberVariable {
+            id: 21000.into(),
+            value: 0,
+        });
+        assert_eq!(Err(VtError::InvalidAttributeId), obj.set_attribute(1, 0));
+        assert_eq!(None, obj.attribute(1));
+    }
+
+    #[test]
+    fn test_animation_attributes() {
+        let mut obj = Object::Animation(Animation {
+            id: 30000.into(),
+            width: 100,
+            height: 50,
+            refresh_interval: 200,
+            value: 0,
+            enabled: true,
+            first_child_index: 0,
+            last_child_index: 3,
+            default_child_index: 0,
+            options: 0,
+            object_refs: Vec::new(),
+            macro_refs: Vec::new(),
+        });
+
+        assert_eq!(Ok(()), obj.set_attribute(4, 2));
+        assert_eq!(Ok(()), obj.set_attribute(8, 1));
+        assert_eq!(Ok(()), obj.set_attribute(9, 0b11));
+
+        match &obj {
+            Object::Animation(o) => {
+                assert_eq!(2, o.value);
+                assert!(o.enabled);
+                assert_eq!(1, o.default_child_index);
+                assert_eq!(0b11, o.options);
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(Some(1), obj.attribute(5));
+    }
+
+    #[test]
+    fn test_read_only_attributes() {
+        let mut obj = Object::PictureGraphic(PictureGraphic {
+            id: 20000.into(),
+            width: 100,
+            actual_width: 50,
+            actual_height: 40,
+            format: 2,
+            options: 0,
+            transparency_colour: 0,
+            data: Vec::new(),
+            macro_refs: Vec::new(),
+        });
+        assert_eq!(Some(50), obj.attribute(4));
+        assert_eq!(Some(40), obj.attribute(5));
+        assert_eq!(Some(2), obj.attribute(6));
+        assert_eq!(Err(VtError::InvalidAttributeId), obj.set_attribute(4, 60));
+        assert_eq!(Ok(()), obj.set_attribute(1, 60));
+        assert_eq!(Some(50), obj.attribute(4));
+
+        let mut obj = Object::Container(Container {
+            id: 3000.into(),
+            width: 100,
+            height: 50,
+            hidden: false,
+            object_refs: Vec::new(),
+            macro_refs: Vec::new(),
+        });
+        assert_eq!(Err(VtError::InvalidAttributeId), obj.set_attribute(3, 1));
+        assert_eq!(Some(0), obj.attribute(3));
+    }
+}
@@ -0,0 +1,149 @@
+use alloc::vec::Vec;
+
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuilderError {
+    /// An object reference points to [`ObjectId::NULL`]
+    NullObjectRef,
+}
+
+/// Builder for a [`Button`] object.
+///
+/// The colours default to a grey background with a black border.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ag_iso_stack::object_pool::{Button, ButtonOptions, Point};
+/// let button = Button::builder(5000.into())
+///     .width(80)
+///     .height(40)
+///     .key_code(1)
+///     .options(ButtonOptions::LATCHABLE)
+///     .add_object_ref(11000.into(), Point { x: 4, y: 4 })
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(button.object_refs.len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct ButtonBuilder {
+    id: ObjectId,
+    width: u16,
+    height: u16,
+    background_colour: u8,
+    border_colour: u8,
+    key_code: u8,
+    options: ButtonOptions,
+    object_refs: Vec<ObjectRef>,
+    macro_refs: Vec<MacroRef>,
+}
+
+impl ButtonBuilder {
+    pub fn new(id: ObjectId) -> ButtonBuilder {
+        ButtonBuilder {
+            id,
+            width: 0,
+            height: 0,
+            background_colour: Colour::GREY_INDEX,
+            border_colour: 0, // Colour::BLACK
+            key_code: 0,
+            options: ButtonOptions::empty(),
+            object_refs: Vec::new(),
+            macro_refs: Vec::new(),
+        }
+    }
+
+    pub fn build(&self) -> Result<Button, BuilderError> {
+        if self.object_refs.iter().any(|r| r.id == ObjectId::NULL) {
+            return Err(BuilderError::NullObjectRef);
+        }
+
+        Ok(Button {
+            id: self.id,
+            width: self.width,
+            height: self.height,
+            background_colour: self.background_colour,
+            border_colour: self.border_colour,
+            key_code: self.key_code,
+            options: self.options.bits(),
+            object_refs: self.object_refs.clone(),
+            macro_refs: self.macro_refs.clone(),
+        })
+    }
+
+    pub fn width(&mut self, value: u16) -> &mut ButtonBuilder {
+        self.width = value;
+        self
+    }
+    pub fn height(&mut self, value: u16) -> &mut ButtonBuilder {
+        self.height = value;
+        self
+    }
+    pub fn background_colour(&mut self, value: u8) -> &mut ButtonBuilder {
+        self.background_colour = value;
+        self
+    }
+    pub fn border_colour(&mut self, value: u8) -> &mut ButtonBuilder {
+        self.border_colour = value;
+        self
+    }
+    pub fn key_code(&mut self, value: u8) -> &mut ButtonBuilder {
+        self.key_code = value;
+        self
+    }
+    pub fn options(&mut self, value: ButtonOptions) -> &mut ButtonBuilder {
+        self.options = value;
+        self
+    }
+    pub fn add_object_ref(&mut self, id: ObjectId, offset: Point<i16>) -> &mut ButtonBuilder {
+        self.object_refs.push(ObjectRef { id, offset });
+        self
+    }
+    pub fn add_macro_ref(&mut self, macro_id: u8, event_id: u8) -> &mut ButtonBuilder {
+        self.macro_refs.push(MacroRef { macro_id, event_id });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_button_builder() {
+        let button = Button::builder(5000.into())
+            .width(100)
+            .height(50)
+            .key_code(3)
+            .options(ButtonOptions::LATCHABLE | ButtonOptions::NO_BORDER)
+            .add_object_ref(11000.into(), Point { x: 10, y: 5 })
+            .build()
+            .unwrap();
+
+        assert_eq!(ObjectId::from(5000), button.id);
+        assert_eq!(100, button.width);
+        assert_eq!(50, button.height);
+        assert_eq!(8, button.background_colour);
+        assert_eq!(0, button.border_colour);
+        assert_eq!(3, button.key_code);
+        assert_eq!(0b0010_0001, button.options);
+        assert_eq!(
+            ButtonOptions::LATCHABLE | ButtonOptions::NO_BORDER,
+            button.button_options()
+        );
+        assert_eq!(ObjectId::from(11000), button.object_refs[0].id);
+        assert_eq!(10, button.object_refs[0].offset.x);
+        assert_eq!(5, button.object_refs[0].offset.y);
+    }
+
+    #[test]
+    fn test_button_builder_null_object_ref() {
+        let result = Button::builder(5000.into())
+            .add_object_ref(ObjectId::NULL, Point::default())
+            .build();
+
+        assert_eq!(Err(BuilderError::NullObjectRef), result.map(|b| b.id));
+    }
+}
@@ -0,0 +1,126 @@
+use alloc::{string::String, vec::Vec};
+
+/// The 8-bit character set a string is encoded in, selected by [`FontAttributes::font_type`].
+///
+/// The reader and writer map every byte to the character with the same code point, as
+/// [`CharacterSet::Latin1`] does, so strings in other character sets survive a round trip
+/// unchanged. Use [`CharacterSet::decode_str`] and [`CharacterSet::encode_str`] to convert such
+/// strings from and to the text they represent. Characters above U+00FF can not be written and
+/// become `?`, [`ObjectPool::validate`] reports them.
+///
+/// ISO 8859-2 (Latin 2), ISO 8859-4 (Latin 4) and ISO 8859-7 (Greek), font types 2, 4 and 7, are
+/// not supported; [`CharacterSet::from_font_type`] returns `None` for them.
+///
+/// [`FontAttributes::font_type`]: super::FontAttributes::font_type
+/// [`ObjectPool::validate`]: super::ObjectPool::validate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharacterSet {
+    /// ISO 8859-1
+    Latin1,
+    /// ISO 8859-15, Latin 1 with the euro sign and a few French, Finnish and Estonian letters
+    Latin9,
+    /// ISO 8859-5
+    Cyrillic,
+}
+
+impl CharacterSet {
+    /// Get the character set of a font type, `None` for the font types that are not supported,
+    /// reserved or proprietary.
+    pub fn from_font_type(font_type: u8) -> Option<Self> {
+        match font_type {
+            0 => Some(CharacterSet::Latin1),
+            1 => Some(CharacterSet::Latin9),
+            5 => Some(CharacterSet::Cyrillic),
+            _ => None,
+        }
+    }
+
+    /// Get the character for `byte`.
+    pub fn decode_byte(self, byte: u8) -> char {
+        let c = match (self, byte) {
+            (_, 0x00..=0x9F) | (CharacterSet::Latin1, _) => return byte as char,
+            (CharacterSet::Latin9, 0xA4) => 0x20AC,
+            (CharacterSet::Latin9, 0xA6) => 0x0160,
+            (CharacterSet::Latin9, 0xA8) => 0x0161,
+            (CharacterSet::Latin9, 0xB4) => 0x017D,
+            (CharacterSet::Latin9, 0xB8) => 0x017E,
+            (CharacterSet::Latin9, 0xBC) => 0x0152,
+            (CharacterSet::Latin9, 0xBD) => 0x0153,
+            (CharacterSet::Latin9, 0xBE) => 0x0178,
+            (CharacterSet::Latin9, _) => return byte as char,
+            (CharacterSet::Cyrillic, 0xA0 | 0xAD) => return byte as char,
+            (CharacterSet::Cyrillic, 0xF0) => 0x2116,
+            (CharacterSet::Cyrillic, 0xFD) => 0x00A7,
+            (CharacterSet::Cyrillic, _) => 0x0360 + byte as u32,
+        };
+        // All code points above are valid characters
+        char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+
+    /// Get the byte for `c`, `None` if the character set does not contain it.
+    pub fn encode_char(self, c: char) -> Option<u8> {
+        match u8::try_from(c) {
+            Ok(byte @ 0x00..=0x9F) => Some(byte),
+            _ => (0xA0..=0xFF).find(|&byte| self.decode_byte(byte) == c),
+        }
+    }
+
+    /// Get the text of `bytes`, every byte decodes to exactly one character.
+    pub fn decode(self, bytes: &[u8]) -> String {
+        bytes.iter().map(|&byte| self.decode_byte(byte)).collect()
+    }
+
+    /// Encode `text`, `None` if the character set does not contain every character.
+    pub fn encode(self, text: &str) -> Option<Vec<u8>> {
+        text.chars().map(|c| self.encode_char(c)).collect()
+    }
+
+    /// Encode `text`, replacing characters the character set does not contain with `?`.
+    pub fn encode_lossy(self, text: &str) -> Vec<u8> {
+        text.chars()
+            .map(|c| self.encode_char(c).unwrap_or(b'?'))
+            .collect()
+    }
+
+    /// Get the text of a string as read from an object pool.
+    pub fn decode_str(self, value: &str) -> String {
+        self.decode(&CharacterSet::Latin1.encode_lossy(value))
+    }
+
+    /// Get the string to store in an object pool for `text`, `None` if the character set does
+    /// not contain every character.
+    pub fn encode_str(self, text: &str) -> Option<String> {
+        Some(CharacterSet::Latin1.decode(&self.encode(text)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_character_sets() {
+        for set in [
+            CharacterSet::Latin1,
+            CharacterSet::Latin9,
+            CharacterSet::Cyrillic,
+        ] {
+            for byte in 0..=u8::MAX {
+                assert_eq!(Some(byte), set.encode_char(set.decode_byte(byte)));
+            }
+        }
+
+        assert_eq!("é", CharacterSet::Latin1.decode(&[0xE9]));
+        assert_eq!("€é", CharacterSet::Latin9.decode(&[0xA4, 0xE9]));
+        assert_eq!("Жж№", CharacterSet::Cyrillic.decode(&[0xB6, 0xD6, 0xF0]));
+        assert_eq!(None, CharacterSet::Latin1.encode("€"));
+        assert_eq!(b"a?".to_vec(), CharacterSet::Cyrillic.encode_lossy("aé"));
+
+        let stored = CharacterSet::Latin9.encode_str("5 €").unwrap();
+        assert_eq!("5 \u{A4}", stored);
+        assert_eq!("5 €", CharacterSet::Latin9.decode_str(&stored));
+        assert_eq!(Some(CharacterSet::Latin9), CharacterSet::from_font_type(1));
+        assert_eq!(None, CharacterSet::from_font_type(2));
+        assert_eq!(None, CharacterSet::from_font_type(0xFF));
+    }
+}
@@ -0,0 +1,870 @@
+//! Virtual Terminal function commands, as sent between a working set and a VT.
+//!
+//! Every command starts with its function code, followed by the parameters in little endian.
+//! Commands shorter than a CAN frame are padded with `0xFF` to 8 bytes.
+//!
+//! ```rust
+//! use ag_iso_stack::object_pool::commands::{ChangeNumericValue, Command};
+//!
+//! let command = ChangeNumericValue {
+//!     id: 21000.into(),
+//!     value: 5,
+//! };
+//! let bytes = command.to_bytes();
+//! assert_eq!(vec![0xA8, 0x08, 0x52, 0xFF, 0x05, 0x00, 0x00, 0x00], bytes);
+//! assert_eq!(Ok(command), ChangeNumericValue::from_bytes(&bytes));
+//! ```
+
+use alloc::{string::String, vec::Vec};
+
+use super::{CharacterSet, ObjectId, VtCommand};
+
+/// Errors when decoding a [`Command`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandError {
+    /// The data ends before the command does
+    DataEmpty,
+    /// The data starts with the function code of another command
+    UnexpectedFunction(u8),
+    /// A parameter holds a value that is not defined for the command
+    InvalidValue,
+}
+
+/// A VT function command with its byte encoding.
+pub trait Command: Sized {
+    /// The function code, the first byte of the command
+    const FUNCTION: u8;
+
+    fn to_bytes(&self) -> Vec<u8>;
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError>;
+}
+
+/// Check the function code and length of `data`, returning the parameters.
+fn parameters<C: Command>(data: &[u8], len: usize) -> Result<&[u8], CommandError> {
+    match data.first() {
+        None => Err(CommandError::DataEmpty),
+        Some(&function) if function != C::FUNCTION => {
+            Err(CommandError::UnexpectedFunction(function))
+        }
+        Some(_) => data.get(1..len).ok_or(CommandError::DataEmpty),
+    }
+}
+
+/// Pad a command to the length of a CAN frame.
+fn pad(mut data: Vec<u8>) -> Vec<u8> {
+    if data.len() < 8 {
+        data.resize(8, 0xFF);
+    }
+    data
+}
+
+fn object_id(data: &[u8]) -> ObjectId {
+    ObjectId::from_le_bytes([data[0], data[1]])
+}
+
+fn bool_value(value: u8) -> Result<bool, CommandError> {
+    match value {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(CommandError::InvalidValue),
+    }
+}
+
+/// Show or hide a `Container`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HideShowObject {
+    pub id: ObjectId,
+    pub show: bool,
+}
+
+impl Command for HideShowObject {
+    const FUNCTION: u8 = 0xA0;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION];
+        data.extend(self.id.to_le_bytes());
+        data.push(self.show.into());
+        pad(data)
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 4)?;
+        Ok(HideShowObject {
+            id: object_id(p),
+            show: bool_value(p[2])?,
+        })
+    }
+}
+
+/// Enable or disable an input object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnableDisableObject {
+    pub id: ObjectId,
+    pub enable: bool,
+}
+
+impl Command for EnableDisableObject {
+    const FUNCTION: u8 = 0xA1;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION];
+        data.extend(self.id.to_le_bytes());
+        data.push(self.enable.into());
+        pad(data)
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 4)?;
+        Ok(EnableDisableObject {
+            id: object_id(p),
+            enable: bool_value(p[2])?,
+        })
+    }
+}
+
+/// Select an input object, or deselect all objects with [`ObjectId::NULL`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelectInputObject {
+    pub id: ObjectId,
+    /// Open the object for data input instead of only giving it focus
+    pub open_for_input: bool,
+}
+
+impl Command for SelectInputObject {
+    const FUNCTION: u8 = 0xA2;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION];
+        data.extend(self.id.to_le_bytes());
+        data.push(if self.open_for_input { 0x00 } else { 0xFF });
+        pad(data)
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 4)?;
+        Ok(SelectInputObject {
+            id: object_id(p),
+            open_for_input: match p[2] {
+                0x00 => true,
+                0xFF => false,
+                _ => return Err(CommandError::InvalidValue),
+            },
+        })
+    }
+}
+
+/// Move a child object relative to its current position within `parent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeChildLocation {
+    pub parent: ObjectId,
+    pub child: ObjectId,
+    /// The change in x position, from -127 to 128
+    pub dx: i16,
+    /// The change in y position, from -127 to 128
+    pub dy: i16,
+}
+
+impl Command for ChangeChildLocation {
+    const FUNCTION: u8 = 0xA5;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION];
+        data.extend(self.parent.to_le_bytes());
+        data.extend(self.child.to_le_bytes());
+        data.push((i32::from(self.dx) + 127).clamp(0, 255) as u8);
+        data.push((i32::from(self.dy) + 127).clamp(0, 255) as u8);
+        pad(data)
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 7)?;
+        Ok(ChangeChildLocation {
+            parent: object_id(p),
+            child: object_id(&p[2..]),
+            dx: i16::from(p[4]) - 127,
+            dy: i16::from(p[5]) - 127,
+        })
+    }
+}
+
+/// Change the size of an object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeSize {
+    pub id: ObjectId,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Command for ChangeSize {
+    const FUNCTION: u8 = 0xA6;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION];
+        data.extend(self.id.to_le_bytes());
+        data.extend(self.width.to_le_bytes());
+        data.extend(self.height.to_le_bytes());
+        pad(data)
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 7)?;
+        Ok(ChangeSize {
+            id: object_id(p),
+            width: u16::from_le_bytes([p[2], p[3]]),
+            height: u16::from_le_bytes([p[4], p[5]]),
+        })
+    }
+}
+
+/// Change the background colour of an object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeBackgroundColour {
+    pub id: ObjectId,
+    pub colour: u8,
+}
+
+impl Command for ChangeBackgroundColour {
+    const FUNCTION: u8 = 0xA7;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION];
+        data.extend(self.id.to_le_bytes());
+        data.push(self.colour);
+        pad(data)
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 4)?;
+        Ok(ChangeBackgroundColour {
+            id: object_id(p),
+            colour: p[2],
+        })
+    }
+}
+
+/// Change the value of an object, sent by the working set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeNumericValue {
+    pub id: ObjectId,
+    pub value: u32,
+}
+
+impl Command for ChangeNumericValue {
+    const FUNCTION: u8 = 0xA8;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION];
+        data.extend(self.id.to_le_bytes());
+        data.push(0xFF);
+        data.extend(self.value.to_le_bytes());
+        data
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 8)?;
+        Ok(ChangeNumericValue {
+            id: object_id(p),
+            value: u32::from_le_bytes([p[3], p[4], p[5], p[6]]),
+        })
+    }
+}
+
+/// Change the active mask of a working set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeActiveMask {
+    pub working_set: ObjectId,
+    pub mask: ObjectId,
+}
+
+impl Command for ChangeActiveMask {
+    const FUNCTION: u8 = 0xAD;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION];
+        data.extend(self.working_set.to_le_bytes());
+        data.extend(self.mask.to_le_bytes());
+        pad(data)
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 5)?;
+        Ok(ChangeActiveMask {
+            working_set: object_id(p),
+            mask: object_id(&p[2..]),
+        })
+    }
+}
+
+/// The type of mask in a [`ChangeSoftKeyMask`] command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaskType {
+    DataMask = 1,
+    AlarmMask = 2,
+}
+
+/// Change the soft key mask of a data or alarm mask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeSoftKeyMask {
+    pub mask_type: MaskType,
+    pub mask: ObjectId,
+    pub soft_key_mask: ObjectId,
+}
+
+impl Command for ChangeSoftKeyMask {
+    const FUNCTION: u8 = 0xAE;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION, self.mask_type as u8];
+        data.extend(self.mask.to_le_bytes());
+        data.extend(self.soft_key_mask.to_le_bytes());
+        pad(data)
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 6)?;
+        Ok(ChangeSoftKeyMask {
+            mask_type: match p[0] {
+                1 => MaskType::DataMask,
+                2 => MaskType::AlarmMask,
+                _ => return Err(CommandError::InvalidValue),
+            },
+            mask: object_id(&p[1..]),
+            soft_key_mask: object_id(&p[3..]),
+        })
+    }
+}
+
+/// Change an attribute of an object by its attribute id (AID).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeAttribute {
+    pub id: ObjectId,
+    pub aid: u8,
+    pub value: u32,
+}
+
+impl Command for ChangeAttribute {
+    const FUNCTION: u8 = 0xAF;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION];
+        data.extend(self.id.to_le_bytes());
+        data.push(self.aid);
+        data.extend(self.value.to_le_bytes());
+        data
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 8)?;
+        Ok(ChangeAttribute {
+            id: object_id(p),
+            aid: p[2],
+            value: u32::from_le_bytes([p[3], p[4], p[5], p[6]]),
+        })
+    }
+}
+
+/// Change the value of a string object, sent by the working set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeStringValue {
+    pub id: ObjectId,
+    pub value: String,
+}
+
+impl Command for ChangeStringValue {
+    const FUNCTION: u8 = 0xB3;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION];
+        data.extend(self.id.to_le_bytes());
+        let value = CharacterSet::Latin1.encode_lossy(&self.value);
+        let len = value.len().min(u16::MAX.into());
+        data.extend((len as u16).to_le_bytes());
+        data.extend(&value[..len]);
+        pad(data)
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 5)?;
+        let len = u16::from_le_bytes([p[2], p[3]]) as usize;
+        let value = data.get(5..5 + len).ok_or(CommandError::DataEmpty)?;
+        Ok(ChangeStringValue {
+            id: object_id(p),
+            value: CharacterSet::Latin1.decode(value),
+        })
+    }
+}
+
+/// Move a child object to a position relative to the top left corner of `parent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeChildPosition {
+    pub parent: ObjectId,
+    pub child: ObjectId,
+    pub x: i16,
+    pub y: i16,
+}
+
+impl Command for ChangeChildPosition {
+    const FUNCTION: u8 = 0xB4;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION];
+        data.extend(self.parent.to_le_bytes());
+        data.extend(self.child.to_le_bytes());
+        data.extend(self.x.to_le_bytes());
+        data.extend(self.y.to_le_bytes());
+        data
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 9)?;
+        Ok(ChangeChildPosition {
+            parent: object_id(p),
+            child: object_id(&p[2..]),
+            x: i16::from_le_bytes([p[4], p[5]]),
+            y: i16::from_le_bytes([p[6], p[7]]),
+        })
+    }
+}
+
+/// Execute a macro with an 8 bit object id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecuteMacro {
+    pub id: u8,
+}
+
+impl Command for ExecuteMacro {
+    const FUNCTION: u8 = 0xBE;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        pad(vec![Self::FUNCTION, self.id])
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 2)?;
+        Ok(ExecuteMacro { id: p[0] })
+    }
+}
+
+/// The state of a key or button in a [`SoftKeyActivation`] or [`ButtonActivation`] message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyActivationCode {
+    Released = 0,
+    Pressed = 1,
+    StillPressed = 2,
+    Aborted = 3,
+}
+
+impl TryFrom<u8> for KeyActivationCode {
+    type Error = CommandError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(KeyActivationCode::Released),
+            1 => Ok(KeyActivationCode::Pressed),
+            2 => Ok(KeyActivationCode::StillPressed),
+            3 => Ok(KeyActivationCode::Aborted),
+            _ => Err(CommandError::InvalidValue),
+        }
+    }
+}
+
+/// A soft key was pressed or released, sent by the VT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoftKeyActivation {
+    pub code: KeyActivationCode,
+    /// The `Key` object
+    pub id: ObjectId,
+    /// The visible data or alarm mask, or the `KeyGroup` for a key in a key group
+    pub parent: ObjectId,
+    pub key_code: u8,
+}
+
+impl Command for SoftKeyActivation {
+    const FUNCTION: u8 = 0x00;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION, self.code as u8];
+        data.extend(self.id.to_le_bytes());
+        data.extend(self.parent.to_le_bytes());
+        data.push(self.key_code);
+        pad(data)
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 7)?;
+        Ok(SoftKeyActivation {
+            code: p[0].try_into()?,
+            id: object_id(&p[1..]),
+            parent: object_id(&p[3..]),
+            key_code: p[5],
+        })
+    }
+}
+
+/// A button was pressed or released, sent by the VT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ButtonActivation {
+    pub code: KeyActivationCode,
+    /// The `Button` object
+    pub id: ObjectId,
+    /// The parent of the button
+    pub parent: ObjectId,
+    pub key_code: u8,
+}
+
+impl Command for ButtonActivation {
+    const FUNCTION: u8 = 0x01;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION, self.code as u8];
+        data.extend(self.id.to_le_bytes());
+        data.extend(self.parent.to_le_bytes());
+        data.push(self.key_code);
+        pad(data)
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 7)?;
+        Ok(ButtonActivation {
+            code: p[0].try_into()?,
+            id: object_id(&p[1..]),
+            parent: object_id(&p[3..]),
+            key_code: p[5],
+        })
+    }
+}
+
+/// The operator entered a numeric value, sent by the VT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VtChangeNumericValue {
+    pub id: ObjectId,
+    pub value: u32,
+}
+
+impl Command for VtChangeNumericValue {
+    const FUNCTION: u8 = 0x05;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION];
+        data.extend(self.id.to_le_bytes());
+        data.push(0xFF);
+        data.extend(self.value.to_le_bytes());
+        data
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 8)?;
+        Ok(VtChangeNumericValue {
+            id: object_id(p),
+            value: u32::from_le_bytes([p[3], p[4], p[5], p[6]]),
+        })
+    }
+}
+
+/// The operator entered a string, sent by the VT.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VtChangeStringValue {
+    pub id: ObjectId,
+    pub value: String,
+}
+
+impl Command for VtChangeStringValue {
+    const FUNCTION: u8 = 0x08;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![Self::FUNCTION];
+        data.extend(self.id.to_le_bytes());
+        let value = CharacterSet::Latin1.encode_lossy(&self.value);
+        let len = value.len().min(u8::MAX.into());
+        data.push(len as u8);
+        data.extend(&value[..len]);
+        pad(data)
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, CommandError> {
+        let p = parameters::<Self>(data, 4)?;
+        let len = p[2] as usize;
+        let value = data.get(4..4 + len).ok_or(CommandError::DataEmpty)?;
+        Ok(VtChangeStringValue {
+            id: object_id(p),
+            value: CharacterSet::Latin1.decode(value),
+        })
+    }
+}
+
+impl From<HideShowObject> for VtCommand {
+    fn from(value: HideShowObject) -> Self {
+        VtCommand::HideShow {
+            id: value.id,
+            show: value.show,
+        }
+    }
+}
+
+impl From<ChangeNumericValue> for VtCommand {
+    fn from(value: ChangeNumericValue) -> Self {
+        VtCommand::ChangeNumericValue {
+            id: value.id,
+            value: value.value,
+        }
+    }
+}
+
+impl From<ChangeActiveMask> for VtCommand {
+    fn from(value: ChangeActiveMask) -> Self {
+        VtCommand::ChangeActiveMask {
+            working_set: value.working_set,
+            mask: value.mask,
+        }
+    }
+}
+
+impl From<ChangeAttribute> for VtCommand {
+    fn from(value: ChangeAttribute) -> Self {
+        VtCommand::ChangeAttribute {
+            id: value.id,
+            aid: value.aid,
+            value: value.value,
+        }
+    }
+}
+
+impl From<ChangeStringValue> for VtCommand {
+    fn from(value: ChangeStringValue) -> Self {
+        VtCommand::ChangeStringValue {
+            id: value.id,
+            value: value.value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{NumberVariable, Object, ObjectPool};
+    use super::*;
+
+    fn round_trip<C: Command + PartialEq + core::fmt::Debug>(command: C, bytes: &[u8]) {
+        assert_eq!(bytes, command.to_bytes());
+        assert_eq!(Ok(command), C::from_bytes(bytes));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        round_trip(
+            HideShowObject {
+                id: 3000.into(),
+                show: true,
+            },
+            &[0xA0, 0xB8, 0x0B, 0x01, 0xFF, 0xFF, 0xFF, 0xFF],
+        );
+        round_trip(
+            EnableDisableObject {
+                id: 3000.into(),
+                enable: false,
+            },
+            &[0xA1, 0xB8, 0x0B, 0x00, 0xFF, 0xFF, 0xFF, 0xFF],
+        );
+        round_trip(
+            SelectInputObject {
+                id: 9000.into(),
+                open_for_input: true,
+            },
+            &[0xA2, 0x28, 0x23, 0x00, 0xFF, 0xFF, 0xFF, 0xFF],
+        );
+        round_trip(
+            ChangeChildLocation {
+                parent: 1000.into(),
+                child: 3000.into(),
+                dx: -127,
+                dy: 128,
+            },
+            &[0xA5, 0xE8, 0x03, 0xB8, 0x0B, 0x00, 0xFF, 0xFF],
+        );
+        round_trip(
+            ChangeSize {
+                id: 3000.into(),
+                width: 200,
+                height: 300,
+            },
+            &[0xA6, 0xB8, 0x0B, 0xC8, 0x00, 0x2C, 0x01, 0xFF],
+        );
+        round_trip(
+            ChangeBackgroundColour {
+                id: 1000.into(),
+                colour: 12,
+            },
+            &[0xA7, 0xE8, 0x03, 0x0C, 0xFF, 0xFF, 0xFF, 0xFF],
+        );
+        round_trip(
+            ChangeNumericValue {
+                id: 21000.into(),
+                value: 0x12345678,
+            },
+            &[0xA8, 0x08, 0x52, 0xFF, 0x78, 0x56, 0x34, 0x12],
+        );
+        round_trip(
+            ChangeActiveMask {
+                working_set: 0.into(),
+                mask: 1001.into(),
+            },
+            &[0xAD, 0x00, 0x00, 0xE9, 0x03, 0xFF, 0xFF, 0xFF],
+        );
+        round_trip(
+            ChangeSoftKeyMask {
+                mask_type: MaskType::AlarmMask,
+                mask: 2000.into(),
+                soft_key_mask: 4000.into(),
+            },
+            &[0xAE, 0x02, 0xD0, 0x07, 0xA0, 0x0F, 0xFF, 0xFF],
+        );
+        round_trip(
+            ChangeAttribute {
+                id: 12000.into(),
+                aid: 1,
+                value: 80,
+            },
+            &[0xAF, 0xE0, 0x2E, 0x01, 0x50, 0x00, 0x00, 0x00],
+        );
+        round_trip(
+            ChangeStringValue {
+                id: 22000.into(),
+                value: "OK".into(),
+            },
+            &[0xB3, 0xF0, 0x55, 0x02, 0x00, b'O', b'K', 0xFF],
+        );
+        round_trip(
+            ChangeStringValue {
+                id: 22000.into(),
+                value: "Hello".into(),
+            },
+            &[0xB3, 0xF0, 0x55, 0x05, 0x00, b'H', b'e', b'l', b'l', b'o'],
+        );
+        round_trip(
+            ChangeChildPosition {
+                parent: 1000.into(),
+                child: 3000.into(),
+                x: -1,
+                y: 20,
+            },
+            &[0xB4, 0xE8, 0x03, 0xB8, 0x0B, 0xFF, 0xFF, 0x14, 0x00],
+        );
+        round_trip(
+            ExecuteMacro { id: 5 },
+            &[0xBE, 0x05, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+        );
+        round_trip(
+            SoftKeyActivation {
+                code: KeyActivationCode::Pressed,
+                id: 5000.into(),
+                parent: 1000.into(),
+                key_code: 3,
+            },
+            &[0x00, 0x01, 0x88, 0x13, 0xE8, 0x03, 0x03, 0xFF],
+        );
+        round_trip(
+            ButtonActivation {
+                code: KeyActivationCode::Released,
+                id: 6000.into(),
+                parent: 1000.into(),
+                key_code: 7,
+            },
+            &[0x01, 0x00, 0x70, 0x17, 0xE8, 0x03, 0x07, 0xFF],
+        );
+        round_trip(
+            VtChangeNumericValue {
+                id: 9000.into(),
+                value: 42,
+            },
+            &[0x05, 0x28, 0x23, 0xFF, 0x2A, 0x00, 0x00, 0x00],
+        );
+        round_trip(
+            VtChangeStringValue {
+                id: 8000.into(),
+                value: "abc".into(),
+            },
+            &[0x08, 0x40, 0x1F, 0x03, b'a', b'b', b'c', 0xFF],
+        );
+
+        // Out of range values are clamped
+        let data = ChangeChildLocation {
+            parent: 1000.into(),
+            child: 3000.into(),
+            dx: i16::MAX,
+            dy: i16::MIN,
+        }
+        .to_bytes();
+        assert_eq!([0xFF, 0x00], data[5..7]);
+        let command = ChangeChildLocation::from_bytes(&data).unwrap();
+        assert_eq!((128, -127), (command.dx, command.dy));
+
+        let value = "x".repeat(300);
+        let data = VtChangeStringValue {
+            id: 8000.into(),
+            value: value.clone(),
+        }
+        .to_bytes();
+        assert_eq!(4 + 255, data.len());
+        assert_eq!(
+            value[..255],
+            VtChangeStringValue::from_bytes(&data).unwrap().value
+        );
+
+        let value = "x".repeat(70000);
+        let data = ChangeStringValue {
+            id: 22000.into(),
+            value: value.clone(),
+        }
+        .to_bytes();
+        assert_eq!(5 + 65535, data.len());
+        assert_eq!(
+            value[..65535],
+            ChangeStringValue::from_bytes(&data).unwrap().value
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_errors() {
+        assert_eq!(
+            Err(CommandError::DataEmpty),
+            HideShowObject::from_bytes(&[])
+        );
+        assert_eq!(
+            Err(CommandError::DataEmpty),
+            ChangeNumericValue::from_bytes(&[0xA8, 0x08, 0x52, 0xFF, 0x05])
+        );
+        assert_eq!(
+            Err(CommandError::UnexpectedFunction(0xA1)),
+            HideShowObject::from_bytes(&[0xA1, 0xB8, 0x0B, 0x01, 0xFF, 0xFF, 0xFF, 0xFF])
+        );
+        assert_eq!(
+            Err(CommandError::InvalidValue),
+            HideShowObject::from_bytes(&[0xA0, 0xB8, 0x0B, 0x02, 0xFF, 0xFF, 0xFF, 0xFF])
+        );
+        assert_eq!(
+            Err(CommandError::DataEmpty),
+            ChangeStringValue::from_bytes(&[0xB3, 0xF0, 0x55, 0x05, 0x00, b'H', b'e', 0xFF])
+        );
+    }
+
+    #[test]
+    fn test_into_vt_command() {
+        let mut pool = ObjectPool::new();
+        pool.add(Object::NumberVariable(NumberVariable {
+            id: 21000.into(),
+            value: 0,
+        }));
+
+        let command = ChangeNumericValue::from_bytes(&[0xA8, 0x08, 0x52, 0xFF, 5, 0, 0, 0]);
+        assert_eq!(
+            vec![Ok(())],
+            pool.apply_commands(&[command.unwrap().into()])
+        );
+        assert_eq!(
+            Some(&Object::NumberVariable(NumberVariable {
+                id: 21000.into(),
+                value: 5,
+            })),
+            pool.object_by_id(21000.into())
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use alloc::{boxed::Box, collections::BTreeMap};
+
+use super::FontAttributes;
+
+/// The width of characters in a font, used to lay out text.
+///
+/// The standard only defines the size of monospace fonts, see [`Monospace`]. Implement this
+/// trait to describe proportional fonts and register it with a [`FontRegistry`].
+pub trait FontMetrics {
+    /// Get the width of `c` in pixels, `None` if the font size is not supported.
+    fn char_width(&self, font: &FontAttributes, c: char) -> Option<u16>;
+
+    /// Get the width of `text` in pixels, `None` if the font size is not supported.
+    fn text_width(&self, font: &FontAttributes, text: &str) -> Option<u32> {
+        text.chars()
+            .map(|c| self.char_width(font, c).map(u32::from))
+            .sum()
+    }
+}
+
+/// Metrics of the monospace fonts of the standard, every character is
+/// [`FontAttributes::font_dimensions`] wide.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Monospace;
+
+impl FontMetrics for Monospace {
+    fn char_width(&self, font: &FontAttributes, _c: char) -> Option<u16> {
+        Some(font.font_dimensions()?.x)
+    }
+}
+
+/// Font metrics per font type, falling back to [`Monospace`] for font types without metrics.
+#[derive(Default)]
+pub struct FontRegistry {
+    fonts: BTreeMap<u8, Box<dyn FontMetrics>>,
+}
+
+impl FontRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `metrics` for fonts of the given font type, replacing any earlier registration.
+    pub fn register(&mut self, font_type: u8, metrics: impl FontMetrics + 'static) {
+        self.fonts.insert(font_type, Box::new(metrics));
+    }
+
+    /// Get the metrics for a font type, if any were registered.
+    pub fn get(&self, font_type: u8) -> Option<&dyn FontMetrics> {
+        self.fonts.get(&font_type).map(|metrics| metrics.as_ref())
+    }
+}
+
+impl FontMetrics for FontRegistry {
+    fn char_width(&self, font: &FontAttributes, c: char) -> Option<u16> {
+        match self.get(font.font_type) {
+            Some(metrics) => metrics.char_width(font, c),
+            None => Monospace.char_width(font, c),
+        }
+    }
+
+    fn text_width(&self, font: &FontAttributes, text: &str) -> Option<u32> {
+        match self.get(font.font_type) {
+            Some(metrics) => metrics.text_width(font, text),
+            None => Monospace.text_width(font, text),
+        }
+    }
+}
+
+impl core::fmt::Debug for FontRegistry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FontRegistry")
+            .field("font_types", &self.fonts.keys())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// A font with narrow `i`s, at the height of the font size.
+    struct Narrow;
+
+    impl FontMetrics for Narrow {
+        fn char_width(&self, font: &FontAttributes, c: char) -> Option<u16> {
+            let width = font.font_dimensions()?.x;
+            Some(if c == 'i' { width / 4 } else { width })
+        }
+    }
+
+    fn font(font_type: u8) -> FontAttributes {
+        FontAttributes {
+            id: 23000.into(),
+            font_colour: 0,
+            font_size: 1, // 8x8
+            font_type,
+            font_style: 0,
+            macro_refs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_font_registry() {
+        let mut registry = FontRegistry::new();
+        registry.register(0xFF, Narrow);
+
+        assert_eq!(Some(40), Monospace.text_width(&font(0xFF), "iiiii"));
+        assert_eq!(Some(40), registry.text_width(&font(0), "iiiii"));
+        assert_eq!(Some(10), registry.text_width(&font(0xFF), "iiiii"));
+        assert_eq!(Some(2), registry.char_width(&font(0xFF), 'i'));
+        assert_eq!(Some(8), registry.char_width(&font(0xFF), 'W'));
+
+        let mut unsupported = font(0xFF);
+        unsupported.font_size = 0xFF;
+        assert_eq!(None, registry.text_width(&unsupported, "i"));
+    }
+}
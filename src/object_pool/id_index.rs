@@ -0,0 +1,98 @@
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use super::ObjectId;
+
+/// Index from object id to the position of the first object with that id.
+///
+/// With the `std` feature this is a `HashMap`, otherwise a `Vec` sorted by id that is searched
+/// with a binary search.
+#[derive(Debug, Default)]
+pub(super) struct IdIndex {
+    #[cfg(feature = "std")]
+    map: HashMap<u16, usize>,
+    #[cfg(not(feature = "std"))]
+    sorted: Vec<(u16, usize)>,
+}
+
+impl IdIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        #[cfg(feature = "std")]
+        self.map.reserve(additional);
+        #[cfg(not(feature = "std"))]
+        self.sorted.reserve(additional);
+    }
+
+    /// Rebuild the index from the ids in object order.
+    pub fn rebuild(&mut self, ids: impl Iterator<Item = ObjectId>) {
+        #[cfg(feature = "std")]
+        {
+            self.map.clear();
+            for (index, id) in ids.enumerate() {
+                self.map.entry(id.into()).or_insert(index);
+            }
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            self.sorted.clear();
+            self.sorted
+                .extend(ids.enumerate().map(|(index, id)| (id.into(), index)));
+            // The sort is stable, so the first object of each id is kept
+            self.sorted.sort_by_key(|(id, _)| *id);
+            self.sorted.dedup_by_key(|(id, _)| *id);
+        }
+    }
+
+    /// Add the position of an object, unless an earlier object has the same id.
+    pub fn insert(&mut self, id: ObjectId, index: usize) {
+        #[cfg(feature = "std")]
+        self.map.entry(id.into()).or_insert(index);
+        #[cfg(not(feature = "std"))]
+        if let Err(pos) = self.search(id) {
+            self.sorted.insert(pos, (id.into(), index));
+        }
+    }
+
+    pub fn get(&self, id: ObjectId) -> Option<usize> {
+        #[cfg(feature = "std")]
+        return self.map.get(&id.into()).copied();
+        #[cfg(not(feature = "std"))]
+        return self.search(id).ok().map(|pos| self.sorted[pos].1);
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn search(&self, id: ObjectId) -> Result<usize, usize> {
+        let id = u16::from(id);
+        self.sorted.binary_search_by_key(&id, |(key, _)| *key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id_index() {
+        let mut index = IdIndex::new();
+        index.rebuild([3000, 1000, 3000, 2000].into_iter().map(ObjectId::from));
+        assert_eq!(Some(0), index.get(3000.into()));
+        assert_eq!(Some(1), index.get(1000.into()));
+        assert_eq!(Some(3), index.get(2000.into()));
+        assert_eq!(None, index.get(4000.into()));
+
+        index.insert(4000.into(), 4);
+        index.insert(1000.into(), 5);
+        assert_eq!(Some(4), index.get(4000.into()));
+        assert_eq!(Some(1), index.get(1000.into()));
+
+        index.rebuild(core::iter::empty());
+        assert_eq!(None, index.get(1000.into()));
+    }
+}
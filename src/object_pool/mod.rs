@@ -1,19 +1,156 @@
+mod attributes;
+pub mod commands;
+mod id_index;
+pub mod prelude;
 pub mod reader;
 pub mod writer;
 
 use alloc::{string::String, vec::Vec};
+use bitflags::bitflags;
 
-use crate::name::Name;
+use crate::network_management::name::NAME as Name;
 
+mod builder;
+pub use builder::{BuilderError, ButtonBuilder};
+mod charset;
+pub use charset::CharacterSet;
+mod font_metrics;
+pub use font_metrics::{FontMetrics, FontRegistry, Monospace};
 mod object_pool;
-pub use object_pool::ObjectPool;
+pub use object_pool::{
+    BufferTooSmall, ObjectPool, PatchSummary, PoolChunk, PoolHeader, PoolStats, ResolutionPlan,
+};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ParseError {
     DataEmpty,
     UnknownObjectType,
+    /// The data holds a different type of object than requested
+    UnexpectedObjectType(ObjectType),
+    /// An `OutputPolygon` declares more points than the data holds
+    PointCountMismatch {
+        declared: usize,
+        found: usize,
+    },
+}
+
+/// Options for [`ObjectPool::from_iop_with`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Reject data that can not be parsed instead of skipping it
+    pub strict: bool,
+}
+
+/// A non-fatal issue found while parsing an object pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// The object the issue applies to, [`ObjectId::NULL`] if it is unknown
+    pub id: ObjectId,
+    pub message: String,
+}
+
+/// The non-fatal issues found by [`ObjectPool::from_iop_with`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ParseReport {
+    pub warnings: Vec<ParseWarning>,
+}
+
+impl ParseReport {
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The pool does not contain a `WorkingSet` object
+    MissingWorkingSet,
+    /// The pool contains more than one `WorkingSet` object
+    MultipleWorkingSets(Vec<ObjectId>),
+    /// A `SoftKeyMask` lists more keys than the VT supports
+    TooManySoftKeys { id: ObjectId, count: usize, max: u8 },
+    /// A `GraphicData` object uses a format that is not defined
+    UnknownGraphicDataFormat { id: ObjectId, format: u8 },
+    /// The data of a `GraphicData` object does not match its format
+    InvalidGraphicData(ObjectId),
+    /// An object type is not supported by the VT version
+    UnsupportedObject {
+        id: ObjectId,
+        object_type: ObjectType,
+        version: VtVersion,
+    },
+    /// The active mask of a `WorkingSet` is missing or not a `DataMask` or `AlarmMask`
+    InvalidActiveMask {
+        id: ObjectId,
+        active_mask: ObjectId,
+        found: Option<ObjectType>,
+    },
+    /// Objects reference ids that are not in the pool, as pairs of the referencing object and
+    /// the missing id
+    DanglingReferences(Vec<(ObjectId, ObjectId)>),
+    /// An `OutputPolygon` has fewer than 3 or more than 255 points
+    InvalidPointCount { id: ObjectId, count: usize },
+    /// The last command of a `Macro` is cut off, `offset` is where that command starts
+    TruncatedMacro { id: ObjectId, offset: usize },
+    /// A `PictureGraphic` object uses a format that is not defined
+    UnknownPictureGraphicFormat { id: ObjectId, format: u8 },
+    /// The data of a `PictureGraphic` holds fewer bytes than its size and format need, `found`
+    /// counts the bytes after undoing the run-length encoding
+    InvalidPictureGraphicSize {
+        id: ObjectId,
+        expected: usize,
+        found: usize,
+    },
+    /// More than one object has this id
+    DuplicateId(ObjectId),
+    /// Objects reference each other in a loop, listed from the first object of the pool that is
+    /// part of it
+    ReferenceCycle(Vec<ObjectId>),
+    /// A string holds a character above U+00FF, which is written as `?`. Text in the character
+    /// set of the font is stored with [`CharacterSet::encode_str`]
+    UnencodableString { id: ObjectId, character: char },
+}
+
+/// Errors a VT reports when changing an object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VtError {
+    /// The object does not exist or does not support the command
+    InvalidObjectId,
+    /// The object type has no attribute with the given attribute id, or it is read-only
+    InvalidAttributeId,
+    /// The value is out of range for the attribute or object
+    InvalidValue,
+    /// The string is longer than the object can display
+    StringTooLong,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// A VT command that changes the object pool, see [`ObjectPool::apply_commands`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum VtCommand {
+    ChangeNumericValue {
+        id: ObjectId,
+        value: u32,
+    },
+    ChangeStringValue {
+        id: ObjectId,
+        value: String,
+    },
+    ChangeActiveMask {
+        working_set: ObjectId,
+        mask: ObjectId,
+    },
+    ChangeAttribute {
+        id: ObjectId,
+        aid: u8,
+        value: u32,
+    },
+    HideShow {
+        id: ObjectId,
+        show: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ObjectType {
     WorkingSet = 0,
     DataMask = 1,
@@ -66,6 +203,142 @@ pub enum ObjectType {
     ScalesGraphic = 48,
 }
 
+impl ObjectType {
+    /// Every object type, in order of their discriminant.
+    pub const ALL: &'static [ObjectType] = &[
+        ObjectType::WorkingSet,
+        ObjectType::DataMask,
+        ObjectType::AlarmMask,
+        ObjectType::Container,
+        ObjectType::SoftKeyMask,
+        ObjectType::Key,
+        ObjectType::Button,
+        ObjectType::InputBoolean,
+        ObjectType::InputString,
+        ObjectType::InputNumber,
+        ObjectType::InputList,
+        ObjectType::OutputString,
+        ObjectType::OutputNumber,
+        ObjectType::OutputLine,
+        ObjectType::OutputRectangle,
+        ObjectType::OutputEllipse,
+        ObjectType::OutputPolygon,
+        ObjectType::OutputMeter,
+        ObjectType::OutputLinearBarGraph,
+        ObjectType::OutputArchedBarGraph,
+        ObjectType::PictureGraphic,
+        ObjectType::NumberVariable,
+        ObjectType::StringVariable,
+        ObjectType::FontAttributes,
+        ObjectType::LineAttributes,
+        ObjectType::FillAttributes,
+        ObjectType::InputAttributes,
+        ObjectType::ObjectPointer,
+        ObjectType::Macro,
+        ObjectType::AuxiliaryFunctionType1,
+        ObjectType::AuxiliaryInputType1,
+        ObjectType::AuxiliaryFunctionType2,
+        ObjectType::AuxiliaryInputType2,
+        ObjectType::AuxiliaryControlDesignatorType2,
+        ObjectType::WindowMask,
+        ObjectType::KeyGroup,
+        ObjectType::GraphicsContext,
+        ObjectType::OutputList,
+        ObjectType::ExtendedInputAttributes,
+        ObjectType::ColourMap,
+        ObjectType::ObjectLabelReferenceList,
+        ObjectType::ExternalObjectDefinition,
+        ObjectType::ExternalReferenceName,
+        ObjectType::ExternalObjectPointer,
+        ObjectType::Animation,
+        ObjectType::ColourPalette,
+        ObjectType::GraphicData,
+        ObjectType::WorkingSetSpecialControls,
+        ObjectType::ScalesGraphic,
+    ];
+
+    /// The first VT version that supports this object type.
+    pub fn min_vt_version(&self) -> VtVersion {
+        match self.discriminant() {
+            0..=30 => VtVersion::V2,
+            31..=33 => VtVersion::V3,
+            34..=40 => VtVersion::V4,
+            41..=44 => VtVersion::V5,
+            // ColourPalette, GraphicData, WorkingSetSpecialControls and ScalesGraphic
+            45..=u8::MAX => VtVersion::V6,
+        }
+    }
+
+    /// The value of this object type in a serialized pool.
+    pub const fn discriminant(self) -> u8 {
+        self as u8
+    }
+
+    /// The name of this object type, as written in the variant identifier.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ObjectType::WorkingSet => "WorkingSet",
+            ObjectType::DataMask => "DataMask",
+            ObjectType::AlarmMask => "AlarmMask",
+            ObjectType::Container => "Container",
+            ObjectType::SoftKeyMask => "SoftKeyMask",
+            ObjectType::Key => "Key",
+            ObjectType::Button => "Button",
+            ObjectType::InputBoolean => "InputBoolean",
+            ObjectType::InputString => "InputString",
+            ObjectType::InputNumber => "InputNumber",
+            ObjectType::InputList => "InputList",
+            ObjectType::OutputString => "OutputString",
+            ObjectType::OutputNumber => "OutputNumber",
+            ObjectType::OutputLine => "OutputLine",
+            ObjectType::OutputRectangle => "OutputRectangle",
+            ObjectType::OutputEllipse => "OutputEllipse",
+            ObjectType::OutputPolygon => "OutputPolygon",
+            ObjectType::OutputMeter => "OutputMeter",
+            ObjectType::OutputLinearBarGraph => "OutputLinearBarGraph",
+            ObjectType::OutputArchedBarGraph => "OutputArchedBarGraph",
+            ObjectType::PictureGraphic => "PictureGraphic",
+            ObjectType::NumberVariable => "NumberVariable",
+            ObjectType::StringVariable => "StringVariable",
+            ObjectType::FontAttributes => "FontAttributes",
+            ObjectType::LineAttributes => "LineAttributes",
+            ObjectType::FillAttributes => "FillAttributes",
+            ObjectType::InputAttributes => "InputAttributes",
+            ObjectType::ObjectPointer => "ObjectPointer",
+            ObjectType::Macro => "Macro",
+            ObjectType::AuxiliaryFunctionType1 => "AuxiliaryFunctionType1",
+            ObjectType::AuxiliaryInputType1 => "AuxiliaryInputType1",
+            ObjectType::AuxiliaryFunctionType2 => "AuxiliaryFunctionType2",
+            ObjectType::AuxiliaryInputType2 => "AuxiliaryInputType2",
+            ObjectType::AuxiliaryControlDesignatorType2 => "AuxiliaryControlDesignatorType2",
+            ObjectType::WindowMask => "WindowMask",
+            ObjectType::KeyGroup => "KeyGroup",
+            ObjectType::GraphicsContext => "GraphicsContext",
+            ObjectType::OutputList => "OutputList",
+            ObjectType::ExtendedInputAttributes => "ExtendedInputAttributes",
+            ObjectType::ColourMap => "ColourMap",
+            ObjectType::ObjectLabelReferenceList => "ObjectLabelReferenceList",
+            ObjectType::ExternalObjectDefinition => "ExternalObjectDefinition",
+            ObjectType::ExternalReferenceName => "ExternalReferenceName",
+            ObjectType::ExternalObjectPointer => "ExternalObjectPointer",
+            ObjectType::Animation => "Animation",
+            ObjectType::ColourPalette => "ColourPalette",
+            ObjectType::GraphicData => "GraphicData",
+            ObjectType::WorkingSetSpecialControls => "WorkingSetSpecialControls",
+            ObjectType::ScalesGraphic => "ScalesGraphic",
+        }
+    }
+
+    /// Get the object type with the given name, matched case-sensitively against
+    /// [`ObjectType::name`].
+    pub fn from_name(name: &str) -> Option<ObjectType> {
+        ObjectType::ALL
+            .iter()
+            .copied()
+            .find(|object_type| object_type.name() == name)
+    }
+}
+
 impl TryFrom<u8> for ObjectType {
     type Error = ParseError;
 
@@ -181,7 +454,53 @@ impl From<ObjectType> for u8 {
     }
 }
 
-#[derive(Debug)]
+/// The version of a VT, which determines the object types it supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VtVersion {
+    V2,
+    V3,
+    V4,
+    V5,
+    V6,
+    Reserved(u8),
+}
+
+impl VtVersion {
+    /// Check if a VT of this version supports the object type.
+    ///
+    /// Reserved versions above 6 are treated as supporting every object type.
+    pub fn supports(self, object_type: ObjectType) -> bool {
+        u8::from(self) >= u8::from(object_type.min_vt_version())
+    }
+}
+
+impl From<u8> for VtVersion {
+    fn from(value: u8) -> Self {
+        match value {
+            2 => VtVersion::V2,
+            3 => VtVersion::V3,
+            4 => VtVersion::V4,
+            5 => VtVersion::V5,
+            6 => VtVersion::V6,
+            _ => VtVersion::Reserved(value),
+        }
+    }
+}
+
+impl From<VtVersion> for u8 {
+    fn from(value: VtVersion) -> Self {
+        match value {
+            VtVersion::V2 => 2,
+            VtVersion::V3 => 3,
+            VtVersion::V4 => 4,
+            VtVersion::V5 => 5,
+            VtVersion::V6 => 6,
+            VtVersion::Reserved(v) => v,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Object {
     WorkingSet(WorkingSet),
     DataMask(DataMask),
@@ -234,6 +553,153 @@ pub enum Object {
     ScalesGraphic(ScalesGraphic),
 }
 
+/// Unwrap an [`Object`] into the struct of its variant, giving the object back on a mismatch.
+macro_rules! impl_try_from_object {
+    ($($object:ident),* $(,)?) => {
+        $(
+            impl TryFrom<Object> for $object {
+                type Error = Object;
+
+                fn try_from(value: Object) -> Result<Self, Self::Error> {
+                    match value {
+                        Object::$object(o) => Ok(o),
+                        other => Err(other),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_object! {
+    WorkingSet,
+    DataMask,
+    AlarmMask,
+    Container,
+    SoftKeyMask,
+    Key,
+    Button,
+    InputBoolean,
+    InputString,
+    InputNumber,
+    InputList,
+    OutputString,
+    OutputNumber,
+    OutputLine,
+    OutputRectangle,
+    OutputEllipse,
+    OutputPolygon,
+    OutputMeter,
+    OutputLinearBarGraph,
+    OutputArchedBarGraph,
+    PictureGraphic,
+    NumberVariable,
+    StringVariable,
+    FontAttributes,
+    LineAttributes,
+    FillAttributes,
+    InputAttributes,
+    ObjectPointer,
+    Macro,
+    AuxiliaryFunctionType1,
+    AuxiliaryInputType1,
+    AuxiliaryFunctionType2,
+    AuxiliaryInputType2,
+    AuxiliaryControlDesignatorType2,
+    WindowMask,
+    KeyGroup,
+    GraphicsContext,
+    OutputList,
+    ExtendedInputAttributes,
+    ColourMap,
+    ObjectLabelReferenceList,
+    ExternalObjectDefinition,
+    ExternalReferenceName,
+    ExternalObjectPointer,
+    Animation,
+    ColourPalette,
+    GraphicData,
+    WorkingSetSpecialControls,
+    ScalesGraphic,
+}
+
+/// Borrow the struct of an [`Object`] variant, `None` for other variants.
+macro_rules! impl_object_accessors {
+    ($($object:ident => $as_ref:ident, $as_mut:ident),* $(,)?) => {
+        impl Object {
+            $(
+                pub fn $as_ref(&self) -> Option<&$object> {
+                    match self {
+                        Object::$object(o) => Some(o),
+                        _ => None,
+                    }
+                }
+
+                pub fn $as_mut(&mut self) -> Option<&mut $object> {
+                    match self {
+                        Object::$object(o) => Some(o),
+                        _ => None,
+                    }
+                }
+            )*
+        }
+    };
+}
+
+impl_object_accessors! {
+    WorkingSet => as_working_set, as_working_set_mut,
+    DataMask => as_data_mask, as_data_mask_mut,
+    AlarmMask => as_alarm_mask, as_alarm_mask_mut,
+    Container => as_container, as_container_mut,
+    SoftKeyMask => as_soft_key_mask, as_soft_key_mask_mut,
+    Key => as_key, as_key_mut,
+    Button => as_button, as_button_mut,
+    InputBoolean => as_input_boolean, as_input_boolean_mut,
+    InputString => as_input_string, as_input_string_mut,
+    InputNumber => as_input_number, as_input_number_mut,
+    InputList => as_input_list, as_input_list_mut,
+    OutputString => as_output_string, as_output_string_mut,
+    OutputNumber => as_output_number, as_output_number_mut,
+    OutputLine => as_output_line, as_output_line_mut,
+    OutputRectangle => as_output_rectangle, as_output_rectangle_mut,
+    OutputEllipse => as_output_ellipse, as_output_ellipse_mut,
+    OutputPolygon => as_output_polygon, as_output_polygon_mut,
+    OutputMeter => as_output_meter, as_output_meter_mut,
+    OutputLinearBarGraph => as_output_linear_bar_graph, as_output_linear_bar_graph_mut,
+    OutputArchedBarGraph => as_output_arched_bar_graph, as_output_arched_bar_graph_mut,
+    PictureGraphic => as_picture_graphic, as_picture_graphic_mut,
+    NumberVariable => as_number_variable, as_number_variable_mut,
+    StringVariable => as_string_variable, as_string_variable_mut,
+    FontAttributes => as_font_attributes, as_font_attributes_mut,
+    LineAttributes => as_line_attributes, as_line_attributes_mut,
+    FillAttributes => as_fill_attributes, as_fill_attributes_mut,
+    InputAttributes => as_input_attributes, as_input_attributes_mut,
+    ObjectPointer => as_object_pointer, as_object_pointer_mut,
+    Macro => as_macro, as_macro_mut,
+    AuxiliaryFunctionType1 => as_auxiliary_function_type1, as_auxiliary_function_type1_mut,
+    AuxiliaryInputType1 => as_auxiliary_input_type1, as_auxiliary_input_type1_mut,
+    AuxiliaryFunctionType2 => as_auxiliary_function_type2, as_auxiliary_function_type2_mut,
+    AuxiliaryInputType2 => as_auxiliary_input_type2, as_auxiliary_input_type2_mut,
+    AuxiliaryControlDesignatorType2 =>
+        as_auxiliary_control_designator_type2, as_auxiliary_control_designator_type2_mut,
+    WindowMask => as_window_mask, as_window_mask_mut,
+    KeyGroup => as_key_group, as_key_group_mut,
+    GraphicsContext => as_graphics_context, as_graphics_context_mut,
+    OutputList => as_output_list, as_output_list_mut,
+    ExtendedInputAttributes => as_extended_input_attributes, as_extended_input_attributes_mut,
+    ColourMap => as_colour_map, as_colour_map_mut,
+    ObjectLabelReferenceList => as_object_label_reference_list, as_object_label_reference_list_mut,
+    ExternalObjectDefinition => as_external_object_definition, as_external_object_definition_mut,
+    ExternalReferenceName => as_external_reference_name, as_external_reference_name_mut,
+    ExternalObjectPointer => as_external_object_pointer, as_external_object_pointer_mut,
+    Animation => as_animation, as_animation_mut,
+    ColourPalette => as_colour_palette, as_colour_palette_mut,
+    GraphicData => as_graphic_data, as_graphic_data_mut,
+    WorkingSetSpecialControls =>
+        as_working_set_special_controls, as_working_set_special_controls_mut,
+    ScalesGraphic => as_scales_graphic, as_scales_graphic_mut,
+}
+
 impl Object {
     pub fn id(&self) -> ObjectId {
         match self {
@@ -289,6 +755,60 @@ impl Object {
         }
     }
 
+    pub(super) fn id_mut(&mut self) -> &mut ObjectId {
+        match self {
+            Object::WorkingSet(o) => &mut o.id,
+            Object::DataMask(o) => &mut o.id,
+            Object::AlarmMask(o) => &mut o.id,
+            Object::Container(o) => &mut o.id,
+            Object::SoftKeyMask(o) => &mut o.id,
+            Object::Key(o) => &mut o.id,
+            Object::Button(o) => &mut o.id,
+            Object::InputBoolean(o) => &mut o.id,
+            Object::InputString(o) => &mut o.id,
+            Object::InputNumber(o) => &mut o.id,
+            Object::InputList(o) => &mut o.id,
+            Object::OutputString(o) => &mut o.id,
+            Object::OutputNumber(o) => &mut o.id,
+            Object::OutputLine(o) => &mut o.id,
+            Object::OutputRectangle(o) => &mut o.id,
+            Object::OutputEllipse(o) => &mut o.id,
+            Object::OutputPolygon(o) => &mut o.id,
+            Object::OutputMeter(o) => &mut o.id,
+            Object::OutputLinearBarGraph(o) => &mut o.id,
+            Object::OutputArchedBarGraph(o) => &mut o.id,
+            Object::PictureGraphic(o) => &mut o.id,
+            Object::NumberVariable(o) => &mut o.id,
+            Object::StringVariable(o) => &mut o.id,
+            Object::FontAttributes(o) => &mut o.id,
+            Object::LineAttributes(o) => &mut o.id,
+            Object::FillAttributes(o) => &mut o.id,
+            Object::InputAttributes(o) => &mut o.id,
+            Object::ObjectPointer(o) => &mut o.id,
+            Object::Macro(o) => &mut o.id,
+            Object::AuxiliaryFunctionType1(o) => &mut o.id,
+            Object::AuxiliaryInputType1(o) => &mut o.id,
+            Object::AuxiliaryFunctionType2(o) => &mut o.id,
+            Object::AuxiliaryInputType2(o) => &mut o.id,
+            Object::AuxiliaryControlDesignatorType2(o) => &mut o.id,
+            Object::WindowMask(o) => &mut o.id,
+            Object::KeyGroup(o) => &mut o.id,
+            Object::GraphicsContext(o) => &mut o.id,
+            Object::OutputList(o) => &mut o.id,
+            Object::ExtendedInputAttributes(o) => &mut o.id,
+            Object::ColourMap(o) => &mut o.id,
+            Object::ObjectLabelReferenceList(o) => &mut o.id,
+            Object::ExternalObjectDefinition(o) => &mut o.id,
+            Object::ExternalReferenceName(o) => &mut o.id,
+            Object::ExternalObjectPointer(o) => &mut o.id,
+            Object::Animation(o) => &mut o.id,
+            Object::ColourPalette(o) => &mut o.id,
+            Object::GraphicData(o) => &mut o.id,
+            Object::WorkingSetSpecialControls(o) => &mut o.id,
+            Object::ScalesGraphic(o) => &mut o.id,
+        }
+    }
+
     pub fn object_type(&self) -> ObjectType {
         match self {
             Object::WorkingSet(_) => ObjectType::WorkingSet,
@@ -344,12 +864,403 @@ impl Object {
             Object::ScalesGraphic(_) => ObjectType::ScalesGraphic,
         }
     }
+
+    /// Get the positioned child objects of this object.
+    pub fn object_refs(&self) -> &[ObjectRef] {
+        match self {
+            Object::WorkingSet(o) => &o.object_refs,
+            Object::DataMask(o) => &o.object_refs,
+            Object::AlarmMask(o) => &o.object_refs,
+            Object::Container(o) => &o.object_refs,
+            Object::Key(o) => &o.object_refs,
+            Object::Button(o) => &o.object_refs,
+            Object::AuxiliaryFunctionType1(o) => &o.object_refs,
+            Object::AuxiliaryInputType1(o) => &o.object_refs,
+            Object::AuxiliaryFunctionType2(o) => &o.object_refs,
+            Object::AuxiliaryInputType2(o) => &o.object_refs,
+            Object::WindowMask(o) => &o.object_refs,
+            Object::Animation(o) => &o.object_refs,
+            _ => &[],
+        }
+    }
+
+    fn object_refs_mut(&mut self) -> &mut [ObjectRef] {
+        match self {
+            Object::WorkingSet(o) => &mut o.object_refs,
+            Object::DataMask(o) => &mut o.object_refs,
+            Object::AlarmMask(o) => &mut o.object_refs,
+            Object::Container(o) => &mut o.object_refs,
+            Object::Key(o) => &mut o.object_refs,
+            Object::Button(o) => &mut o.object_refs,
+            Object::AuxiliaryFunctionType1(o) => &mut o.object_refs,
+            Object::AuxiliaryInputType1(o) => &mut o.object_refs,
+            Object::AuxiliaryFunctionType2(o) => &mut o.object_refs,
+            Object::AuxiliaryInputType2(o) => &mut o.object_refs,
+            Object::WindowMask(o) => &mut o.object_refs,
+            Object::Animation(o) => &mut o.object_refs,
+            _ => &mut [],
+        }
+    }
+
+    /// Get the macros triggered by events on this object.
+    pub fn macro_refs(&self) -> &[MacroRef] {
+        match self {
+            Object::WorkingSet(o) => &o.macro_refs,
+            Object::DataMask(o) => &o.macro_refs,
+            Object::AlarmMask(o) => &o.macro_refs,
+            Object::Container(o) => &o.macro_refs,
+            Object::SoftKeyMask(o) => &o.macro_refs,
+            Object::Key(o) => &o.macro_refs,
+            Object::Button(o) => &o.macro_refs,
+            Object::InputBoolean(o) => &o.macro_refs,
+            Object::InputString(o) => &o.macro_refs,
+            Object::InputNumber(o) => &o.macro_refs,
+            Object::InputList(o) => &o.macro_refs,
+            Object::OutputString(o) => &o.macro_refs,
+            Object::OutputNumber(o) => &o.macro_refs,
+            Object::OutputList(o) => &o.macro_refs,
+            Object::OutputLine(o) => &o.macro_refs,
+            Object::OutputRectangle(o) => &o.macro_refs,
+            Object::OutputEllipse(o) => &o.macro_refs,
+            Object::OutputPolygon(o) => &o.macro_refs,
+            Object::OutputMeter(o) => &o.macro_refs,
+            Object::OutputLinearBarGraph(o) => &o.macro_refs,
+            Object::OutputArchedBarGraph(o) => &o.macro_refs,
+            Object::PictureGraphic(o) => &o.macro_refs,
+            Object::FontAttributes(o) => &o.macro_refs,
+            Object::LineAttributes(o) => &o.macro_refs,
+            Object::FillAttributes(o) => &o.macro_refs,
+            Object::InputAttributes(o) => &o.macro_refs,
+            Object::WindowMask(o) => &o.macro_refs,
+            Object::KeyGroup(o) => &o.macro_refs,
+            Object::Animation(o) => &o.macro_refs,
+            Object::ScalesGraphic(o) => &o.macro_refs,
+            _ => &[],
+        }
+    }
+
+    /// Get the ids of the macros this object runs when `event` occurs.
+    pub fn macros_for_event(&self, event: EventId) -> Vec<u8> {
+        self.macro_refs()
+            .iter()
+            .filter(|m| m.event() == event)
+            .map(|m| m.macro_id)
+            .collect()
+    }
+
+    /// Get the width and height this object takes up on screen, if it has a size of its own.
+    pub fn dimensions(&self) -> Option<Point<u16>> {
+        let (x, y) = match self {
+            Object::Container(o) => (o.width, o.height),
+            Object::Button(o) => (o.width, o.height),
+            Object::InputBoolean(o) => (o.width, o.width),
+            Object::InputString(o) => (o.width, o.height),
+            Object::InputNumber(o) => (o.width, o.height),
+            Object::InputList(o) => (o.width, o.height),
+            Object::OutputString(o) => (o.width, o.height),
+            Object::OutputNumber(o) => (o.width, o.height),
+            Object::OutputList(o) => (o.width, o.height),
+            Object::OutputLine(o) => (o.width, o.height),
+            Object::OutputRectangle(o) => (o.width, o.height),
+            Object::OutputEllipse(o) => (o.width, o.height),
+            Object::OutputPolygon(o) => (o.width, o.height),
+            Object::OutputMeter(o) => (o.width, o.width),
+            Object::OutputLinearBarGraph(o) => (o.width, o.height),
+            Object::OutputArchedBarGraph(o) => (o.width, o.height),
+            Object::PictureGraphic(o) => return Some(o.scaled_dimensions()),
+            Object::Animation(o) => (o.width, o.height),
+            Object::ScalesGraphic(o) => (o.width, o.height),
+            _ => return None,
+        };
+        Some(Point { x, y })
+    }
+
+    /// Set the width and height of this object.
+    ///
+    /// Returns false, without changing anything, for objects that do not have both a width and
+    /// a height.
+    pub fn set_size(&mut self, width: u16, height: u16) -> bool {
+        let (w, h) = match self {
+            Object::Container(o) => (&mut o.width, &mut o.height),
+            Object::Button(o) => (&mut o.width, &mut o.height),
+            Object::InputString(o) => (&mut o.width, &mut o.height),
+            Object::InputNumber(o) => (&mut o.width, &mut o.height),
+            Object::InputList(o) => (&mut o.width, &mut o.height),
+            Object::OutputString(o) => (&mut o.width, &mut o.height),
+            Object::OutputNumber(o) => (&mut o.width, &mut o.height),
+            Object::OutputList(o) => (&mut o.width, &mut o.height),
+            Object::OutputLine(o) => (&mut o.width, &mut o.height),
+            Object::OutputRectangle(o) => (&mut o.width, &mut o.height),
+            Object::OutputEllipse(o) => (&mut o.width, &mut o.height),
+            Object::OutputPolygon(o) => (&mut o.width, &mut o.height),
+            Object::OutputLinearBarGraph(o) => (&mut o.width, &mut o.height),
+            Object::OutputArchedBarGraph(o) => (&mut o.width, &mut o.height),
+            Object::Animation(o) => (&mut o.width, &mut o.height),
+            Object::ScalesGraphic(o) => (&mut o.width, &mut o.height),
+            _ => return false,
+        };
+        *w = width;
+        *h = height;
+        true
+    }
+
+    /// Get the ids of all objects referenced by this object.
+    ///
+    /// This includes child objects, attribute objects, variable references and list items.
+    /// References to [`ObjectId::NULL`] are skipped.
+    pub fn child_ids(&self) -> Vec<ObjectId> {
+        let ids: Vec<ObjectId> = match self {
+            Object::WorkingSet(o) => core::iter::once(o.active_mask)
+                .chain(o.object_refs.iter().map(|r| r.id))
+                .collect(),
+            Object::DataMask(o) => core::iter::once(o.soft_key_mask)
+                .chain(o.object_refs.iter().map(|r| r.id))
+                .collect(),
+            Object::AlarmMask(o) => core::iter::once(o.soft_key_mask)
+                .chain(o.object_refs.iter().map(|r| r.id))
+                .collect(),
+            Object::Container(o) => o.object_refs.iter().map(|r| r.id).collect(),
+            Object::SoftKeyMask(o) => o.objects.clone(),
+            Object::Key(o) => o.object_refs.iter().map(|r| r.id).collect(),
+            Object::Button(o) => o.object_refs.iter().map(|r| r.id).collect(),
+            Object::InputBoolean(o) => vec![o.foreground_colour, o.variable_reference],
+            Object::InputString(o) => {
+                vec![o.font_attributes, o.input_attributes, o.variable_reference]
+            }
+            Object::InputNumber(o) => vec![o.font_attributes, o.variable_reference],
+            Object::InputList(o) => core::iter::once(o.variable_reference)
+                .chain(o.list_items.iter().copied())
+                .collect(),
+            Object::OutputString(o) => vec![o.font_attributes, o.variable_reference],
+            Object::OutputNumber(o) => vec![o.font_attributes, o.variable_reference],
+            Object::OutputLine(o) => vec![o.line_attributes],
+            Object::OutputRectangle(o) => vec![o.line_attributes, o.fill_attributes],
+            Object::OutputEllipse(o) => vec![o.line_attributes, o.fill_attributes],
+            Object::OutputPolygon(o) => vec![o.line_attributes, o.fill_attributes],
+            Object::OutputMeter(o) => vec![o.variable_reference],
+            Object::OutputLinearBarGraph(o) => {
+                vec![o.variable_reference, o.target_value_variable_reference]
+            }
+            Object::OutputArchedBarGraph(o) => {
+                vec![o.variable_reference, o.target_value_variable_reference]
+            }
+            Object::FillAttributes(o) => vec![o.fill_pattern],
+            Object::ObjectPointer(o) => vec![o.value],
+            Object::AuxiliaryFunctionType1(o) => o.object_refs.iter().map(|r| r.id).collect(),
+            Object::AuxiliaryInputType1(o) => o.object_refs.iter().map(|r| r.id).collect(),
+            Object::AuxiliaryFunctionType2(o) => o.object_refs.iter().map(|r| r.id).collect(),
+            Object::AuxiliaryInputType2(o) => o.object_refs.iter().map(|r| r.id).collect(),
+            Object::AuxiliaryControlDesignatorType2(o) => vec![o.auxiliary_object_id],
+            Object::WindowMask(o) => [o.name, o.window_title, o.window_icon]
+                .into_iter()
+                .chain(o.objects.iter().copied())
+                .chain(o.object_refs.iter().map(|r| r.id))
+                .collect(),
+            Object::KeyGroup(o) => [o.name, o.key_group_icon]
+                .into_iter()
+                .chain(o.objects.iter().copied())
+                .collect(),
+            Object::GraphicsContext(o) => vec![
+                o.font_attributes_object,
+                o.line_attributes_object,
+                o.fill_attributes_object,
+            ],
+            Object::OutputList(o) => core::iter::once(o.variable_reference)
+                .chain(o.list_items.iter().copied())
+                .collect(),
+            Object::ObjectLabelReferenceList(o) => o
+                .object_labels
+                .iter()
+                .flat_map(|l| [l.id, l.string_variable_reference, l.graphic_representation])
+                .collect(),
+            Object::ExternalObjectDefinition(o) => o.objects.clone(),
+            Object::ExternalObjectPointer(o) => {
+                vec![o.default_object_id, o.external_reference_name_id]
+            }
+            Object::Animation(o) => o.object_refs.iter().map(|r| r.id).collect(),
+            Object::WorkingSetSpecialControls(o) => {
+                vec![o.id_of_colour_map, o.id_of_colour_palette]
+            }
+            Object::PictureGraphic(_)
+            | Object::NumberVariable(_)
+            | Object::StringVariable(_)
+            | Object::FontAttributes(_)
+            | Object::LineAttributes(_)
+            | Object::InputAttributes(_)
+            | Object::Macro(_)
+            | Object::ExtendedInputAttributes(_)
+            | Object::ColourMap(_)
+            | Object::ExternalReferenceName(_)
+            | Object::ColourPalette(_)
+            | Object::GraphicData(_)
+            | Object::ScalesGraphic(_) => Vec::new(),
+        };
+
+        ids.into_iter().filter(|&id| id != ObjectId::NULL).collect()
+    }
+
+    /// Get the colour palette indices used by the colour attributes of this object.
+    pub fn colour_indices(&self) -> Vec<u8> {
+        match self {
+            Object::WorkingSet(o) => vec![o.background_colour],
+            Object::DataMask(o) => vec![o.background_colour],
+            Object::AlarmMask(o) => vec![o.background_colour],
+            Object::SoftKeyMask(o) => vec![o.background_colour],
+            Object::Key(o) => vec![o.background_colour],
+            Object::Button(o) => vec![o.background_colour, o.border_colour],
+            Object::InputBoolean(o) => vec![o.background_colour],
+            Object::InputString(o) => vec![o.background_colour],
+            Object::InputNumber(o) => vec![o.background_colour],
+            Object::OutputString(o) => vec![o.background_colour],
+            Object::OutputNumber(o) => vec![o.background_colour],
+            Object::OutputMeter(o) => vec![o.needle_colour, o.border_colour, o.arc_and_tick_colour],
+            Object::OutputLinearBarGraph(o) => vec![o.colour, o.target_line_colour],
+            Object::OutputArchedBarGraph(o) => vec![o.colour, o.target_line_colour],
+            Object::PictureGraphic(o) => vec![o.transparency_colour],
+            Object::FontAttributes(o) => vec![o.font_colour],
+            Object::LineAttributes(o) => vec![o.line_colour],
+            Object::FillAttributes(o) => vec![o.fill_colour],
+            Object::AuxiliaryFunctionType1(o) => vec![o.background_colour],
+            Object::AuxiliaryInputType1(o) => vec![o.background_colour],
+            Object::AuxiliaryFunctionType2(o) => vec![o.background_colour],
+            Object::AuxiliaryInputType2(o) => vec![o.background_colour],
+            Object::WindowMask(o) => vec![o.background_colour],
+            Object::GraphicsContext(o) => vec![
+                o.foreground_colour,
+                o.background_colour,
+                o.transparency_colour,
+            ],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Get mutable access to every object reference field of this object, including NULL ones.
+    pub(super) fn child_ids_mut(&mut self) -> Vec<&mut ObjectId> {
+        match self {
+            Object::WorkingSet(o) => core::iter::once(&mut o.active_mask)
+                .chain(o.object_refs.iter_mut().map(|r| &mut r.id))
+                .collect(),
+            Object::DataMask(o) => core::iter::once(&mut o.soft_key_mask)
+                .chain(o.object_refs.iter_mut().map(|r| &mut r.id))
+                .collect(),
+            Object::AlarmMask(o) => core::iter::once(&mut o.soft_key_mask)
+                .chain(o.object_refs.iter_mut().map(|r| &mut r.id))
+                .collect(),
+            Object::Container(o) => o.object_refs.iter_mut().map(|r| &mut r.id).collect(),
+            Object::SoftKeyMask(o) => o.objects.iter_mut().collect(),
+            Object::Key(o) => o.object_refs.iter_mut().map(|r| &mut r.id).collect(),
+            Object::Button(o) => o.object_refs.iter_mut().map(|r| &mut r.id).collect(),
+            Object::InputBoolean(o) => vec![&mut o.foreground_colour, &mut o.variable_reference],
+            Object::InputString(o) => vec![
+                &mut o.font_attributes,
+                &mut o.input_attributes,
+                &mut o.variable_reference,
+            ],
+            Object::InputNumber(o) => vec![&mut o.font_attributes, &mut o.variable_reference],
+            Object::InputList(o) => core::iter::once(&mut o.variable_reference)
+                .chain(o.list_items.iter_mut())
+                .collect(),
+            Object::OutputString(o) => vec![&mut o.font_attributes, &mut o.variable_reference],
+            Object::OutputNumber(o) => vec![&mut o.font_attributes, &mut o.variable_reference],
+            Object::OutputLine(o) => vec![&mut o.line_attributes],
+            Object::OutputRectangle(o) => vec![&mut o.line_attributes, &mut o.fill_attributes],
+            Object::OutputEllipse(o) => vec![&mut o.line_attributes, &mut o.fill_attributes],
+            Object::OutputPolygon(o) => vec![&mut o.line_attributes, &mut o.fill_attributes],
+            Object::OutputMeter(o) => vec![&mut o.variable_reference],
+            Object::OutputLinearBarGraph(o) => vec![
+                &mut o.variable_reference,
+                &mut o.target_value_variable_reference,
+            ],
+            Object::OutputArchedBarGraph(o) => vec![
+                &mut o.variable_reference,
+                &mut o.target_value_variable_reference,
+            ],
+            Object::FillAttributes(o) => vec![&mut o.fill_pattern],
+            Object::ObjectPointer(o) => vec![&mut o.value],
+            Object::AuxiliaryFunctionType1(o) => {
+                o.object_refs.iter_mut().map(|r| &mut r.id).collect()
+            }
+            Object::AuxiliaryInputType1(o) => o.object_refs.iter_mut().map(|r| &mut r.id).collect(),
+            Object::AuxiliaryFunctionType2(o) => {
+                o.object_refs.iter_mut().map(|r| &mut r.id).collect()
+            }
+            Object::AuxiliaryInputType2(o) => o.object_refs.iter_mut().map(|r| &mut r.id).collect(),
+            Object::AuxiliaryControlDesignatorType2(o) => vec![&mut o.auxiliary_object_id],
+            Object::WindowMask(o) => [&mut o.name, &mut o.window_title, &mut o.window_icon]
+                .into_iter()
+                .chain(o.objects.iter_mut())
+                .chain(o.object_refs.iter_mut().map(|r| &mut r.id))
+                .collect(),
+            Object::KeyGroup(o) => [&mut o.name, &mut o.key_group_icon]
+                .into_iter()
+                .chain(o.objects.iter_mut())
+                .collect(),
+            Object::GraphicsContext(o) => vec![
+                &mut o.font_attributes_object,
+                &mut o.line_attributes_object,
+                &mut o.fill_attributes_object,
+            ],
+            Object::OutputList(o) => core::iter::once(&mut o.variable_reference)
+                .chain(o.list_items.iter_mut())
+                .collect(),
+            Object::ObjectLabelReferenceList(o) => o
+                .object_labels
+                .iter_mut()
+                .flat_map(|l| {
+                    [
+                        &mut l.id,
+                        &mut l.string_variable_reference,
+                        &mut l.graphic_representation,
+                    ]
+                })
+                .collect(),
+            Object::ExternalObjectDefinition(o) => o.objects.iter_mut().collect(),
+            Object::ExternalObjectPointer(o) => {
+                vec![&mut o.default_object_id, &mut o.external_reference_name_id]
+            }
+            Object::Animation(o) => o.object_refs.iter_mut().map(|r| &mut r.id).collect(),
+            Object::WorkingSetSpecialControls(o) => {
+                vec![&mut o.id_of_colour_map, &mut o.id_of_colour_palette]
+            }
+            Object::PictureGraphic(_)
+            | Object::NumberVariable(_)
+            | Object::StringVariable(_)
+            | Object::FontAttributes(_)
+            | Object::LineAttributes(_)
+            | Object::InputAttributes(_)
+            | Object::Macro(_)
+            | Object::ExtendedInputAttributes(_)
+            | Object::ColourMap(_)
+            | Object::ExternalReferenceName(_)
+            | Object::ColourPalette(_)
+            | Object::GraphicData(_)
+            | Object::ScalesGraphic(_) => Vec::new(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ObjectId(u16);
 impl ObjectId {
     pub const NULL: ObjectId = ObjectId(0xFFFF);
+
+    /// Create an id from little endian bytes, the byte order of ISO 11783.
+    pub const fn from_le_bytes(bytes: [u8; 2]) -> Self {
+        ObjectId(u16::from_le_bytes(bytes))
+    }
+
+    /// Create an id from big endian bytes, for data from tools that do not follow ISO 11783.
+    pub const fn from_be_bytes(bytes: [u8; 2]) -> Self {
+        ObjectId(u16::from_be_bytes(bytes))
+    }
+
+    pub const fn to_le_bytes(self) -> [u8; 2] {
+        self.0.to_le_bytes()
+    }
+
+    pub const fn to_be_bytes(self) -> [u8; 2] {
+        self.0.to_be_bytes()
+    }
 }
 impl Default for ObjectId {
     fn default() -> Self {
@@ -366,14 +1277,16 @@ impl From<ObjectId> for u16 {
         val.0
     }
 }
+/// Little endian, prefer [`ObjectId::from_le_bytes`] which makes the byte order explicit.
 impl From<[u8; 2]> for ObjectId {
     fn from(val: [u8; 2]) -> Self {
-        ObjectId(u16::from_le_bytes(val))
+        ObjectId::from_le_bytes(val)
     }
 }
+/// Little endian, prefer [`ObjectId::to_le_bytes`] which makes the byte order explicit.
 impl From<ObjectId> for [u8; 2] {
     fn from(val: ObjectId) -> Self {
-        val.0.to_le_bytes()
+        val.to_le_bytes()
     }
 }
 // impl From<Vec<u8>> for ObjectId {
@@ -391,27 +1304,145 @@ impl From<ObjectId> for [u8; 2] {
 impl From<&[u8]> for ObjectId {
     fn from(val: &[u8]) -> Self {
         match val.len() {
-            2.. => ObjectId(u16::from_le_bytes([val[0], val[1]])),
+            2.. => ObjectId::from_le_bytes([val[0], val[1]]),
             _ => ObjectId::NULL,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ObjectRef {
     pub id: ObjectId,
+    /// Position relative to the referencing object, see [`ObjectPool::absolute_offset`] for the
+    /// position relative to a mask
     pub offset: Point<i16>,
     // pub x: i16,
     // pub y: i16,
 }
 
-#[derive(Debug)]
+/// The event that triggers a macro, see [`MacroRef::event_id`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventId {
+    OnActivate,
+    OnDeactivate,
+    OnShow,
+    OnHide,
+    OnEnable,
+    OnDisable,
+    OnChangeActiveMask,
+    OnChangeSoftKeyMask,
+    OnChangeAttribute,
+    OnChangeBackgroundColour,
+    OnChangeFontAttributes,
+    OnChangeLineAttributes,
+    OnChangeFillAttributes,
+    OnChangeChildLocation,
+    OnChangeSize,
+    OnChangeValue,
+    OnChangePriority,
+    OnChangeEndPoint,
+    OnInputFieldSelection,
+    OnInputFieldDeselection,
+    OnEsc,
+    OnEntryOfValue,
+    OnEntryOfNewValue,
+    OnKeyPress,
+    OnKeyRelease,
+    OnChangeChildPosition,
+    OnPointingEventPress,
+    OnPointingEventRelease,
+    /// Proprietary events, 240 to 254
+    Proprietary(u8),
+    /// Reserved event ids, including 0 and 255
+    Reserved(u8),
+}
+
+impl From<u8> for EventId {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => EventId::OnActivate,
+            2 => EventId::OnDeactivate,
+            3 => EventId::OnShow,
+            4 => EventId::OnHide,
+            5 => EventId::OnEnable,
+            6 => EventId::OnDisable,
+            7 => EventId::OnChangeActiveMask,
+            8 => EventId::OnChangeSoftKeyMask,
+            9 => EventId::OnChangeAttribute,
+            10 => EventId::OnChangeBackgroundColour,
+            11 => EventId::OnChangeFontAttributes,
+            12 => EventId::OnChangeLineAttributes,
+            13 => EventId::OnChangeFillAttributes,
+            14 => EventId::OnChangeChildLocation,
+            15 => EventId::OnChangeSize,
+            16 => EventId::OnChangeValue,
+            17 => EventId::OnChangePriority,
+            18 => EventId::OnChangeEndPoint,
+            19 => EventId::OnInputFieldSelection,
+            20 => EventId::OnInputFieldDeselection,
+            21 => EventId::OnEsc,
+            22 => EventId::OnEntryOfValue,
+            23 => EventId::OnEntryOfNewValue,
+            24 => EventId::OnKeyPress,
+            25 => EventId::OnKeyRelease,
+            26 => EventId::OnChangeChildPosition,
+            27 => EventId::OnPointingEventPress,
+            28 => EventId::OnPointingEventRelease,
+            240..=254 => EventId::Proprietary(value),
+            _ => EventId::Reserved(value),
+        }
+    }
+}
+
+impl From<EventId> for u8 {
+    fn from(value: EventId) -> Self {
+        match value {
+            EventId::OnActivate => 1,
+            EventId::OnDeactivate => 2,
+            EventId::OnShow => 3,
+            EventId::OnHide => 4,
+            EventId::OnEnable => 5,
+            EventId::OnDisable => 6,
+            EventId::OnChangeActiveMask => 7,
+            EventId::OnChangeSoftKeyMask => 8,
+            EventId::OnChangeAttribute => 9,
+            EventId::OnChangeBackgroundColour => 10,
+            EventId::OnChangeFontAttributes => 11,
+            EventId::OnChangeLineAttributes => 12,
+            EventId::OnChangeFillAttributes => 13,
+            EventId::OnChangeChildLocation => 14,
+            EventId::OnChangeSize => 15,
+            EventId::OnChangeValue => 16,
+            EventId::OnChangePriority => 17,
+            EventId::OnChangeEndPoint => 18,
+            EventId::OnInputFieldSelection => 19,
+            EventId::OnInputFieldDeselection => 20,
+            EventId::OnEsc => 21,
+            EventId::OnEntryOfValue => 22,
+            EventId::OnEntryOfNewValue => 23,
+            EventId::OnKeyPress => 24,
+            EventId::OnKeyRelease => 25,
+            EventId::OnChangeChildPosition => 26,
+            EventId::OnPointingEventPress => 27,
+            EventId::OnPointingEventRelease => 28,
+            EventId::Proprietary(v) | EventId::Reserved(v) => v,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct MacroRef {
     pub macro_id: u8,
     pub event_id: u8,
 }
 
-#[derive(Debug, Clone, Copy)]
+impl MacroRef {
+    pub fn event(&self) -> EventId {
+        EventId::from(self.event_id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Colour {
     pub a: u8,
     pub r: u8,
@@ -428,6 +1459,35 @@ impl Colour {
         [self.r, self.g, self.b, self.a]
     }
 
+    /// Composite this colour over `background` with straight (not premultiplied) alpha.
+    pub fn over(&self, background: &Colour) -> Colour {
+        let src_a = u32::from(self.a);
+        let dst_a = u32::from(background.a) * (255 - src_a);
+        let total = src_a * 255 + dst_a;
+        if total == 0 {
+            return Colour {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0,
+            };
+        }
+
+        let blend = |src: u8, dst: u8| {
+            let value = u32::from(src) * src_a * 255 + u32::from(dst) * dst_a;
+            ((value + total / 2) / total) as u8
+        };
+        Colour {
+            r: blend(self.r, background.r),
+            g: blend(self.g, background.g),
+            b: blend(self.b, background.b),
+            a: ((total + 127) / 255) as u8,
+        }
+    }
+
+    /// The index of [`Colour::GREY`] in the standard palette, the default background colour.
+    pub const GREY_INDEX: u8 = 8;
+
     pub const BLACK: Colour = Colour::COLOUR_PALETTE[0];
     pub const WHITE: Colour = Colour::COLOUR_PALETTE[1];
     pub const GREEN: Colour = Colour::COLOUR_PALETTE[2];
@@ -436,7 +1496,7 @@ impl Colour {
     pub const PURPLE: Colour = Colour::COLOUR_PALETTE[5];
     pub const OLIVE: Colour = Colour::COLOUR_PALETTE[6];
     pub const SILVER: Colour = Colour::COLOUR_PALETTE[7];
-    pub const GREY: Colour = Colour::COLOUR_PALETTE[8];
+    pub const GREY: Colour = Colour::COLOUR_PALETTE[Colour::GREY_INDEX as usize];
     pub const BLUE: Colour = Colour::COLOUR_PALETTE[9];
     pub const LIME: Colour = Colour::COLOUR_PALETTE[10];
     pub const CYAN: Colour = Colour::COLOUR_PALETTE[11];
@@ -445,6 +1505,16 @@ impl Colour {
     pub const YELLOW: Colour = Colour::COLOUR_PALETTE[14];
     pub const NAVY: Colour = Colour::COLOUR_PALETTE[15];
 
+    /// The palette indices that are proprietary or undefined in the standard palette.
+    ///
+    /// [`Colour::COLOUR_PALETTE`] holds black placeholders for these indices.
+    pub const RESERVED_INDICES: core::ops::RangeInclusive<u8> = 232..=255;
+
+    /// Returns true if `index` has a colour defined by the standard palette.
+    pub fn is_defined_index(index: u8) -> bool {
+        !Self::RESERVED_INDICES.contains(&index)
+    }
+
     #[rustfmt::skip] // Skip formatting the lines
     pub const COLOUR_PALETTE: [Colour; 256] = [
         Colour { r: 0x00, g: 0x00, b: 0x00, a: 0xFF },
@@ -724,12 +1794,73 @@ impl From<u32> for Colour {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy)]
+impl From<Colour> for u32 {
+    fn from(val: Colour) -> Self {
+        u32::from_le_bytes(val.as_rgba())
+    }
+}
+
+/// A position or size, ordered by `x` first and then by `y`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Point<T> {
     pub x: T,
     pub y: T,
 }
 
+impl Point<u16> {
+    /// Convert to a signed point, coordinates above `i16::MAX` saturate.
+    pub fn to_i16(&self) -> Point<i16> {
+        Point {
+            x: i16::try_from(self.x).unwrap_or(i16::MAX),
+            y: i16::try_from(self.y).unwrap_or(i16::MAX),
+        }
+    }
+}
+
+impl Point<i16> {
+    /// Convert to an unsigned point, negative coordinates saturate to zero.
+    pub fn to_u16_saturating(&self) -> Point<u16> {
+        Point {
+            x: u16::try_from(self.x).unwrap_or(0),
+            y: u16::try_from(self.y).unwrap_or(0),
+        }
+    }
+}
+
+impl TryFrom<Point<u16>> for Point<i16> {
+    type Error = core::num::TryFromIntError;
+
+    fn try_from(value: Point<u16>) -> Result<Self, Self::Error> {
+        Ok(Point {
+            x: i16::try_from(value.x)?,
+            y: i16::try_from(value.y)?,
+        })
+    }
+}
+
+impl TryFrom<Point<i16>> for Point<u16> {
+    type Error = core::num::TryFromIntError;
+
+    fn try_from(value: Point<i16>) -> Result<Self, Self::Error> {
+        Ok(Point {
+            x: u16::try_from(value.x)?,
+            y: u16::try_from(value.y)?,
+        })
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(value: Point<T>) -> Self {
+        (value.x, value.y)
+    }
+}
+
 impl core::ops::Add<Point<i16>> for Point<u16> {
     type Output = Point<u16>;
 
@@ -741,7 +1872,7 @@ impl core::ops::Add<Point<i16>> for Point<u16> {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ObjectLabel {
     pub id: ObjectId,
     pub string_variable_reference: ObjectId,
@@ -749,7 +1880,7 @@ pub struct ObjectLabel {
     pub graphic_representation: ObjectId,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct WorkingSet {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -760,7 +1891,17 @@ pub struct WorkingSet {
     pub language_codes: Vec<String>,
 }
 
-#[derive(Debug)]
+impl WorkingSet {
+    /// Get the reference to the object the VT shows to select this working set.
+    ///
+    /// The VT designates the working set with the objects it references, by convention the
+    /// first reference is the icon.
+    pub fn icon_ref(&self) -> Option<&ObjectRef> {
+        self.object_refs.first()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct DataMask {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -769,7 +1910,7 @@ pub struct DataMask {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AlarmMask {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -780,7 +1921,94 @@ pub struct AlarmMask {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+impl AlarmMask {
+    pub fn alarm_priority(&self) -> AlarmPriority {
+        AlarmPriority::from(self.priority)
+    }
+
+    pub fn set_alarm_priority(&mut self, priority: AlarmPriority) {
+        self.priority = priority.into();
+    }
+
+    pub fn alarm_acoustic_signal(&self) -> AcousticSignal {
+        AcousticSignal::from(self.acoustic_signal)
+    }
+
+    pub fn set_alarm_acoustic_signal(&mut self, signal: AcousticSignal) {
+        self.acoustic_signal = signal.into();
+    }
+}
+
+/// The priority attribute of an [`AlarmMask`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlarmPriority {
+    /// The operator is in danger or an urgent machine malfunction occurred
+    High,
+    /// Normal alarm, the machine is malfunctioning
+    Medium,
+    /// Information only
+    Low,
+    Reserved(u8),
+}
+
+impl From<u8> for AlarmPriority {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => AlarmPriority::High,
+            1 => AlarmPriority::Medium,
+            2 => AlarmPriority::Low,
+            _ => AlarmPriority::Reserved(value),
+        }
+    }
+}
+
+impl From<AlarmPriority> for u8 {
+    fn from(value: AlarmPriority) -> Self {
+        match value {
+            AlarmPriority::High => 0,
+            AlarmPriority::Medium => 1,
+            AlarmPriority::Low => 2,
+            AlarmPriority::Reserved(v) => v,
+        }
+    }
+}
+
+/// The acoustic signal attribute of an [`AlarmMask`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AcousticSignal {
+    Highest,
+    Medium,
+    Lowest,
+    /// Silent
+    None,
+    Reserved(u8),
+}
+
+impl From<u8> for AcousticSignal {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => AcousticSignal::Highest,
+            1 => AcousticSignal::Medium,
+            2 => AcousticSignal::Lowest,
+            3 => AcousticSignal::None,
+            _ => AcousticSignal::Reserved(value),
+        }
+    }
+}
+
+impl From<AcousticSignal> for u8 {
+    fn from(value: AcousticSignal) -> Self {
+        match value {
+            AcousticSignal::Highest => 0,
+            AcousticSignal::Medium => 1,
+            AcousticSignal::Lowest => 2,
+            AcousticSignal::None => 3,
+            AcousticSignal::Reserved(v) => v,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Container {
     pub id: ObjectId,
     pub width: u16,
@@ -790,7 +2018,35 @@ pub struct Container {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+impl Container {
+    /// Crop the area of a child at `offset` with the given size to the bounds of the container.
+    ///
+    /// Children are not drawn outside their container. Returns the offset and size of the
+    /// visible part, `None` if the child is completely outside the container.
+    pub fn clip(&self, offset: Point<i16>, size: Point<u16>) -> Option<(Point<i16>, Point<u16>)> {
+        let left = i32::from(offset.x).max(0);
+        let top = i32::from(offset.y).max(0);
+        let right = (i32::from(offset.x) + i32::from(size.x)).min(i32::from(self.width));
+        let bottom = (i32::from(offset.y) + i32::from(size.y)).min(i32::from(self.height));
+        if left >= right || top >= bottom {
+            return None;
+        }
+
+        // The clipped area lies within the container, so it fits in the point types
+        Some((
+            Point {
+                x: left as i16,
+                y: top as i16,
+            },
+            Point {
+                x: (right - left) as u16,
+                y: (bottom - top) as u16,
+            },
+        ))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct SoftKeyMask {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -798,7 +2054,26 @@ pub struct SoftKeyMask {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+impl SoftKeyMask {
+    /// The number of keys in this mask, including object pointers to keys.
+    pub fn key_count(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Check the number of keys against the number of soft keys the VT supports.
+    pub fn validate_for_vt(&self, max_soft_keys: u8) -> Result<(), ValidationError> {
+        if self.key_count() > max_soft_keys as usize {
+            return Err(ValidationError::TooManySoftKeys {
+                id: self.id,
+                count: self.key_count(),
+                max: max_soft_keys,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Key {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -807,7 +2082,7 @@ pub struct Key {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Button {
     pub id: ObjectId,
     pub width: u16,
@@ -820,7 +2095,40 @@ pub struct Button {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+impl Button {
+    pub fn builder(id: ObjectId) -> ButtonBuilder {
+        ButtonBuilder::new(id)
+    }
+
+    pub fn button_options(&self) -> ButtonOptions {
+        ButtonOptions::from_bits_retain(self.options)
+    }
+
+    pub fn set_button_options(&mut self, options: ButtonOptions) {
+        self.options = options.bits();
+    }
+}
+
+bitflags! {
+    /// The options attribute of a [`Button`].
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct ButtonOptions: u8 {
+        /// The button latches when pressed
+        const LATCHABLE = 0b0000_0001;
+        /// The current state of a latchable button, set when latched
+        const LATCHED = 0b0000_0010;
+        /// Suppress the 3D border (VT version 4 and later)
+        const SUPPRESS_BORDER = 0b0000_0100;
+        /// Draw the background transparent (VT version 4 and later)
+        const TRANSPARENT_BACKGROUND = 0b0000_1000;
+        /// The button is disabled (VT version 4 and later)
+        const DISABLED = 0b0001_0000;
+        /// Draw the button without a border (VT version 4 and later)
+        const NO_BORDER = 0b0010_0000;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct InputBoolean {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -832,7 +2140,7 @@ pub struct InputBoolean {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct InputString {
     pub id: ObjectId,
     pub width: u16,
@@ -848,7 +2156,66 @@ pub struct InputString {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+impl InputString {
+    /// Get the number of characters that fit in the width of the field.
+    ///
+    /// This assumes the monospace font sizes of the standard, see [`Monospace`]. For other fonts
+    /// the number depends on the text, use [`InputString::fitting_chars`] with their
+    /// [`FontMetrics`] instead. Returns `None` if the referenced `FontAttributes` is missing or
+    /// has a proprietary size.
+    pub fn max_chars(&self, pool: &ObjectPool) -> Option<u16> {
+        let font = match pool.object_by_id(self.font_attributes) {
+            Some(Object::FontAttributes(o)) => o,
+            _ => return None,
+        };
+
+        Some(self.width / font.font_dimensions()?.x)
+    }
+
+    /// Get the number of leading characters of `text` that fit in the width of the field,
+    /// measured with `metrics`.
+    ///
+    /// Returns `None` if the referenced `FontAttributes` is missing or `metrics` does not
+    /// support its size. With [`Monospace`] metrics this is at most [`InputString::max_chars`].
+    pub fn fitting_chars(
+        &self,
+        pool: &ObjectPool,
+        metrics: &dyn FontMetrics,
+        text: &str,
+    ) -> Option<usize> {
+        let font = match pool.object_by_id(self.font_attributes) {
+            Some(Object::FontAttributes(o)) => o,
+            _ => return None,
+        };
+
+        let mut width = 0u32;
+        for (count, c) in text.chars().enumerate() {
+            width += u32::from(metrics.char_width(font, c)?);
+            if width > u32::from(self.width) {
+                return Some(count);
+            }
+        }
+        Some(text.chars().count())
+    }
+
+    /// Set the value, rejecting strings longer than [`InputString::max_chars`].
+    pub fn set_value(
+        &mut self,
+        pool: &ObjectPool,
+        value: impl Into<String>,
+    ) -> Result<(), VtError> {
+        let value: String = value.into();
+        if let Some(max_chars) = self.max_chars(pool) {
+            if value.chars().count() > max_chars as usize {
+                return Err(VtError::StringTooLong);
+            }
+        }
+        self.value = value;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct InputNumber {
     pub id: ObjectId,
     pub width: u16,
@@ -863,13 +2230,27 @@ pub struct InputNumber {
     pub offset: i32,
     pub scale: f32,
     pub nr_of_decimals: u8,
+    /// Show the value in exponential notation instead of with fixed decimals
     pub format: bool,
     pub justification: u8,
     pub options2: u8,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+impl InputNumber {
+    /// Format a raw value the way the VT displays it, see [`OutputNumber::formatted`].
+    pub fn formatted(&self, value: u32) -> String {
+        format_number(
+            value,
+            self.offset,
+            self.scale,
+            self.nr_of_decimals,
+            self.format,
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct InputList {
     pub id: ObjectId,
     pub width: u16,
@@ -881,7 +2262,7 @@ pub struct InputList {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct OutputString {
     pub id: ObjectId,
     pub width: u16,
@@ -895,7 +2276,7 @@ pub struct OutputString {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct OutputNumber {
     pub id: ObjectId,
     pub width: u16,
@@ -908,12 +2289,38 @@ pub struct OutputNumber {
     pub offset: i32,
     pub scale: f32,
     pub nr_of_decimals: u8,
+    /// Show the value in exponential notation instead of with fixed decimals
     pub format: bool,
     pub justification: u8,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+impl OutputNumber {
+    /// Format a raw value the way the VT displays it.
+    ///
+    /// The displayed value is `(value + offset) * scale`, shown with `nr_of_decimals` decimals.
+    /// With `format` set it is shown in exponential notation, like `1.2E3`.
+    pub fn formatted(&self, value: u32) -> String {
+        format_number(
+            value,
+            self.offset,
+            self.scale,
+            self.nr_of_decimals,
+            self.format,
+        )
+    }
+}
+
+/// Format a number as the VT displays it, shared by [`OutputNumber`] and [`InputNumber`].
+fn format_number(value: u32, offset: i32, scale: f32, decimals: u8, exponential: bool) -> String {
+    let displayed = (value as f64 + offset as f64) * scale as f64;
+    match exponential {
+        true => format!("{:.*E}", decimals as usize, displayed),
+        false => format!("{:.*}", decimals as usize, displayed),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct OutputList {
     pub id: ObjectId,
     pub width: u16,
@@ -924,7 +2331,7 @@ pub struct OutputList {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct OutputLine {
     pub id: ObjectId,
     pub line_attributes: ObjectId,
@@ -934,7 +2341,7 @@ pub struct OutputLine {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct OutputRectangle {
     pub id: ObjectId,
     pub line_attributes: ObjectId,
@@ -945,32 +2352,156 @@ pub struct OutputRectangle {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
-pub struct OutputEllipse {
-    pub id: ObjectId,
-    pub line_attributes: ObjectId,
-    pub width: u16,
-    pub height: u16,
-    pub ellipse_type: u8,
-    pub start_angle: u8,
-    pub end_angle: u8,
-    pub fill_attributes: ObjectId,
-    pub macro_refs: Vec<MacroRef>,
+impl OutputRectangle {
+    pub fn line_suppression(&self) -> LineSuppression {
+        LineSuppression::from_bits_retain(self.line_suppression)
+    }
+
+    pub fn set_line_suppression(&mut self, suppression: LineSuppression) {
+        self.line_suppression = suppression.bits();
+    }
 }
 
-#[derive(Debug)]
-pub struct OutputPolygon {
-    pub id: ObjectId,
-    pub width: u16,
-    pub height: u16,
-    pub line_attributes: ObjectId,
-    pub fill_attributes: ObjectId,
-    pub polygon_type: u8,
+bitflags! {
+    /// The line suppression attribute of an [`OutputRectangle`], the edges that are not drawn.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct LineSuppression: u8 {
+        const TOP = 0b0000_0001;
+        const RIGHT = 0b0000_0010;
+        const BOTTOM = 0b0000_0100;
+        const LEFT = 0b0000_1000;
+    }
+}
+
+/// An angle attribute, stored in units of 2 degrees.
+///
+/// Angles are measured counter-clockwise from the positive x-axis.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Angle(pub u8);
+
+impl Angle {
+    pub fn degrees(&self) -> u16 {
+        self.0 as u16 * 2
+    }
+
+    /// Create an angle from degrees, rounding down to the 2 degree resolution.
+    ///
+    /// Angles above 360 degrees saturate.
+    pub fn from_degrees(degrees: u16) -> Angle {
+        Angle((degrees.min(360) / 2) as u8)
+    }
+}
+
+/// How the value of a meter or bar graph maps onto its range.
+///
+/// The options of these objects have no bit for the scale, so the caller picks one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Scale {
+    #[default]
+    Linear,
+    Logarithmic,
+}
+
+impl Scale {
+    /// Get the position of `value` between `min` and `max`, from 0 to 1.
+    ///
+    /// Values outside the range are clamped. The logarithm of 0 is undefined, so a logarithmic
+    /// scale starts at 1 or higher. Returns 0 for an empty range.
+    pub fn fraction(self, value: u16, min: u16, max: u16) -> f32 {
+        let min = match self {
+            Scale::Linear => min,
+            Scale::Logarithmic => min.max(1),
+        };
+        if max <= min {
+            return 0.0;
+        }
+        let value = value.clamp(min, max);
+        match self {
+            Scale::Linear => f32::from(value - min) / f32::from(max - min),
+            Scale::Logarithmic => {
+                let min = f32::from(min).ln();
+                (f32::from(value).ln() - min) / (f32::from(max).ln() - min)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputEllipse {
+    pub id: ObjectId,
+    pub line_attributes: ObjectId,
+    pub width: u16,
+    pub height: u16,
+    pub ellipse_type: u8,
+    pub start_angle: u8,
+    pub end_angle: u8,
+    pub fill_attributes: ObjectId,
+    pub macro_refs: Vec<MacroRef>,
+}
+
+impl OutputEllipse {
+    pub fn angles(&self) -> (Angle, Angle) {
+        (Angle(self.start_angle), Angle(self.end_angle))
+    }
+
+    pub fn set_angles(&mut self, start: Angle, end: Angle) {
+        self.start_angle = start.0;
+        self.end_angle = end.0;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputPolygon {
+    pub id: ObjectId,
+    pub width: u16,
+    pub height: u16,
+    pub line_attributes: ObjectId,
+    pub fill_attributes: ObjectId,
+    pub polygon_type: u8,
     pub points: Vec<Point<u16>>,
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+impl OutputPolygon {
+    pub fn point_count(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Check that the number of points is between 3 and 255.
+    ///
+    /// The point count is serialized as a single byte, only the first 255 points are written.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        match self.point_count() {
+            3..=255 => Ok(()),
+            count => Err(ValidationError::InvalidPointCount { id: self.id, count }),
+        }
+    }
+
+    /// Get the area enclosed by the points with the shoelace formula, truncated toward zero.
+    ///
+    /// The sign gives the winding: positive when the points go clockwise on the screen, where
+    /// y points down. Polygons smaller than one pixel also give zero, use
+    /// [`OutputPolygon::is_degenerate`] to detect polygons without area.
+    pub fn signed_area(&self) -> i64 {
+        self.doubled_signed_area() / 2
+    }
+
+    /// Check whether the points enclose no area, such as points on a single line.
+    pub fn is_degenerate(&self) -> bool {
+        self.doubled_signed_area() == 0
+    }
+
+    /// Twice the signed area, which the shoelace formula gives without rounding.
+    fn doubled_signed_area(&self) -> i64 {
+        self.points
+            .iter()
+            .zip(self.points.iter().cycle().skip(1))
+            .map(|(a, b)| i64::from(a.x) * i64::from(b.y) - i64::from(b.x) * i64::from(a.y))
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct OutputMeter {
     pub id: ObjectId,
     pub width: u16,
@@ -988,7 +2519,56 @@ pub struct OutputMeter {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+impl OutputMeter {
+    pub fn angles(&self) -> (Angle, Angle) {
+        (Angle(self.start_angle), Angle(self.end_angle))
+    }
+
+    pub fn set_angles(&mut self, start: Angle, end: Angle) {
+        self.start_angle = start.0;
+        self.end_angle = end.0;
+    }
+
+    /// Get the angle of the needle for the current `value`, in degrees counter-clockwise from
+    /// the positive x-axis.
+    ///
+    /// The needle moves from the start angle to the end angle in the direction of
+    /// [`MeterOptions::CLOCKWISE`].
+    pub fn needle_angle(&self, scale: Scale) -> f32 {
+        let (start, end) = self.angles();
+        let (start, end) = (f32::from(start.degrees()), f32::from(end.degrees()));
+        let sweep = if self.meter_options().contains(MeterOptions::CLOCKWISE) {
+            -(start - end).rem_euclid(360.0)
+        } else {
+            (end - start).rem_euclid(360.0)
+        };
+        let fraction = scale.fraction(self.value, self.min_value, self.max_value);
+        (start + fraction * sweep).rem_euclid(360.0)
+    }
+
+    pub fn meter_options(&self) -> MeterOptions {
+        MeterOptions::from_bits_retain(self.options)
+    }
+
+    pub fn set_meter_options(&mut self, options: MeterOptions) {
+        self.options = options.bits();
+    }
+}
+
+bitflags! {
+    /// The options attribute of an [`OutputMeter`].
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct MeterOptions: u8 {
+        const DRAW_ARC = 0b0000_0001;
+        const DRAW_BORDER = 0b0000_0010;
+        const DRAW_TICKS = 0b0000_0100;
+        /// The needle moves clockwise from the start angle to the end angle, instead of
+        /// counter-clockwise
+        const CLOCKWISE = 0b0000_1000;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct OutputLinearBarGraph {
     pub id: ObjectId,
     pub width: u16,
@@ -1006,7 +2586,38 @@ pub struct OutputLinearBarGraph {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+impl OutputLinearBarGraph {
+    /// Get how far the bar is filled for the current `value`, from 0 to 1.
+    pub fn fill_fraction(&self, scale: Scale) -> f32 {
+        scale.fraction(self.value, self.min_value, self.max_value)
+    }
+
+    pub fn bar_graph_options(&self) -> LinearBarGraphOptions {
+        LinearBarGraphOptions::from_bits_retain(self.options)
+    }
+
+    pub fn set_bar_graph_options(&mut self, options: LinearBarGraphOptions) {
+        self.options = options.bits();
+    }
+}
+
+bitflags! {
+    /// The options attribute of an [`OutputLinearBarGraph`].
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct LinearBarGraphOptions: u8 {
+        const DRAW_BORDER = 0b0000_0001;
+        const DRAW_TARGET_LINE = 0b0000_0010;
+        const DRAW_TICKS = 0b0000_0100;
+        /// Draw a line at the value instead of filling the bar up to it
+        const VALUE_LINE = 0b0000_1000;
+        /// The bar lies along the x-axis instead of the y-axis
+        const HORIZONTAL = 0b0001_0000;
+        /// The bar grows up or to the right instead of down or to the left
+        const GROWS_POSITIVE = 0b0010_0000;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct OutputArchedBarGraph {
     pub id: ObjectId,
     pub width: u16,
@@ -1026,7 +2637,45 @@ pub struct OutputArchedBarGraph {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+impl OutputArchedBarGraph {
+    pub fn angles(&self) -> (Angle, Angle) {
+        (Angle(self.start_angle), Angle(self.end_angle))
+    }
+
+    pub fn set_angles(&mut self, start: Angle, end: Angle) {
+        self.start_angle = start.0;
+        self.end_angle = end.0;
+    }
+
+    /// Get how far the bar is filled for the current `value`, from 0 to 1.
+    pub fn fill_fraction(&self, scale: Scale) -> f32 {
+        scale.fraction(self.value, self.min_value, self.max_value)
+    }
+
+    pub fn bar_graph_options(&self) -> ArchedBarGraphOptions {
+        ArchedBarGraphOptions::from_bits_retain(self.options)
+    }
+
+    pub fn set_bar_graph_options(&mut self, options: ArchedBarGraphOptions) {
+        self.options = options.bits();
+    }
+}
+
+bitflags! {
+    /// The options attribute of an [`OutputArchedBarGraph`].
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct ArchedBarGraphOptions: u8 {
+        const DRAW_BORDER = 0b0000_0001;
+        const DRAW_TARGET_LINE = 0b0000_0010;
+        /// Draw a line at the value instead of filling the bar up to it
+        const VALUE_LINE = 0b0000_1000;
+        /// The bar grows clockwise from the start angle to the end angle, instead of
+        /// counter-clockwise
+        const CLOCKWISE = 0b0001_0000;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct PictureGraphic {
     pub id: ObjectId,
     pub width: u16,
@@ -1039,19 +2688,233 @@ pub struct PictureGraphic {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+impl PictureGraphic {
+    /// Options bit that marks the data as run-length encoded.
+    pub const RUN_LENGTH_ENCODED: u8 = 0b0000_0100;
+
+    pub fn picture_format(&self) -> PictureGraphicFormat {
+        self.format.into()
+    }
+
+    pub fn is_run_length_encoded(&self) -> bool {
+        self.options & Self::RUN_LENGTH_ENCODED != 0
+    }
+
+    /// Get the factor the VT scales the image by, `width` over `actual_width`.
+    ///
+    /// The image keeps its aspect ratio, so the same factor applies to the height. Returns 0 if
+    /// the image has no pixels.
+    pub fn display_scale(&self) -> f32 {
+        match self.actual_width {
+            0 => 0.0,
+            actual_width => self.width as f32 / actual_width as f32,
+        }
+    }
+
+    /// Get the size the image is drawn at, `width` by the scaled `actual_height`.
+    pub fn scaled_dimensions(&self) -> Point<u16> {
+        let height = match self.actual_width {
+            0 => 0,
+            actual_width => {
+                (self.actual_height as u32 * self.width as u32 / actual_width as u32) as u16
+            }
+        };
+        Point {
+            x: self.width,
+            y: height,
+        }
+    }
+
+    /// Get the data with the run-length encoding undone.
+    fn raw_data(&self) -> Option<alloc::borrow::Cow<'_, [u8]>> {
+        if !self.is_run_length_encoded() {
+            return Some(self.data.as_slice().into());
+        }
+        let mut raw = Vec::new();
+        for pair in self.data.chunks(2) {
+            match pair {
+                [count, value] => raw.extend(core::iter::repeat_n(*value, *count as usize)),
+                _ => return None,
+            }
+        }
+        Some(raw.into())
+    }
+
+    /// Check that the format is defined and the data holds `actual_width` x `actual_height`
+    /// pixels, after undoing the run-length encoding.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let (width, height) = (self.actual_width as usize, self.actual_height as usize);
+        let expected = match self.picture_format() {
+            PictureGraphicFormat::Monochrome => width.div_ceil(8) * height,
+            PictureGraphicFormat::FourBit => width.div_ceil(2) * height,
+            PictureGraphicFormat::EightBit => width * height,
+            PictureGraphicFormat::Reserved(format) => {
+                return Err(ValidationError::UnknownPictureGraphicFormat {
+                    id: self.id,
+                    format,
+                })
+            }
+        };
+        let found = if self.is_run_length_encoded() {
+            self.data.chunks_exact(2).map(|pair| pair[0] as usize).sum()
+        } else {
+            self.data.len()
+        };
+        if found < expected {
+            return Err(ValidationError::InvalidPictureGraphicSize {
+                id: self.id,
+                expected,
+                found,
+            });
+        }
+        Ok(())
+    }
+
+    /// Get the colour index of every pixel, row by row.
+    ///
+    /// Returns `None` for direct colour and reserved formats, or if the data does not hold
+    /// `actual_width` x `actual_height` pixels.
+    pub fn pixel_indices(&self) -> Option<Vec<u8>> {
+        let bits = match self.picture_format() {
+            PictureGraphicFormat::Monochrome => 1,
+            PictureGraphicFormat::FourBit => 4,
+            PictureGraphicFormat::EightBit => 8,
+            _ => return None,
+        };
+        let data = self.raw_data()?;
+
+        let width = self.actual_width as usize;
+        let height = self.actual_height as usize;
+        let row_len = (width * bits).div_ceil(8);
+        let rows = data.get(..row_len * height)?.chunks(row_len.max(1));
+        let pixels_per_byte = 8 / bits;
+        let mask = u8::MAX >> (8 - bits);
+        Some(
+            rows.take(height)
+                .flat_map(|row| {
+                    (0..width).map(move |x| {
+                        let shift = 8 - bits * (x % pixels_per_byte + 1);
+                        (row[x / pixels_per_byte] >> shift) & mask
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    /// Decode the data to one colour per pixel, row by row.
+    ///
+    /// The indices are resolved through the colour map and palette of the pool. Returns `None`
+    /// if the format is reserved or the data does not hold `actual_width` x `actual_height`
+    /// pixels, see [`PictureGraphic::decode_direct_colour`] for VTs with direct colour formats.
+    pub fn decode(&self, pool: &ObjectPool) -> Option<Vec<Colour>> {
+        match self.picture_format() {
+            PictureGraphicFormat::Monochrome
+            | PictureGraphicFormat::FourBit
+            | PictureGraphicFormat::EightBit => Some(
+                self.pixel_indices()?
+                    .into_iter()
+                    .map(|index| pool.color_by_index(index))
+                    .collect(),
+            ),
+            PictureGraphicFormat::Reserved(_) => None,
+        }
+    }
+
+    /// Decode the data as direct colour pixels in `format`, row by row.
+    ///
+    /// Direct colour is a proprietary extension, the format attribute is not checked. Returns
+    /// `None` if the data does not hold `actual_width` x `actual_height` pixels.
+    pub fn decode_direct_colour(&self, format: DirectColourFormat) -> Option<Vec<Colour>> {
+        let pixels = self.actual_width as usize * self.actual_height as usize;
+
+        match format {
+            DirectColourFormat::Rgb565 => Some(
+                self.raw_data()?
+                    .get(..pixels * 2)?
+                    .chunks(2)
+                    .map(|c| {
+                        let v = u16::from_le_bytes([c[0], c[1]]);
+                        let (r, g, b) = ((v >> 11) & 0x1F, (v >> 5) & 0x3F, v & 0x1F);
+                        Colour {
+                            r: ((r << 3) | (r >> 2)) as u8,
+                            g: ((g << 2) | (g >> 4)) as u8,
+                            b: ((b << 3) | (b >> 2)) as u8,
+                            a: 0xFF,
+                        }
+                    })
+                    .collect(),
+            ),
+            DirectColourFormat::Rgb888 => Some(
+                self.raw_data()?
+                    .get(..pixels * 3)?
+                    .chunks(3)
+                    .map(|c| Colour {
+                        r: c[0],
+                        g: c[1],
+                        b: c[2],
+                        a: 0xFF,
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// The format attribute of a [`PictureGraphic`] object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PictureGraphicFormat {
+    Monochrome,
+    FourBit,
+    EightBit,
+    Reserved(u8),
+}
+
+/// A direct colour pixel format, see [`PictureGraphic::decode_direct_colour`].
+///
+/// ISO 11783-6 only defines indexed formats, some VTs support these as an extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectColourFormat {
+    /// 16 bits per pixel, little endian RGB565
+    Rgb565,
+    /// 24 bits per pixel, RGB888
+    Rgb888,
+}
+
+impl From<u8> for PictureGraphicFormat {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => PictureGraphicFormat::Monochrome,
+            1 => PictureGraphicFormat::FourBit,
+            2 => PictureGraphicFormat::EightBit,
+            value => PictureGraphicFormat::Reserved(value),
+        }
+    }
+}
+
+impl From<PictureGraphicFormat> for u8 {
+    fn from(value: PictureGraphicFormat) -> Self {
+        match value {
+            PictureGraphicFormat::Monochrome => 0,
+            PictureGraphicFormat::FourBit => 1,
+            PictureGraphicFormat::EightBit => 2,
+            PictureGraphicFormat::Reserved(value) => value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct NumberVariable {
     pub id: ObjectId,
     pub value: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StringVariable {
     pub id: ObjectId,
     pub value: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct FontAttributes {
     pub id: ObjectId,
     pub font_colour: u8,
@@ -1061,7 +2924,40 @@ pub struct FontAttributes {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+impl FontAttributes {
+    /// Get the character set of the strings drawn with this font, `None` if it is not
+    /// supported.
+    pub fn character_set(&self) -> Option<CharacterSet> {
+        CharacterSet::from_font_type(self.font_type)
+    }
+
+    /// Get the width and height of a single character in pixels.
+    ///
+    /// Returns `None` for font sizes that are not defined by the standard.
+    pub fn font_dimensions(&self) -> Option<Point<u16>> {
+        let (x, y) = match self.font_size {
+            0 => (6, 8),
+            1 => (8, 8),
+            2 => (8, 12),
+            3 => (12, 16),
+            4 => (16, 16),
+            5 => (16, 24),
+            6 => (24, 32),
+            7 => (32, 32),
+            8 => (32, 48),
+            9 => (48, 64),
+            10 => (64, 64),
+            11 => (64, 96),
+            12 => (96, 128),
+            13 => (128, 128),
+            14 => (128, 192),
+            _ => return None,
+        };
+        Some(Point { x, y })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct LineAttributes {
     pub id: ObjectId,
     pub line_colour: u8,
@@ -1070,7 +2966,7 @@ pub struct LineAttributes {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct FillAttributes {
     pub id: ObjectId,
     pub fill_type: u8,
@@ -1079,7 +2975,7 @@ pub struct FillAttributes {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct InputAttributes {
     pub id: ObjectId,
     pub validation_type: u8,
@@ -1087,27 +2983,149 @@ pub struct InputAttributes {
     pub macro_refs: Vec<MacroRef>,
 }
 
-// TODO; Implement code planes
-#[derive(Debug)]
+impl InputAttributes {
+    pub fn validation(&self) -> ValidationType {
+        ValidationType::from(self.validation_type)
+    }
+
+    /// Returns true if `c` may be entered in an input field using these attributes.
+    ///
+    /// An empty validation string accepts nothing for [`ValidationType::ValidCharacters`] and
+    /// everything for [`ValidationType::InvalidCharacters`]. Reserved validation types accept
+    /// everything.
+    pub fn accepts(&self, c: char) -> bool {
+        let listed = self.validation_string.contains(c);
+        match self.validation() {
+            ValidationType::ValidCharacters => listed,
+            ValidationType::InvalidCharacters => !listed,
+            ValidationType::Reserved(_) => true,
+        }
+    }
+}
+
+/// The validation type attribute of [`InputAttributes`] and [`ExtendedInputAttributes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationType {
+    /// Only the listed characters are accepted
+    ValidCharacters,
+    /// All but the listed characters are accepted
+    InvalidCharacters,
+    Reserved(u8),
+}
+
+impl From<u8> for ValidationType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => ValidationType::ValidCharacters,
+            1 => ValidationType::InvalidCharacters,
+            _ => ValidationType::Reserved(value),
+        }
+    }
+}
+
+impl From<ValidationType> for u8 {
+    fn from(value: ValidationType) -> Self {
+        match value {
+            ValidationType::ValidCharacters => 0,
+            ValidationType::InvalidCharacters => 1,
+            ValidationType::Reserved(v) => v,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ExtendedInputAttributes {
     pub id: ObjectId,
     pub validation_type: u8,
-    pub nr_of_code_planes: u8,
+    pub code_planes: Vec<CodePlane>,
+}
+
+impl ExtendedInputAttributes {
+    pub fn validation(&self) -> ValidationType {
+        ValidationType::from(self.validation_type)
+    }
+
+    /// Returns true if `c` may be entered in an input field using these attributes.
+    ///
+    /// The characters are listed by the code planes, with the same empty list behaviour as
+    /// [`InputAttributes::accepts`].
+    pub fn accepts(&self, c: char) -> bool {
+        let listed = self.code_planes.iter().any(|plane| plane.contains(c));
+        match self.validation() {
+            ValidationType::ValidCharacters => listed,
+            ValidationType::InvalidCharacters => !listed,
+            ValidationType::Reserved(_) => true,
+        }
+    }
+}
+
+/// A set of character ranges within one Unicode plane, used by [`ExtendedInputAttributes`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodePlane {
+    pub number: u8,
+    /// Inclusive ranges of characters, relative to the start of the plane
+    pub character_ranges: Vec<core::ops::RangeInclusive<u16>>,
+}
+
+impl CodePlane {
+    /// Returns true if `c` lies in one of the character ranges of this plane.
+    pub fn contains(&self, c: char) -> bool {
+        let c = c as u32;
+        if c >> 16 != self.number as u32 {
+            return false;
+        }
+        self.character_ranges
+            .iter()
+            .any(|range| range.contains(&(c as u16)))
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ObjectPointer {
     pub id: ObjectId,
     pub value: ObjectId,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Macro {
     pub id: ObjectId,
     pub commands: Vec<u8>,
 }
 
-#[derive(Debug)]
+/// Get the length of the macro command at the start of `commands`, `None` if the command is
+/// not allowed in a macro.
+///
+/// Commands are 8 bytes long, except for Change String Value which is 5 bytes followed by its
+/// string. Without the string length the 5 bytes are returned.
+const fn command_len(commands: &[u8]) -> Option<usize> {
+    match commands {
+        [0xB3, _, _, lo, hi, ..] => Some(5 + u16::from_le_bytes([*lo, *hi]) as usize),
+        [0xB3, ..] => Some(5),
+        [0xA0..=0xB1 | 0xB4..=0xB8 | 0xBA | 0xBC..=0xBE, ..] => Some(8),
+        _ => None,
+    }
+}
+
+impl Macro {
+    /// Check that the commands consist of whole commands.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut offset = 0;
+        while let Some(remaining) = self.commands.get(offset..).filter(|r| !r.is_empty()) {
+            // Unknown commands are reported by the parser, here they only need a length
+            let length = command_len(remaining).unwrap_or(8);
+            if remaining.len() < length {
+                return Err(ValidationError::TruncatedMacro {
+                    id: self.id,
+                    offset,
+                });
+            }
+            offset += length;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct AuxiliaryFunctionType1 {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -1115,7 +3133,7 @@ pub struct AuxiliaryFunctionType1 {
     pub object_refs: Vec<ObjectRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AuxiliaryInputType1 {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -1124,7 +3142,7 @@ pub struct AuxiliaryInputType1 {
     pub object_refs: Vec<ObjectRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AuxiliaryFunctionType2 {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -1132,7 +3150,7 @@ pub struct AuxiliaryFunctionType2 {
     pub object_refs: Vec<ObjectRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AuxiliaryInputType2 {
     pub id: ObjectId,
     pub background_colour: u8,
@@ -1140,20 +3158,64 @@ pub struct AuxiliaryInputType2 {
     pub object_refs: Vec<ObjectRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AuxiliaryControlDesignatorType2 {
     pub id: ObjectId,
     pub pointer_type: u8,
     pub auxiliary_object_id: ObjectId,
 }
 
-#[derive(Debug)]
+impl AuxiliaryControlDesignatorType2 {
+    pub fn kind(&self) -> PointerType {
+        self.pointer_type.into()
+    }
+}
+
+/// The pointer type attribute of an [`AuxiliaryControlDesignatorType2`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerType {
+    /// Points to the auxiliary function or input in `auxiliary_object_id`
+    AuxiliaryObject,
+    /// Points to the auxiliary objects assigned to `auxiliary_object_id`
+    AssignedAuxiliaryObjects,
+    /// Points to the working set of this pool
+    WorkingSet,
+    /// Points to the working set of the auxiliary objects assigned to `auxiliary_object_id`
+    AssignedWorkingSet,
+    Reserved(u8),
+}
+
+impl From<u8> for PointerType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => PointerType::AuxiliaryObject,
+            1 => PointerType::AssignedAuxiliaryObjects,
+            2 => PointerType::WorkingSet,
+            3 => PointerType::AssignedWorkingSet,
+            _ => PointerType::Reserved(value),
+        }
+    }
+}
+
+impl From<PointerType> for u8 {
+    fn from(value: PointerType) -> Self {
+        match value {
+            PointerType::AuxiliaryObject => 0,
+            PointerType::AssignedAuxiliaryObjects => 1,
+            PointerType::WorkingSet => 2,
+            PointerType::AssignedWorkingSet => 3,
+            PointerType::Reserved(v) => v,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ColourMap {
     pub id: ObjectId,
     pub colour_map: Vec<u8>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct GraphicsContext {
     pub id: ObjectId,
     pub viewport_width: u16,
@@ -1175,7 +3237,7 @@ pub struct GraphicsContext {
     pub transparency_colour: u8,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct WindowMask {
     pub id: ObjectId,
     pub width: u8,
@@ -1191,7 +3253,104 @@ pub struct WindowMask {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+impl WindowMask {
+    pub fn kind(&self) -> WindowType {
+        self.window_type.into()
+    }
+
+    /// Get the object shown as the window title, `None` if the reference is NULL.
+    pub fn title_object(&self) -> Option<ObjectId> {
+        Some(self.window_title).filter(|id| *id != ObjectId::NULL)
+    }
+
+    /// Get the object shown as the window icon, `None` if the reference is NULL.
+    pub fn icon_object(&self) -> Option<ObjectId> {
+        Some(self.window_icon).filter(|id| *id != ObjectId::NULL)
+    }
+}
+
+/// The window type attribute of a [`WindowMask`].
+///
+/// Every type except [`WindowType::FreeForm`] gives the objects in `objects` a fixed role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowType {
+    FreeForm,
+    NumericOutputValueWithUnitsSingleHeight,
+    NumericOutputValueNoUnitsSingleHeight,
+    StringOutputValueSingleHeight,
+    NumericInputValueWithUnitsSingleHeight,
+    NumericInputValueNoUnitsSingleHeight,
+    StringInputValueSingleHeight,
+    HorizontalLinearBarGraphSingleHeight,
+    SingleButtonSingleHeight,
+    DoubleButtonSingleHeight,
+    NumericOutputValueWithUnitsDoubleHeight,
+    NumericOutputValueNoUnitsDoubleHeight,
+    StringOutputValueDoubleHeight,
+    NumericInputValueWithUnitsDoubleHeight,
+    NumericInputValueNoUnitsDoubleHeight,
+    StringInputValueDoubleHeight,
+    HorizontalLinearBarGraphDoubleHeight,
+    SingleButtonDoubleHeight,
+    DoubleButtonDoubleHeight,
+    Reserved(u8),
+}
+
+impl From<u8> for WindowType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => WindowType::FreeForm,
+            1 => WindowType::NumericOutputValueWithUnitsSingleHeight,
+            2 => WindowType::NumericOutputValueNoUnitsSingleHeight,
+            3 => WindowType::StringOutputValueSingleHeight,
+            4 => WindowType::NumericInputValueWithUnitsSingleHeight,
+            5 => WindowType::NumericInputValueNoUnitsSingleHeight,
+            6 => WindowType::StringInputValueSingleHeight,
+            7 => WindowType::HorizontalLinearBarGraphSingleHeight,
+            8 => WindowType::SingleButtonSingleHeight,
+            9 => WindowType::DoubleButtonSingleHeight,
+            10 => WindowType::NumericOutputValueWithUnitsDoubleHeight,
+            11 => WindowType::NumericOutputValueNoUnitsDoubleHeight,
+            12 => WindowType::StringOutputValueDoubleHeight,
+            13 => WindowType::NumericInputValueWithUnitsDoubleHeight,
+            14 => WindowType::NumericInputValueNoUnitsDoubleHeight,
+            15 => WindowType::StringInputValueDoubleHeight,
+            16 => WindowType::HorizontalLinearBarGraphDoubleHeight,
+            17 => WindowType::SingleButtonDoubleHeight,
+            18 => WindowType::DoubleButtonDoubleHeight,
+            _ => WindowType::Reserved(value),
+        }
+    }
+}
+
+impl From<WindowType> for u8 {
+    fn from(value: WindowType) -> Self {
+        match value {
+            WindowType::FreeForm => 0,
+            WindowType::NumericOutputValueWithUnitsSingleHeight => 1,
+            WindowType::NumericOutputValueNoUnitsSingleHeight => 2,
+            WindowType::StringOutputValueSingleHeight => 3,
+            WindowType::NumericInputValueWithUnitsSingleHeight => 4,
+            WindowType::NumericInputValueNoUnitsSingleHeight => 5,
+            WindowType::StringInputValueSingleHeight => 6,
+            WindowType::HorizontalLinearBarGraphSingleHeight => 7,
+            WindowType::SingleButtonSingleHeight => 8,
+            WindowType::DoubleButtonSingleHeight => 9,
+            WindowType::NumericOutputValueWithUnitsDoubleHeight => 10,
+            WindowType::NumericOutputValueNoUnitsDoubleHeight => 11,
+            WindowType::StringOutputValueDoubleHeight => 12,
+            WindowType::NumericInputValueWithUnitsDoubleHeight => 13,
+            WindowType::NumericInputValueNoUnitsDoubleHeight => 14,
+            WindowType::StringInputValueDoubleHeight => 15,
+            WindowType::HorizontalLinearBarGraphDoubleHeight => 16,
+            WindowType::SingleButtonDoubleHeight => 17,
+            WindowType::DoubleButtonDoubleHeight => 18,
+            WindowType::Reserved(v) => v,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct KeyGroup {
     pub id: ObjectId,
     pub options: u8,
@@ -1201,13 +3360,13 @@ pub struct KeyGroup {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ObjectLabelReferenceList {
     pub id: ObjectId,
     pub object_labels: Vec<ObjectLabel>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ExternalObjectDefinition {
     pub id: ObjectId,
     pub options: u8,
@@ -1215,14 +3374,14 @@ pub struct ExternalObjectDefinition {
     pub objects: Vec<ObjectId>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ExternalReferenceName {
     pub id: ObjectId,
     pub options: u8,
     pub name: Name,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ExternalObjectPointer {
     pub id: ObjectId,
     pub default_object_id: ObjectId,
@@ -1230,7 +3389,7 @@ pub struct ExternalObjectPointer {
     pub external_object_id: ObjectId,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Animation {
     pub id: ObjectId,
     pub width: u16,
@@ -1246,21 +3405,63 @@ pub struct Animation {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ColourPalette {
     pub id: ObjectId,
     pub options: u16,
     pub colours: Vec<Colour>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct GraphicData {
     pub id: ObjectId,
     pub format: u8,
     pub data: Vec<u8>,
 }
 
-#[derive(Debug)]
+impl GraphicData {
+    pub fn data_format(&self) -> Result<GraphicDataFormat, ValidationError> {
+        match self.format {
+            0 => Ok(GraphicDataFormat::Png),
+            format => Err(ValidationError::UnknownGraphicDataFormat {
+                id: self.id,
+                format,
+            }),
+        }
+    }
+
+    /// Check that the data is consistent with the format.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        match self.data_format()? {
+            GraphicDataFormat::Png => {
+                if !self.data.starts_with(&GraphicDataFormat::PNG_SIGNATURE) {
+                    return Err(ValidationError::InvalidGraphicData(self.id));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The format attribute of a [`GraphicData`] object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicDataFormat {
+    Png,
+}
+
+impl GraphicDataFormat {
+    pub const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+}
+
+impl From<GraphicDataFormat> for u8 {
+    fn from(value: GraphicDataFormat) -> Self {
+        match value {
+            GraphicDataFormat::Png => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ScalesGraphic {
     pub id: ObjectId,
     pub width: u16,
@@ -1271,10 +3472,1045 @@ pub struct ScalesGraphic {
     pub macro_refs: Vec<MacroRef>,
 }
 
-#[derive(Debug)]
+impl ScalesGraphic {
+    pub fn scale(&self) -> ScaleType {
+        ScaleType::from(self.scale_type)
+    }
+
+    /// Get the value as a fraction of the scale length, clamped to `0.0..=1.0`.
+    ///
+    /// The scale length is the width for a horizontal scale and the height for a vertical
+    /// scale. Reserved scale types and zero length scales result in `0.0`.
+    pub fn normalized_value(&self) -> f32 {
+        let length = match self.scale() {
+            ScaleType::Horizontal => self.width,
+            ScaleType::Vertical => self.height,
+            ScaleType::Reserved(_) => return 0.0,
+        };
+        if length == 0 {
+            return 0.0;
+        }
+        (self.value as f32 / length as f32).clamp(0.0, 1.0)
+    }
+}
+
+/// The scale type attribute of a [`ScalesGraphic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaleType {
+    Horizontal,
+    Vertical,
+    Reserved(u8),
+}
+
+impl From<u8> for ScaleType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => ScaleType::Horizontal,
+            1 => ScaleType::Vertical,
+            _ => ScaleType::Reserved(value),
+        }
+    }
+}
+
+impl From<ScaleType> for u8 {
+    fn from(value: ScaleType) -> Self {
+        match value {
+            ScaleType::Horizontal => 0,
+            ScaleType::Vertical => 1,
+            ScaleType::Reserved(v) => v,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct WorkingSetSpecialControls {
     pub id: ObjectId,
     pub id_of_colour_map: ObjectId,
     pub id_of_colour_palette: ObjectId,
     pub language_pairs: Vec<(String, String)>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alarm_mask_enums() {
+        for value in 0..=u8::MAX {
+            assert_eq!(value, u8::from(AlarmPriority::from(value)));
+            assert_eq!(value, u8::from(AcousticSignal::from(value)));
+        }
+        assert_eq!(AlarmPriority::Low, AlarmPriority::from(2));
+        assert_eq!(AlarmPriority::Reserved(3), AlarmPriority::from(3));
+        assert_eq!(AcousticSignal::None, AcousticSignal::from(3));
+        assert_eq!(AcousticSignal::Reserved(4), AcousticSignal::from(4));
+
+        let mut alarm_mask = AlarmMask {
+            id: 2000.into(),
+            background_colour: 0,
+            soft_key_mask: ObjectId::NULL,
+            priority: 1,
+            acoustic_signal: 0,
+            object_refs: Vec::new(),
+            macro_refs: Vec::new(),
+        };
+        assert_eq!(AlarmPriority::Medium, alarm_mask.alarm_priority());
+        assert_eq!(AcousticSignal::Highest, alarm_mask.alarm_acoustic_signal());
+
+        alarm_mask.set_alarm_priority(AlarmPriority::High);
+        alarm_mask.set_alarm_acoustic_signal(AcousticSignal::None);
+        assert_eq!(0, alarm_mask.priority);
+        assert_eq!(3, alarm_mask.acoustic_signal);
+    }
+
+    #[test]
+    fn test_soft_key_mask_validate_for_vt() {
+        let soft_key_mask = SoftKeyMask {
+            id: 4000.into(),
+            background_colour: 0,
+            objects: vec![5000.into(), 5001.into(), 5002.into()],
+            macro_refs: Vec::new(),
+        };
+        assert_eq!(3, soft_key_mask.key_count());
+        assert_eq!(Ok(()), soft_key_mask.validate_for_vt(3));
+        assert_eq!(
+            Err(ValidationError::TooManySoftKeys {
+                id: 4000.into(),
+                count: 3,
+                max: 2
+            }),
+            soft_key_mask.validate_for_vt(2)
+        );
+    }
+
+    #[test]
+    fn test_point_conversions() {
+        let point = Point::<u16> { x: 40000, y: 100 };
+        assert_eq!(
+            Point {
+                x: i16::MAX,
+                y: 100
+            },
+            point.to_i16()
+        );
+        assert!(Point::<i16>::try_from(point).is_err());
+        assert_eq!(
+            Ok(Point { x: 100i16, y: 0 }),
+            Point::<i16>::try_from(Point::<u16> { x: 100, y: 0 })
+        );
+
+        let point = Point::<i16> { x: -5, y: 20 };
+        assert_eq!(Point { x: 0, y: 20 }, point.to_u16_saturating());
+        assert!(Point::<u16>::try_from(point).is_err());
+
+        assert_eq!(Point { x: 1, y: 2 }, Point::from((1, 2)));
+        assert_eq!((1, 2), <(i16, i16)>::from(Point { x: 1, y: 2 }));
+    }
+
+    #[test]
+    fn test_graphic_data_validate() {
+        let mut graphic_data = GraphicData {
+            id: 46000.into(),
+            format: 0,
+            data: GraphicDataFormat::PNG_SIGNATURE.to_vec(),
+        };
+        assert_eq!(Ok(GraphicDataFormat::Png), graphic_data.data_format());
+        assert_eq!(Ok(()), graphic_data.validate());
+
+        graphic_data.data = vec![0xFF, 0xD8, 0xFF];
+        assert_eq!(
+            Err(ValidationError::InvalidGraphicData(46000.into())),
+            graphic_data.validate()
+        );
+
+        graphic_data.format = 1;
+        assert_eq!(
+            Err(ValidationError::UnknownGraphicDataFormat {
+                id: 46000.into(),
+                format: 1
+            }),
+            graphic_data.validate()
+        );
+    }
+
+    #[test]
+    fn test_macro_validate() {
+        let mut macro_object = Macro {
+            id: 250.into(),
+            commands: vec![
+                0xA0, 0xE0, 0x2E, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, // Hide object 12000
+                0xB3, 0xE0, 0x2E, 0x02, 0x00, b'O', b'K', // Change string value
+            ],
+        };
+        assert_eq!(Ok(()), macro_object.validate());
+
+        macro_object.commands.pop();
+        assert_eq!(
+            Err(ValidationError::TruncatedMacro {
+                id: 250.into(),
+                offset: 8
+            }),
+            macro_object.validate()
+        );
+
+        macro_object.commands = vec![0xA0; 12];
+        assert_eq!(
+            Err(ValidationError::TruncatedMacro {
+                id: 250.into(),
+                offset: 8
+            }),
+            macro_object.validate()
+        );
+    }
+
+    #[test]
+    fn test_scales_graphic_normalized_value() {
+        let mut scales_graphic = ScalesGraphic {
+            id: 48000.into(),
+            width: 200,
+            height: 50,
+            scale_type: 0,
+            options: 0,
+            value: 50,
+            macro_refs: Vec::new(),
+        };
+        assert_eq!(ScaleType::Horizontal, scales_graphic.scale());
+        assert_eq!(0.25, scales_graphic.normalized_value());
+
+        scales_graphic.scale_type = 1;
+        assert_eq!(1.0, scales_graphic.normalized_value());
+
+        scales_graphic.scale_type = 7;
+        assert_eq!(ScaleType::Reserved(7), scales_graphic.scale());
+        assert_eq!(0.0, scales_graphic.normalized_value());
+    }
+
+    #[test]
+    fn test_colour_is_defined_index() {
+        assert!(Colour::is_defined_index(0));
+        assert!(Colour::is_defined_index(231));
+        assert!(!Colour::is_defined_index(232));
+        assert!(!Colour::is_defined_index(240));
+        assert!(!Colour::is_defined_index(255));
+        assert_eq!(
+            232,
+            (0..=u8::MAX)
+                .filter(|&i| Colour::is_defined_index(i))
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_object_type_name() {
+        assert_eq!("OutputNumber", ObjectType::OutputNumber.name());
+        assert_eq!(
+            Some(ObjectType::OutputNumber),
+            ObjectType::from_name("OutputNumber")
+        );
+        assert_eq!(None, ObjectType::from_name("outputnumber"));
+        assert_eq!(None, ObjectType::from_name("Foo"));
+
+        for &object_type in ObjectType::ALL {
+            assert_eq!(Some(object_type), ObjectType::from_name(object_type.name()));
+            assert_eq!(format!("{:?}", object_type), object_type.name());
+        }
+    }
+
+    #[test]
+    fn test_object_type_all() {
+        assert_eq!(49, ObjectType::ALL.len());
+        for (i, &object_type) in ObjectType::ALL.iter().enumerate() {
+            assert_eq!(i as u8, object_type.discriminant());
+            assert_eq!(u8::from(object_type), object_type.discriminant());
+            assert_eq!(Ok(object_type), ObjectType::try_from(i as u8));
+        }
+        assert!(ObjectType::try_from(ObjectType::ALL.len() as u8).is_err());
+    }
+
+    #[test]
+    fn test_code_plane_contains() {
+        let code_plane = CodePlane {
+            number: 0,
+            character_ranges: vec![0x30..=0x39, 0xC0..=0xFF],
+        };
+        assert!(code_plane.contains('5'));
+        assert!(code_plane.contains('é'));
+        assert!(!code_plane.contains('a'));
+        assert!(!code_plane.contains('\u{10035}'));
+    }
+
+    #[test]
+    fn test_input_attributes_accepts() {
+        let mut input_attributes = InputAttributes {
+            id: 26000.into(),
+            validation_type: 0,
+            validation_string: "0123456789".into(),
+            macro_refs: Vec::new(),
+        };
+        assert_eq!(
+            ValidationType::ValidCharacters,
+            input_attributes.validation()
+        );
+        assert!(input_attributes.accepts('7'));
+        assert!(!input_attributes.accepts('a'));
+
+        input_attributes.validation_type = 1;
+        assert!(!input_attributes.accepts('7'));
+        assert!(input_attributes.accepts('a'));
+
+        input_attributes.validation_string.clear();
+        assert!(input_attributes.accepts('a'));
+        input_attributes.validation_type = 0;
+        assert!(!input_attributes.accepts('a'));
+
+        let extended_input_attributes = ExtendedInputAttributes {
+            id: 29000.into(),
+            validation_type: 1,
+            code_planes: vec![CodePlane {
+                number: 0,
+                character_ranges: vec![0x41..=0x5A],
+            }],
+        };
+        assert!(!extended_input_attributes.accepts('Q'));
+        assert!(extended_input_attributes.accepts('q'));
+    }
+
+    #[test]
+    fn test_object_set_size() {
+        let mut button = Object::Button(Button::builder(5000.into()).build().unwrap());
+        assert!(button.set_size(80, 40));
+        match &button {
+            Object::Button(o) => assert_eq!((80, 40), (o.width, o.height)),
+            _ => unreachable!(),
+        }
+
+        let mut number_variable = Object::NumberVariable(NumberVariable {
+            id: 21000.into(),
+            value: 0,
+        });
+        assert!(!number_variable.set_size(80, 40));
+    }
+
+    #[test]
+    fn test_angle() {
+        assert_eq!(90, Angle(45).degrees());
+        assert_eq!(Angle(45), Angle::from_degrees(90));
+        assert_eq!(Angle(45), Angle::from_degrees(91));
+        assert_eq!(Angle(180), Angle::from_degrees(400));
+        assert_eq!(360, Angle::from_degrees(360).degrees());
+
+        let mut output_meter = OutputMeter {
+            id: 17000.into(),
+            width: 100,
+            needle_colour: 0,
+            border_colour: 0,
+            arc_and_tick_colour: 0,
+            options: 0,
+            nr_of_ticks: 0,
+            start_angle: 0,
+            end_angle: 90,
+            min_value: 0,
+            max_value: 100,
+            variable_reference: ObjectId::NULL,
+            value: 0,
+            macro_refs: Vec::new(),
+        };
+        assert_eq!(180, output_meter.angles().1.degrees());
+
+        output_meter.set_angles(Angle::from_degrees(30), Angle::from_degrees(150));
+        assert_eq!((15, 75), (output_meter.start_angle, output_meter.end_angle));
+    }
+
+    #[test]
+    fn test_event_id() {
+        for value in 0..=u8::MAX {
+            assert_eq!(value, u8::from(EventId::from(value)));
+        }
+        assert_eq!(EventId::OnActivate, EventId::from(1));
+        assert_eq!(EventId::OnPointingEventRelease, EventId::from(28));
+        assert_eq!(EventId::Reserved(29), EventId::from(29));
+        assert_eq!(EventId::Proprietary(240), EventId::from(240));
+        assert_eq!(EventId::Reserved(255), EventId::from(255));
+
+        let button = Object::Button(
+            Button::builder(5000.into())
+                .add_macro_ref(1, EventId::OnKeyPress.into())
+                .add_macro_ref(2, EventId::OnKeyRelease.into())
+                .add_macro_ref(3, EventId::OnKeyPress.into())
+                .build()
+                .unwrap(),
+        );
+        assert_eq!(vec![1, 3], button.macros_for_event(EventId::OnKeyPress));
+        assert!(button.macros_for_event(EventId::OnHide).is_empty());
+    }
+
+    fn picture_graphic(format: u8, options: u8, data: Vec<u8>) -> PictureGraphic {
+        PictureGraphic {
+            id: 20000.into(),
+            width: 3,
+            actual_width: 3,
+            actual_height: 2,
+            format,
+            options,
+            transparency_colour: 0,
+            data,
+            macro_refs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_picture_graphic_display_scale() {
+        let mut picture = picture_graphic(2, 0, vec![0; 6]);
+        assert_eq!(1.0, picture.display_scale());
+        assert_eq!(Point { x: 3, y: 2 }, picture.scaled_dimensions());
+
+        picture.width = 12;
+        assert_eq!(4.0, picture.display_scale());
+        assert_eq!(Point { x: 12, y: 8 }, picture.scaled_dimensions());
+
+        picture.width = 1;
+        assert_eq!(Point { x: 1, y: 0 }, picture.scaled_dimensions());
+        assert_eq!(
+            Some(picture.scaled_dimensions()),
+            Object::PictureGraphic(picture.clone()).dimensions()
+        );
+
+        picture.actual_width = 0;
+        assert_eq!(0.0, picture.display_scale());
+        assert_eq!(Point { x: 1, y: 0 }, picture.scaled_dimensions());
+    }
+
+    #[test]
+    fn test_picture_graphic_decode_indexed() {
+        let pool = ObjectPool::new();
+        let (k, w, r) = (Colour::BLACK, Colour::WHITE, Colour::RED);
+
+        let mono = picture_graphic(0, 0, vec![0b1010_0000, 0b0100_0000]);
+        assert_eq!(Some(vec![w, k, w, k, w, k]), mono.decode(&pool));
+
+        let four_bit = picture_graphic(1, 0, vec![0x01, 0xC0, 0xC1, 0x00]);
+        assert_eq!(Some(vec![k, w, r, r, w, k]), four_bit.decode(&pool));
+
+        let eight_bit = picture_graphic(2, 0, vec![0, 1, 12, 12, 1, 0]);
+        assert_eq!(Some(vec![k, w, r, r, w, k]), eight_bit.decode(&pool));
+
+        let rle = picture_graphic(2, PictureGraphic::RUN_LENGTH_ENCODED, vec![3, 12, 3, 1]);
+        assert_eq!(Some(vec![r, r, r, w, w, w]), rle.decode(&pool));
+
+        assert_eq!(None, picture_graphic(2, 0, vec![0; 5]).decode(&pool));
+        assert_eq!(None, picture_graphic(9, 0, vec![0; 6]).decode(&pool));
+    }
+
+    #[test]
+    fn test_picture_graphic_decode_direct_colour() {
+        let pool = ObjectPool::new();
+        let colour = |r, g, b| Colour { r, g, b, a: 0xFF };
+
+        let rgb565 = picture_graphic(
+            2,
+            0,
+            [0xF800u16, 0x07E0, 0x001F, 0xFFFF, 0x0000, 0x8410]
+                .iter()
+                .flat_map(|v| v.to_le_bytes())
+                .collect(),
+        );
+        assert_eq!(
+            Some(vec![
+                colour(0xFF, 0, 0),
+                colour(0, 0xFF, 0),
+                colour(0, 0, 0xFF),
+                colour(0xFF, 0xFF, 0xFF),
+                colour(0, 0, 0),
+                colour(0x84, 0x82, 0x84),
+            ]),
+            rgb565.decode_direct_colour(DirectColourFormat::Rgb565)
+        );
+
+        let rgb888 = picture_graphic(
+            2,
+            0,
+            vec![
+                1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18,
+            ],
+        );
+        assert_eq!(
+            Some(vec![
+                colour(1, 2, 3),
+                colour(4, 5, 6),
+                colour(7, 8, 9),
+                colour(10, 11, 12),
+                colour(13, 14, 15),
+                colour(16, 17, 18),
+            ]),
+            rgb888.decode_direct_colour(DirectColourFormat::Rgb888)
+        );
+        assert_eq!(
+            None,
+            picture_graphic(2, 0, vec![0; 17]).decode_direct_colour(DirectColourFormat::Rgb888)
+        );
+        assert_eq!(None, picture_graphic(3, 0, vec![0; 12]).decode(&pool));
+    }
+
+    #[test]
+    fn test_picture_graphic_validate() {
+        assert_eq!(Ok(()), picture_graphic(0, 0, vec![0; 2]).validate());
+        assert_eq!(Ok(()), picture_graphic(1, 0, vec![0; 4]).validate());
+        assert_eq!(
+            Err(ValidationError::UnknownPictureGraphicFormat {
+                id: 20000.into(),
+                format: 4,
+            }),
+            picture_graphic(4, 0, vec![0; 18]).validate()
+        );
+        assert_eq!(
+            Ok(()),
+            picture_graphic(2, PictureGraphic::RUN_LENGTH_ENCODED, vec![3, 12, 3, 1]).validate()
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidPictureGraphicSize {
+                id: 20000.into(),
+                expected: 6,
+                found: 5,
+            }),
+            picture_graphic(2, 0, vec![0; 5]).validate()
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidPictureGraphicSize {
+                id: 20000.into(),
+                expected: 6,
+                found: 5,
+            }),
+            picture_graphic(2, PictureGraphic::RUN_LENGTH_ENCODED, vec![3, 12, 2, 1, 1]).validate()
+        );
+        assert_eq!(
+            Err(ValidationError::UnknownPictureGraphicFormat {
+                id: 20000.into(),
+                format: 9,
+            }),
+            picture_graphic(9, 0, vec![0; 6]).validate()
+        );
+    }
+
+    #[test]
+    fn test_hash_keys() {
+        use std::collections::{HashMap, HashSet};
+
+        let ids: HashSet<ObjectId> = [1000.into(), 1000.into(), 2000.into()].into();
+        assert_eq!(2, ids.len());
+
+        let mut colours = HashMap::new();
+        colours.insert(Colour::RED, 12);
+        colours.insert(Colour::WHITE, 1);
+        assert_eq!(Some(&12), colours.get(&Colour::RED));
+
+        let types: HashSet<ObjectType> = [ObjectType::Button, ObjectType::Button].into();
+        assert!(types.contains(&ObjectType::Button));
+    }
+
+    #[test]
+    fn test_point_keys() {
+        use std::collections::HashSet;
+
+        let a = Point { x: 1u16, y: 5 };
+        let b = Point { x: 2u16, y: 0 };
+        let points: HashSet<Point<u16>> = [a, b, a].into();
+        assert_eq!(2, points.len());
+
+        let mut polygon = vec![b, Point { x: 1, y: 2 }, a];
+        polygon.sort();
+        assert_eq!(vec![Point { x: 1, y: 2 }, a, b], polygon);
+        assert!(Point { x: -1i16, y: 9 } < Point { x: 0, y: 0 });
+    }
+
+    #[test]
+    fn test_window_mask() {
+        for value in 0..=u8::MAX {
+            assert_eq!(value, u8::from(WindowType::from(value)));
+        }
+
+        let mut window_mask = WindowMask {
+            id: 34000.into(),
+            width: 1,
+            height: 1,
+            window_type: 3,
+            background_colour: 0,
+            options: 0,
+            name: 11000.into(),
+            window_title: 11001.into(),
+            window_icon: ObjectId::NULL,
+            objects: vec![11002.into()],
+            object_refs: Vec::new(),
+            macro_refs: Vec::new(),
+        };
+        assert_eq!(
+            WindowType::StringOutputValueSingleHeight,
+            window_mask.kind()
+        );
+        assert_eq!(Some(ObjectId::from(11001)), window_mask.title_object());
+        assert_eq!(None, window_mask.icon_object());
+
+        window_mask.window_type = 19;
+        assert_eq!(WindowType::Reserved(19), window_mask.kind());
+    }
+
+    #[test]
+    fn test_output_polygon_area() {
+        let point = |x, y| Point { x, y };
+        let mut polygon = OutputPolygon {
+            id: 16000.into(),
+            width: 1,
+            height: 1,
+            line_attributes: ObjectId::NULL,
+            fill_attributes: ObjectId::NULL,
+            polygon_type: 0,
+            points: vec![point(0, 0), point(1, 0), point(1, 1), point(0, 1)],
+            macro_refs: Vec::new(),
+        };
+        assert_eq!(4, polygon.point_count());
+        assert_eq!(1, polygon.signed_area());
+
+        polygon.points.reverse();
+        assert_eq!(-1, polygon.signed_area());
+
+        polygon.points = vec![point(0, 0), point(u16::MAX, 0), point(u16::MAX, u16::MAX)];
+        assert_eq!(i64::from(u16::MAX).pow(2) / 2, polygon.signed_area());
+
+        polygon.points = vec![point(0, 0), point(5, 5), point(10, 10)];
+        assert_eq!(0, polygon.signed_area());
+        assert!(polygon.is_degenerate());
+
+        polygon.points = vec![point(0, 0), point(1, 0), point(0, 1)];
+        assert_eq!(0, polygon.signed_area());
+        assert!(!polygon.is_degenerate());
+    }
+
+    #[test]
+    fn test_output_polygon_validate() {
+        let mut polygon = OutputPolygon {
+            id: 16000.into(),
+            width: 1,
+            height: 1,
+            line_attributes: ObjectId::NULL,
+            fill_attributes: ObjectId::NULL,
+            polygon_type: 0,
+            points: vec![Point::default(); 2],
+            macro_refs: Vec::new(),
+        };
+        assert_eq!(
+            Err(ValidationError::InvalidPointCount {
+                id: 16000.into(),
+                count: 2
+            }),
+            polygon.validate()
+        );
+
+        polygon.points = vec![Point::default(); 255];
+        assert_eq!(Ok(()), polygon.validate());
+
+        polygon.points = vec![Point::default(); 256];
+        assert_eq!(
+            Err(ValidationError::InvalidPointCount {
+                id: 16000.into(),
+                count: 256
+            }),
+            polygon.validate()
+        );
+        // Only the first 255 points are written, matching the count
+        let parsed = OutputPolygon::try_from(&polygon.to_bytes()[..]).unwrap();
+        assert_eq!(255, parsed.point_count());
+    }
+
+    #[test]
+    fn test_container_clip() {
+        let container = Container {
+            id: 3000.into(),
+            width: 100,
+            height: 50,
+            hidden: false,
+            object_refs: Vec::new(),
+            macro_refs: Vec::new(),
+        };
+
+        let size = Point { x: 20, y: 20 };
+        assert_eq!(
+            Some((Point { x: 10, y: 10 }, size)),
+            container.clip(Point { x: 10, y: 10 }, size)
+        );
+        assert_eq!(
+            Some((Point { x: 90, y: 40 }, Point { x: 10, y: 10 })),
+            container.clip(Point { x: 90, y: 40 }, size)
+        );
+        assert_eq!(
+            Some((Point { x: 0, y: 0 }, Point { x: 15, y: 5 })),
+            container.clip(Point { x: -5, y: -15 }, size)
+        );
+        assert_eq!(None, container.clip(Point { x: 100, y: 0 }, size));
+        assert_eq!(None, container.clip(Point { x: -20, y: 0 }, size));
+        assert_eq!(
+            None,
+            container.clip(Point { x: 0, y: 0 }, Point { x: 0, y: 10 })
+        );
+    }
+
+    #[test]
+    fn test_colour_u32() {
+        for value in [0, 0xFF000000, 0x12345678, 0xFFFFFFFF] {
+            assert_eq!(value, u32::from(Colour::from(value)));
+        }
+        assert_eq!(
+            0xFF336699,
+            u32::from(Colour {
+                r: 0x99,
+                g: 0x66,
+                b: 0x33,
+                a: 0xFF
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_from_object() {
+        let obj = Object::NumberVariable(NumberVariable {
+            id: 21000.into(),
+            value: 5,
+        });
+        assert_eq!(
+            Ok(NumberVariable {
+                id: 21000.into(),
+                value: 5
+            }),
+            NumberVariable::try_from(obj.clone())
+        );
+        assert_eq!(Err(obj.clone()), StringVariable::try_from(obj));
+    }
+
+    #[test]
+    fn test_object_accessors() {
+        let mut obj = Object::NumberVariable(NumberVariable {
+            id: 21000.into(),
+            value: 5,
+        });
+        assert_eq!(Some(5), obj.as_number_variable().map(|o| o.value));
+        assert!(obj.as_string_variable().is_none());
+
+        if let Some(o) = obj.as_number_variable_mut() {
+            o.value = 6;
+        }
+        assert_eq!(Some(6), obj.as_number_variable().map(|o| o.value));
+        assert!(obj.as_auxiliary_function_type2_mut().is_none());
+    }
+
+    #[test]
+    fn test_object_id_ord() {
+        let mut ids: Vec<ObjectId> = [ObjectId::NULL, 1000.into(), 0.into(), 0xFFFE.into()].into();
+        ids.sort();
+        assert_eq!(
+            vec![
+                ObjectId::from(0),
+                ObjectId::from(1000),
+                ObjectId::from(0xFFFE),
+                ObjectId::NULL
+            ],
+            ids
+        );
+    }
+
+    #[test]
+    fn test_object_id_bytes() {
+        let id = ObjectId::from(0x1234);
+        assert_eq!(id, ObjectId::from_le_bytes([0x34, 0x12]));
+        assert_eq!(id, ObjectId::from_be_bytes([0x12, 0x34]));
+        assert_eq!([0x34, 0x12], id.to_le_bytes());
+        assert_eq!([0x12, 0x34], id.to_be_bytes());
+        assert_eq!(id, ObjectId::from(id.to_le_bytes()));
+    }
+
+    #[test]
+    fn test_working_set_icon_ref() {
+        let mut working_set = WorkingSet {
+            id: 0.into(),
+            background_colour: 0,
+            selectable: true,
+            active_mask: 1000.into(),
+            object_refs: Vec::new(),
+            macro_refs: Vec::new(),
+            language_codes: Vec::new(),
+        };
+        assert_eq!(None, working_set.icon_ref());
+
+        working_set.object_refs = vec![
+            ObjectRef {
+                id: 20000.into(),
+                offset: Point { x: 0, y: 0 },
+            },
+            ObjectRef {
+                id: 11000.into(),
+                offset: Point { x: 0, y: 40 },
+            },
+        ];
+        assert_eq!(
+            Some(ObjectId::from(20000)),
+            working_set.icon_ref().map(|r| r.id)
+        );
+    }
+
+    #[test]
+    fn test_colour_over() {
+        let half_red = Colour {
+            r: 0xFF,
+            g: 0,
+            b: 0,
+            a: 0x80,
+        };
+        assert_eq!(
+            Colour {
+                r: 0xFF,
+                g: 0x7F,
+                b: 0x7F,
+                a: 0xFF,
+            },
+            half_red.over(&Colour::WHITE)
+        );
+
+        assert_eq!(Colour::RED, Colour::RED.over(&Colour::WHITE));
+        let clear = Colour {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 0,
+        };
+        assert_eq!(Colour::WHITE, clear.over(&Colour::WHITE));
+        assert_eq!(clear, clear.over(&clear));
+        assert_eq!(half_red, half_red.over(&clear));
+    }
+
+    #[test]
+    fn test_vt_version() {
+        for value in 0..=u8::MAX {
+            assert_eq!(value, u8::from(VtVersion::from(value)));
+        }
+
+        assert_eq!(VtVersion::V6, ObjectType::ScalesGraphic.min_vt_version());
+        assert_eq!(VtVersion::V5, ObjectType::Animation.min_vt_version());
+        assert_eq!(
+            VtVersion::V5,
+            ObjectType::ExternalObjectPointer.min_vt_version()
+        );
+        assert_eq!(VtVersion::V6, ObjectType::ColourPalette.min_vt_version());
+        assert!(!VtVersion::V5.supports(ObjectType::ScalesGraphic));
+        assert!(VtVersion::V6.supports(ObjectType::ScalesGraphic));
+        assert!(VtVersion::V2.supports(ObjectType::Button));
+        assert!(!VtVersion::V3.supports(ObjectType::WindowMask));
+        assert!(VtVersion::V4.supports(ObjectType::WindowMask));
+        assert!(VtVersion::Reserved(7).supports(ObjectType::ScalesGraphic));
+    }
+
+    #[test]
+    fn test_number_format() {
+        let mut output_number = OutputNumber {
+            id: 12000.into(),
+            width: 50,
+            height: 20,
+            background_colour: 1,
+            font_attributes: 23000.into(),
+            options: 0,
+            variable_reference: ObjectId::NULL,
+            value: 0,
+            offset: 0,
+            scale: 1.0,
+            nr_of_decimals: 1,
+            format: false,
+            justification: 0,
+            macro_refs: Vec::new(),
+        };
+        assert_eq!("1200.0", output_number.formatted(1200));
+        output_number.format = true;
+        assert_eq!("1.2E3", output_number.formatted(1200));
+        output_number.scale = 0.001;
+        output_number.nr_of_decimals = 2;
+        assert_eq!("1.00E-3", output_number.formatted(1));
+
+        let input_number = InputNumber {
+            id: 9000.into(),
+            width: 50,
+            height: 20,
+            background_colour: 1,
+            font_attributes: 23000.into(),
+            options: 0,
+            variable_reference: ObjectId::NULL,
+            value: 0,
+            min_value: 0,
+            max_value: 10000,
+            offset: -200,
+            scale: 1.0,
+            nr_of_decimals: 1,
+            format: true,
+            justification: 0,
+            options2: 0,
+            macro_refs: Vec::new(),
+        };
+        assert_eq!("1.0E3", input_number.formatted(1200));
+    }
+
+    #[test]
+    fn test_line_suppression() {
+        let mut rectangle = OutputRectangle {
+            id: 14000.into(),
+            line_attributes: 24000.into(),
+            width: 10,
+            height: 10,
+            line_suppression: 0b0000_0101,
+            fill_attributes: ObjectId::NULL,
+            macro_refs: Vec::new(),
+        };
+        assert_eq!(
+            LineSuppression::TOP | LineSuppression::BOTTOM,
+            rectangle.line_suppression()
+        );
+
+        rectangle.set_line_suppression(LineSuppression::RIGHT | LineSuppression::LEFT);
+        assert_eq!(0b0000_1010, rectangle.line_suppression);
+        rectangle.set_line_suppression(LineSuppression::all());
+        assert_eq!(0b0000_1111, rectangle.line_suppression);
+    }
+
+    #[test]
+    fn test_meter_and_bar_graph_options() {
+        let mut meter = OutputMeter {
+            id: 17000.into(),
+            width: 100,
+            needle_colour: 0,
+            border_colour: 0,
+            arc_and_tick_colour: 0,
+            options: 0b1000_1101,
+            nr_of_ticks: 5,
+            start_angle: 0,
+            end_angle: 90,
+            min_value: 0,
+            max_value: 100,
+            variable_reference: ObjectId::NULL,
+            value: 0,
+            macro_refs: Vec::new(),
+        };
+        let options = meter.meter_options();
+        assert!(options.contains(MeterOptions::DRAW_ARC | MeterOptions::DRAW_TICKS));
+        assert!(options.contains(MeterOptions::CLOCKWISE));
+        assert!(!options.contains(MeterOptions::DRAW_BORDER));
+        // Reserved bits survive the round trip
+        meter.set_meter_options(options);
+        assert_eq!(0b1000_1101, meter.options);
+
+        let mut linear = OutputLinearBarGraph {
+            id: 18000.into(),
+            width: 20,
+            height: 100,
+            colour: 0,
+            target_line_colour: 0,
+            options: 0,
+            nr_of_ticks: 0,
+            min_value: 0,
+            max_value: 100,
+            variable_reference: ObjectId::NULL,
+            value: 0,
+            target_value_variable_reference: ObjectId::NULL,
+            target_value: 0,
+            macro_refs: Vec::new(),
+        };
+        for (bit, flag) in LinearBarGraphOptions::all().iter().enumerate() {
+            linear.set_bar_graph_options(flag);
+            assert_eq!(1 << bit, linear.options);
+        }
+        linear.options = 0b0011_0010;
+        assert_eq!(
+            LinearBarGraphOptions::DRAW_TARGET_LINE
+                | LinearBarGraphOptions::HORIZONTAL
+                | LinearBarGraphOptions::GROWS_POSITIVE,
+            linear.bar_graph_options()
+        );
+
+        let mut arched = OutputArchedBarGraph {
+            id: 19000.into(),
+            width: 100,
+            height: 100,
+            colour: 0,
+            target_line_colour: 0,
+            options: 0b0001_1001,
+            start_angle: 0,
+            end_angle: 90,
+            bar_graph_width: 10,
+            min_value: 0,
+            max_value: 100,
+            variable_reference: ObjectId::NULL,
+            value: 0,
+            target_value_variable_reference: ObjectId::NULL,
+            target_value: 0,
+            macro_refs: Vec::new(),
+        };
+        assert_eq!(
+            ArchedBarGraphOptions::DRAW_BORDER
+                | ArchedBarGraphOptions::VALUE_LINE
+                | ArchedBarGraphOptions::CLOCKWISE,
+            arched.bar_graph_options()
+        );
+        arched.set_bar_graph_options(ArchedBarGraphOptions::DRAW_TARGET_LINE);
+        assert_eq!(0b0000_0010, arched.options);
+    }
+
+    #[test]
+    fn test_scale() {
+        assert_eq!(0.5, Scale::Linear.fraction(50, 0, 100));
+        assert_eq!(1.0, Scale::Linear.fraction(150, 0, 100));
+        assert_eq!(0.0, Scale::Linear.fraction(10, 20, 20));
+
+        // 10 is a third of the way from 1 to 1000 on a logarithmic scale
+        let linear = Scale::Linear.fraction(10, 1, 1000);
+        let log = Scale::Logarithmic.fraction(10, 1, 1000);
+        assert!((linear - 9.0 / 999.0).abs() < 1e-6);
+        assert!((log - 1.0 / 3.0).abs() < 1e-6);
+        // 0 is clamped to the minimum of the logarithmic scale
+        assert_eq!(0.0, Scale::Logarithmic.fraction(0, 0, 1000));
+        assert_eq!(1.0, Scale::Logarithmic.fraction(1000, 0, 1000));
+        assert_eq!(0.0, Scale::Logarithmic.fraction(0, 0, 1));
+
+        let mut meter = OutputMeter {
+            id: 17000.into(),
+            width: 100,
+            needle_colour: 0,
+            border_colour: 0,
+            arc_and_tick_colour: 0,
+            options: 0,
+            nr_of_ticks: 0,
+            start_angle: Angle::from_degrees(180).0,
+            end_angle: 0,
+            min_value: 1,
+            max_value: 100,
+            variable_reference: ObjectId::NULL,
+            value: 10,
+            macro_refs: Vec::new(),
+        };
+        // Counter-clockwise from 180 degrees to 0 passes 270 degrees
+        assert!((meter.needle_angle(Scale::Linear) - (180.0 + 180.0 * 9.0 / 99.0)).abs() < 1e-3);
+        assert!((meter.needle_angle(Scale::Logarithmic) - 270.0).abs() < 1e-3);
+        meter.set_meter_options(MeterOptions::CLOCKWISE);
+        assert!((meter.needle_angle(Scale::Logarithmic) - 90.0).abs() < 1e-3);
+
+        let bar_graph = OutputLinearBarGraph {
+            id: 18000.into(),
+            width: 20,
+            height: 100,
+            colour: 0,
+            target_line_colour: 0,
+            options: 0,
+            nr_of_ticks: 0,
+            min_value: 1,
+            max_value: 100,
+            variable_reference: ObjectId::NULL,
+            value: 10,
+            target_value_variable_reference: ObjectId::NULL,
+            target_value: 0,
+            macro_refs: Vec::new(),
+        };
+        assert!((bar_graph.fill_fraction(Scale::Logarithmic) - 0.5).abs() < 1e-6);
+        assert!(bar_graph.fill_fraction(Scale::Linear) < 0.1);
+    }
+}
@@ -1,59 +1,328 @@
 use core::cell::Cell;
 
-use alloc::vec::Vec;
-
-use crate::virtual_terminal_client::VTVersion;
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
 
+use super::id_index::IdIndex;
 use super::*;
 
+/// A summary of the contents of an [`ObjectPool`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolStats {
+    /// The number of objects per object type, only types present in the pool are listed
+    pub object_counts: Vec<(ObjectType, usize)>,
+    pub total_objects: usize,
+    /// The size of the serialized pool in bytes
+    pub total_bytes: usize,
+    /// The number of distinct colour indices used by the objects
+    pub distinct_colours: usize,
+}
+
+impl PoolStats {
+    pub fn count(&self, object_type: ObjectType) -> usize {
+        self.object_counts
+            .iter()
+            .find(|(t, _)| *t == object_type)
+            .map_or(0, |(_, count)| *count)
+    }
+}
+
+/// An optional header some tools prepend to a serialized object pool.
+///
+/// The header consists of the magic bytes [`PoolHeader::MAGIC`], a one byte format version and
+/// the number of objects in the pool as a little-endian `u16`. The magic can not be mistaken for
+/// the start of an object, since `0x50` is not a valid object type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolHeader {
+    pub version: u8,
+    pub object_count: u16,
+}
+
+impl PoolHeader {
+    pub const MAGIC: [u8; 4] = *b"IOP\0";
+    pub const SIZE: usize = 7;
+}
+
+/// A part of a serialized [`ObjectPool`], see [`ObjectPool::to_chunks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolChunk {
+    pub data: Vec<u8>,
+    /// The chunk holds a single object that is larger than the requested chunk size
+    pub oversized: bool,
+}
+
+/// The buffer passed to [`ObjectPool::to_bytes_into`] can not hold the serialized pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferTooSmall {
+    /// The number of bytes the serialized pool needs
+    pub needed: usize,
+}
+
+/// The objects used to resolve colour indices, see [`ObjectPool::colour_resolution_order`].
+///
+/// An index is first mapped through the `ColourMap`, the result is looked up in the
+/// `ColourPalette`. Without a map indices are used as they are, without a palette the standard
+/// palette is used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolutionPlan {
+    pub colour_map: Option<ObjectId>,
+    pub colour_palette: Option<ObjectId>,
+    /// `ColourMap` and `ColourPalette` objects that are ignored because another one is used
+    pub ignored: Vec<ObjectId>,
+}
+
+/// The result of [`ObjectPool::apply_patch`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PatchSummary {
+    /// Ids of the objects that replaced an existing object
+    pub replaced: Vec<ObjectId>,
+    /// Ids of the objects that were not in the pool before
+    pub added: Vec<ObjectId>,
+}
+
 #[derive(Debug)]
 pub struct ObjectPool {
     objects: Vec<Object>,
+    /// Position of the first object with a given id in `objects`
+    id_index: IdIndex,
+    header: Option<PoolHeader>,
     colour_map: [u8; 256],
     colour_palette: [Colour; 256],
-    supported_vt_version: VTVersion,
+    /// Background colour index for objects created with the builders of the pool
+    default_background_index: u8,
 
     size_cache: Cell<Option<usize>>,
 }
 
 impl ObjectPool {
     pub fn new() -> Self {
-        // Setup the default colour map
-        let mut colour_map = [0xFFu8; 256];
-        for i in 0..(colour_map.len() as u8) {
-            colour_map[i as usize] = i;
-        }
-
         ObjectPool {
             objects: Vec::new(),
-            colour_map,
+            id_index: IdIndex::new(),
+            header: None,
+            colour_map: default_colour_map(),
             colour_palette: Colour::COLOUR_PALETTE,
-            supported_vt_version: VTVersion::default(),
+            default_background_index: Colour::GREY_INDEX,
 
             size_cache: Cell::new(None),
         }
     }
 
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut op = Self::new();
+        op.objects.reserve(capacity);
+        op.id_index.reserve(capacity);
+        op
+    }
+
+    /// Create the smallest valid pool, a selectable `WorkingSet` with an empty `DataMask` as its
+    /// active mask.
+    ///
+    /// Both use the default background colour. Objects can be added to the result as usual.
+    pub fn minimal(working_set_id: ObjectId, data_mask_id: ObjectId) -> Self {
+        let mut pool = Self::new();
+        let background_colour = pool.default_background_index;
+        pool.add(Object::WorkingSet(WorkingSet {
+            id: working_set_id,
+            background_colour,
+            selectable: true,
+            active_mask: data_mask_id,
+            object_refs: Vec::new(),
+            macro_refs: Vec::new(),
+            language_codes: Vec::new(),
+        }));
+        pool.add(Object::DataMask(DataMask {
+            id: data_mask_id,
+            background_colour,
+            soft_key_mask: ObjectId::NULL,
+            object_refs: Vec::new(),
+            macro_refs: Vec::new(),
+        }));
+        pool
+    }
+
+    pub fn objects(&self) -> &[Object] {
+        &self.objects
+    }
+
+    /// Iterate mutably over the objects, for edits in bulk.
+    ///
+    /// The ids must not be changed, the id index is not updated afterwards.
+    pub fn objects_mut(&mut self) -> impl Iterator<Item = &mut Object> {
+        self.size_cache.set(None);
+        self.objects.iter_mut()
+    }
+
+    pub fn into_objects(self) -> Vec<Object> {
+        self.objects
+    }
+
+    /// Rebuild the id index from the object list, needed after objects are removed or moved.
+    fn rebuild_index(&mut self) {
+        self.id_index.rebuild(self.objects.iter().map(Object::id));
+    }
+
     pub fn size(&self) -> usize {
         if self.size_cache.get().is_none() {
-            self.size_cache.set(Some(self.as_iop().len()));
+            let mut data = Vec::new();
+            let mut size = 0;
+            for obj in &self.objects {
+                data.clear();
+                obj.write_into(&mut data);
+                size += data.len();
+            }
+            self.size_cache.set(Some(size));
         }
         self.size_cache.get().unwrap_or_default()
     }
 
+    /// Parse a serialized object pool.
+    ///
+    /// A leading [`PoolHeader`] is detected and skipped, it is available through
+    /// [`ObjectPool::header`] afterwards. Parsing stops at the first object that can not be
+    /// parsed, see [`ObjectPool::from_iop_with`] for more control.
     pub fn from_iop<I>(data: I) -> Self
     where
         I: IntoIterator<Item = u8>,
     {
-        let mut data = data.into_iter();
+        Self::from_iop_with(data, ParseOptions::default())
+            .map(|(op, _)| op)
+            .unwrap_or_default()
+    }
+
+    /// Parse a serialized object pool with the given options.
+    ///
+    /// In strict mode any data that can not be parsed, like trailing padding or a truncated
+    /// object, is an error. In lenient mode parsing stops there and the skipped data is reported
+    /// in the returned [`ParseReport`] instead, so lenient parsing never fails.
+    ///
+    /// In both modes the report lists the non-fatal issues found in the parsed objects.
+    pub fn from_iop_with<I>(
+        data: I,
+        options: ParseOptions,
+    ) -> Result<(Self, ParseReport), ParseError>
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        let data: Vec<u8> = data.into_iter().collect();
 
         let mut op = Self::new();
+        let mut warnings = Vec::new();
 
-        while let Ok(o) = Object::read(&mut data) {
-            op.objects.push(o);
+        let mut remaining = &data[..];
+        if remaining.starts_with(&PoolHeader::MAGIC) {
+            if let [version, count_lo, count_hi] = remaining[PoolHeader::MAGIC.len()..]
+                .get(..PoolHeader::SIZE - PoolHeader::MAGIC.len())
+                .unwrap_or_default()
+            {
+                op.header = Some(PoolHeader {
+                    version: *version,
+                    object_count: u16::from_le_bytes([*count_lo, *count_hi]),
+                });
+            }
+            remaining = remaining.get(PoolHeader::SIZE..).unwrap_or_default();
         }
 
-        op
+        while !remaining.is_empty() {
+            let mut iter = remaining.iter();
+            match Object::read(&mut iter.by_ref().copied()) {
+                Ok(o) => {
+                    op.objects.push(o);
+                    remaining = iter.as_slice();
+                }
+                Err(e) if options.strict => return Err(e),
+                Err(_) => {
+                    let id = match remaining {
+                        [lo, hi, ..] => ObjectId::from_le_bytes([*lo, *hi]),
+                        _ => ObjectId::NULL,
+                    };
+                    warnings.push(ParseWarning {
+                        id,
+                        message: format!("skipped {} bytes of unparsable data", remaining.len()),
+                    });
+                    break;
+                }
+            }
+        }
+        op.rebuild_index();
+        warnings.extend(op.lint());
+
+        Ok((op, ParseReport { warnings }))
+    }
+
+    /// List the id, type and offset of every object in a serialized pool without decoding the
+    /// objects.
+    ///
+    /// Offsets are relative to the start of `data`, after the [`PoolHeader`] if there is one.
+    pub fn scan_headers(data: &[u8]) -> Result<Vec<(ObjectId, ObjectType, usize)>, ParseError> {
+        let mut offset = match data.starts_with(&PoolHeader::MAGIC) {
+            true => PoolHeader::SIZE.min(data.len()),
+            false => 0,
+        };
+
+        let mut headers = Vec::new();
+        while offset < data.len() {
+            let (id, object_type, len) = Object::read_header(&data[offset..])?;
+            headers.push((id, object_type, offset));
+            offset += len;
+        }
+        Ok(headers)
+    }
+
+    /// Find non-fatal issues in the objects.
+    fn lint(&self) -> Vec<ParseWarning> {
+        let mut warnings = Vec::new();
+        for obj in &self.objects {
+            let id = obj.id();
+            for index in obj.colour_indices() {
+                if !Colour::is_defined_index(index) {
+                    warnings.push(ParseWarning {
+                        id,
+                        message: format!("uses reserved colour index {}", index),
+                    });
+                }
+            }
+            for child in obj.child_ids() {
+                if self.object_by_id(child).is_none() {
+                    warnings.push(ParseWarning {
+                        id,
+                        message: format!("references undefined object {}", u16::from(child)),
+                    });
+                }
+            }
+            if let Object::WorkingSet(o) = obj {
+                if !o.selectable && self.validate_working_set_active_mask(o).is_err() {
+                    warnings.push(ParseWarning {
+                        id,
+                        message: "is not selectable and has no valid active mask".into(),
+                    });
+                }
+            }
+            if let Object::Macro(o) = obj {
+                if let Some(command) = unknown_macro_command(&o.commands) {
+                    warnings.push(ParseWarning {
+                        id,
+                        message: format!("contains unknown command 0x{:02X}", command),
+                    });
+                }
+                if let Err(ValidationError::TruncatedMacro { offset, .. }) = o.validate() {
+                    warnings.push(ParseWarning {
+                        id,
+                        message: format!("contains a truncated command at byte {}", offset),
+                    });
+                }
+            }
+        }
+        warnings
+    }
+
+    /// The header the pool was parsed with, if it had one.
+    ///
+    /// [`ObjectPool::as_iop`] never writes a header.
+    pub fn header(&self) -> Option<PoolHeader> {
+        self.header
     }
 
     pub fn as_iop(&self) -> Vec<u8> {
@@ -66,12 +335,385 @@ impl ObjectPool {
         data
     }
 
+    /// Serialize the pool into `buf`, returning the number of bytes written.
+    ///
+    /// Unlike [`ObjectPool::as_iop`] the pool is not collected in a `Vec` first, only one object
+    /// at a time is held in memory. Nothing is written if [`ObjectPool::size`] does not fit in
+    /// `buf`. Should the objects turn out larger than the cached size while writing, `buf` may
+    /// be partly written when the error is returned.
+    pub fn to_bytes_into(&self, buf: &mut [u8]) -> Result<usize, BufferTooSmall> {
+        let needed = self.size();
+        if buf.len() < needed {
+            return Err(BufferTooSmall { needed });
+        }
+
+        // One scratch buffer for all objects, sized for the largest object
+        let mut data = Vec::new();
+        let mut written = 0;
+        for obj in &self.objects {
+            data.clear();
+            obj.write_into(&mut data);
+            // Checked again in case the cached size is out of date
+            let Some(dest) = buf.get_mut(written..written + data.len()) else {
+                self.size_cache.set(None);
+                return Err(BufferTooSmall {
+                    needed: self.size(),
+                });
+            };
+            dest.copy_from_slice(&data);
+            written += data.len();
+        }
+        Ok(written)
+    }
+
+    /// Get a checksum over the serialized pool, for verifying an upload.
+    ///
+    /// ISO 11783-6 does not define a checksum for object pools, this is the CRC-16/CCITT-FALSE
+    /// (polynomial 0x1021, initial value 0xFFFF) of [`ObjectPool::as_iop`].
+    pub fn checksum(&self) -> u16 {
+        crc16_ccitt(&self.as_iop())
+    }
+
+    /// Serialize the pool into chunks of at most `max_chunk` bytes, split on object boundaries.
+    ///
+    /// Objects are never split, an object larger than `max_chunk` is put in a chunk of its own
+    /// that is flagged as oversized.
+    pub fn to_chunks(&self, max_chunk: usize) -> Vec<PoolChunk> {
+        let mut chunks = Vec::new();
+        let mut current = Vec::new();
+
+        for obj in &self.objects {
+            let data = obj.write();
+            if !current.is_empty() && current.len() + data.len() > max_chunk {
+                chunks.push(PoolChunk {
+                    data: core::mem::take(&mut current),
+                    oversized: false,
+                });
+            }
+            if data.len() > max_chunk {
+                chunks.push(PoolChunk {
+                    data,
+                    oversized: true,
+                });
+            } else {
+                current.extend(data);
+            }
+        }
+        if !current.is_empty() {
+            chunks.push(PoolChunk {
+                data: current,
+                oversized: false,
+            });
+        }
+
+        chunks
+    }
+
+    /// Check that the pool contains exactly one `WorkingSet` object.
+    pub fn validate_working_set_count(&self) -> Result<(), ValidationError> {
+        let working_sets: Vec<ObjectId> = self
+            .objects_by_type(ObjectType::WorkingSet)
+            .iter()
+            .map(|o| o.id())
+            .collect();
+
+        match working_sets.len() {
+            0 => Err(ValidationError::MissingWorkingSet),
+            1 => Ok(()),
+            _ => Err(ValidationError::MultipleWorkingSets(working_sets)),
+        }
+    }
+
+    /// Check that every object reference points to an object in the pool.
+    ///
+    /// References to [`ObjectId::NULL`] are allowed. See
+    /// [`ObjectPool::has_dangling_references`] for a faster check without the details.
+    pub fn validate_references(&self) -> Result<(), ValidationError> {
+        let dangling: Vec<(ObjectId, ObjectId)> = self.dangling_references().collect();
+        if dangling.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError::DanglingReferences(dangling))
+        }
+    }
+
+    /// Returns true if any object references an id that is not in the pool.
+    ///
+    /// Stops at the first dangling reference, see [`ObjectPool::validate_references`] for all
+    /// of them.
+    pub fn has_dangling_references(&self) -> bool {
+        self.dangling_references().next().is_some()
+    }
+
+    /// Get the referencing object and the missing id of every dangling reference, lazily.
+    fn dangling_references(&self) -> impl Iterator<Item = (ObjectId, ObjectId)> + '_ {
+        self.objects.iter().flat_map(move |obj| {
+            obj.child_ids()
+                .into_iter()
+                .filter(|&child| self.object_by_id(child).is_none())
+                .map(move |child| (obj.id(), child))
+        })
+    }
+
+    /// Check that a VT of the given version supports every object in the pool.
+    pub fn validate_for_version(&self, version: VtVersion) -> Result<(), ValidationError> {
+        match self
+            .objects
+            .iter()
+            .find(|o| !version.supports(o.object_type()))
+        {
+            Some(obj) => Err(ValidationError::UnsupportedObject {
+                id: obj.id(),
+                object_type: obj.object_type(),
+                version,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that the active mask of every `WorkingSet` is a `DataMask` or `AlarmMask`.
+    pub fn validate_active_mask(&self) -> Result<(), ValidationError> {
+        for obj in &self.objects {
+            if let Object::WorkingSet(working_set) = obj {
+                self.validate_working_set_active_mask(working_set)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that the active mask of `working_set` is a `DataMask` or `AlarmMask`.
+    fn validate_working_set_active_mask(
+        &self,
+        working_set: &WorkingSet,
+    ) -> Result<(), ValidationError> {
+        let found = self
+            .object_by_id(working_set.active_mask)
+            .map(Object::object_type);
+        if !matches!(
+            found,
+            Some(ObjectType::DataMask) | Some(ObjectType::AlarmMask)
+        ) {
+            return Err(ValidationError::InvalidActiveMask {
+                id: working_set.id,
+                active_mask: working_set.active_mask,
+                found,
+            });
+        }
+        Ok(())
+    }
+
+    /// Run every check that does not depend on the VT and collect all errors.
+    ///
+    /// This covers the working set count, duplicate ids, dangling references, the active mask,
+    /// reference cycles and the checks of individual objects, e.g.
+    /// [`PictureGraphic::validate`]. The VT dependent checks are
+    /// [`ObjectPool::validate_for_version`] and [`SoftKeyMask::validate_for_vt`].
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        errors.extend(self.validate_working_set_count().err());
+        errors.extend(
+            self.duplicate_ids()
+                .into_iter()
+                .map(ValidationError::DuplicateId),
+        );
+        errors.extend(self.validate_references().err());
+        errors.extend(self.validate_active_mask().err());
+        errors.extend(
+            self.reference_cycles()
+                .into_iter()
+                .map(ValidationError::ReferenceCycle),
+        );
+        for obj in &self.objects {
+            let result = match obj {
+                Object::OutputPolygon(o) => o.validate(),
+                Object::PictureGraphic(o) => o.validate(),
+                Object::Macro(o) => o.validate(),
+                Object::GraphicData(o) => o.validate(),
+                Object::InputString(o) => validate_string(o.id, &o.value),
+                Object::OutputString(o) => validate_string(o.id, &o.value),
+                Object::StringVariable(o) => validate_string(o.id, &o.value),
+                Object::InputAttributes(o) => validate_string(o.id, &o.validation_string),
+                _ => Ok(()),
+            };
+            errors.extend(result.err());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Get every loop of references, each starting at the object of the loop that is visited
+    /// first.
+    fn reference_cycles(&self) -> Vec<Vec<ObjectId>> {
+        let mut done = BTreeSet::new();
+        let mut cycles = Vec::new();
+        for obj in &self.objects {
+            self.find_cycles(obj.id(), &mut Vec::new(), &mut done, &mut cycles);
+        }
+        cycles
+    }
+
+    fn find_cycles(
+        &self,
+        id: ObjectId,
+        path: &mut Vec<ObjectId>,
+        done: &mut BTreeSet<ObjectId>,
+        cycles: &mut Vec<Vec<ObjectId>>,
+    ) {
+        if let Some(start) = path.iter().position(|&ancestor| ancestor == id) {
+            cycles.push(path[start..].to_vec());
+            return;
+        }
+        if done.contains(&id) {
+            return;
+        }
+        let Some(obj) = self.object_by_id(id) else {
+            return;
+        };
+
+        path.push(id);
+        for child in obj.child_ids() {
+            self.find_cycles(child, path, done, cycles);
+        }
+        path.pop();
+        done.insert(id);
+    }
+
     pub fn add(&mut self, obj: Object) {
+        self.id_index.insert(obj.id(), self.objects.len());
         self.objects.push(obj);
+        self.size_cache.set(None);
+    }
+
+    /// Replace the object with the same id as `obj`, returning the old object.
+    ///
+    /// If the pool has no object with that id, `obj` is added and `None` is returned.
+    pub fn replace_object(&mut self, obj: Object) -> Option<Object> {
+        match self.object_mut(obj.id()) {
+            Some(existing) => {
+                let old = core::mem::replace(existing, obj);
+                self.size_cache.set(None);
+                Some(old)
+            }
+            None => {
+                self.add(obj);
+                None
+            }
+        }
+    }
+
+    /// Get the lowest id that is not used by any object, `None` if every id is in use.
+    pub fn next_free_id(&self) -> Option<ObjectId> {
+        let used: BTreeSet<u16> = self.objects.iter().map(|o| u16::from(o.id())).collect();
+        (0..u16::from(ObjectId::NULL))
+            .find(|id| !used.contains(id))
+            .map(ObjectId::from)
+    }
+
+    /// Add a copy of the object with the given id under the id from
+    /// [`ObjectPool::next_free_id`], returning the new id.
+    ///
+    /// The copy references the same children as the original. Returns `None` if the object
+    /// does not exist or the pool has no free ids left.
+    pub fn clone_object(&mut self, id: ObjectId) -> Option<ObjectId> {
+        let mut copy = self.object_by_id(id)?.clone();
+        let new_id = self.next_free_id()?;
+        *copy.id_mut() = new_id;
+        self.add(copy);
+        Some(new_id)
+    }
+
+    /// Replace or add every object of `patch`, leaving the other objects untouched.
+    ///
+    /// Objects are matched by id, see [`ObjectPool::replace_object`]. The ids in the summary
+    /// are in the order of `patch`.
+    pub fn apply_patch(&mut self, patch: &ObjectPool) -> PatchSummary {
+        let mut summary = PatchSummary::default();
+        for obj in patch {
+            let id = obj.id();
+            match self.replace_object(obj.clone()) {
+                Some(_) => summary.replaced.push(id),
+                None => summary.added.push(id),
+            }
+        }
+        summary
+    }
+
+    /// Get the ids that are used by more than one object, in order of first use.
+    pub fn duplicate_ids(&self) -> Vec<ObjectId> {
+        let mut duplicates = Vec::new();
+        for (i, obj) in self.objects.iter().enumerate() {
+            let id = obj.id();
+            if self.id_index.get(id) != Some(i) && !duplicates.contains(&id) {
+                duplicates.push(id);
+            }
+        }
+        duplicates
+    }
+
+    /// Get a summary of the objects in the pool.
+    pub fn statistics(&self) -> PoolStats {
+        let mut object_counts = Vec::new();
+        for &object_type in ObjectType::ALL {
+            let count = self.objects_by_type(object_type).len();
+            if count > 0 {
+                object_counts.push((object_type, count));
+            }
+        }
+
+        let colours: BTreeSet<u8> = self
+            .objects
+            .iter()
+            .flat_map(|o| o.colour_indices())
+            .collect();
+
+        PoolStats {
+            object_counts,
+            total_objects: self.objects.len(),
+            total_bytes: self.size(),
+            distinct_colours: colours.len(),
+        }
+    }
+
+    /// Get the ids of the `FontAttributes` referenced by any object in the pool.
+    ///
+    /// `FontAttributes` that are not listed can be removed. The ids are not checked to exist.
+    pub fn used_font_ids(&self) -> BTreeSet<ObjectId> {
+        self.objects
+            .iter()
+            .filter_map(|obj| match obj {
+                Object::InputBoolean(o) => Some(o.foreground_colour),
+                Object::InputString(o) => Some(o.font_attributes),
+                Object::InputNumber(o) => Some(o.font_attributes),
+                Object::OutputString(o) => Some(o.font_attributes),
+                Object::OutputNumber(o) => Some(o.font_attributes),
+                Object::GraphicsContext(o) => Some(o.font_attributes_object),
+                _ => None,
+            })
+            .filter(|&id| id != ObjectId::NULL)
+            .collect()
+    }
+
+    /// Get every colour index used by the objects, including the pixels of indexed picture
+    /// graphics.
+    pub fn used_colour_indices(&self) -> BTreeSet<u8> {
+        let mut indices = BTreeSet::new();
+        for obj in &self.objects {
+            indices.extend(obj.colour_indices());
+            if let Object::PictureGraphic(o) = obj {
+                indices.extend(o.pixel_indices().unwrap_or_default());
+            }
+        }
+        indices
     }
 
     pub fn object_by_id(&self, id: ObjectId) -> Option<&Object> {
-        self.objects.iter().find(|&o| o.id() == id)
+        self.id_index
+            .get(id)
+            .and_then(|index| self.objects.get(index))
     }
 
     pub fn objects_by_type(&self, object_type: ObjectType) -> Vec<&Object> {
@@ -81,62 +723,2744 @@ impl ObjectPool {
             .collect()
     }
 
-    // Get objects by type
+    /// Get all objects for which `pred` returns `true`, in pool order.
+    pub fn find_by_predicate(&self, pred: impl Fn(&Object) -> bool) -> Vec<&Object> {
+        self.objects.iter().filter(|o| pred(o)).collect()
+    }
 
-    pub fn working_set_object(&self) -> Option<&WorkingSet> {
-        match &self.objects_by_type(ObjectType::WorkingSet).first() {
-            Some(Object::WorkingSet(o)) => Some(o),
-            _ => None,
+    /// Get the ids of all objects that reference `target`.
+    ///
+    /// This is the inverse of [`Object::child_ids`].
+    pub fn objects_referencing(&self, target: ObjectId) -> Vec<ObjectId> {
+        if target == ObjectId::NULL {
+            return Vec::new();
         }
-    }
 
-    pub fn data_mask_objects(&self) -> Vec<&DataMask> {
-        let r: Vec<&DataMask> = self
-            .objects_by_type(ObjectType::DataMask)
+        self.objects
             .iter()
-            .filter_map(|&o| match o {
-                Object::DataMask(o) => Some(o),
-                _ => None,
-            })
+            .filter(|o| o.child_ids().contains(&target))
+            .map(|o| o.id())
+            .collect()
+    }
+
+    /// Remove the object with the given id, unless other objects still reference it.
+    ///
+    /// On refusal the ids of the referring objects are returned. If no object with the given
+    /// id exists, an empty list is returned as error.
+    pub fn try_remove(&mut self, id: ObjectId) -> Result<Object, Vec<ObjectId>> {
+        let referrers: Vec<ObjectId> = self
+            .objects_referencing(id)
+            .into_iter()
+            .filter(|&r| r != id)
             .collect();
-        r
+        if !referrers.is_empty() {
+            return Err(referrers);
+        }
+
+        match self.id_index.get(id) {
+            Some(index) => {
+                let removed = self.objects.remove(index);
+                self.rebuild_index();
+                self.size_cache.set(None);
+                Ok(removed)
+            }
+            None => Err(Vec::new()),
+        }
     }
 
-    pub fn picture_graphic_objects(&self) -> Vec<&PictureGraphic> {
-        let r: Vec<&PictureGraphic> = self
-            .objects_by_type(ObjectType::PictureGraphic)
-            .iter()
-            .filter_map(|&o| match o {
-                Object::PictureGraphic(o) => Some(o),
-                _ => None,
-            })
+    /// Keep only the objects for which `keep` returns true, in their original order.
+    ///
+    /// References to removed objects are left as they are, see
+    /// [`ObjectPool::has_dangling_references`] to find them.
+    pub fn retain(&mut self, keep: impl Fn(&Object) -> bool) {
+        self.objects.retain(keep);
+        self.rebuild_index();
+        self.size_cache.set(None);
+    }
+
+    /// Reorder the objects so referenced objects come before the objects referencing them,
+    /// with the `WorkingSet` first.
+    ///
+    /// Some terminals expect attributes and variables to be defined before they are used.
+    /// Objects are visited in their current order and a reference back to an object that is
+    /// still being placed (a cycle, e.g. through an `ObjectPointer`) is ignored, so the result
+    /// only depends on the current order.
+    pub fn topological_sort(&mut self) {
+        let mut placed = BTreeSet::new();
+        let mut order = Vec::with_capacity(self.objects.len());
+
+        for obj in &self.objects {
+            if obj.object_type() == ObjectType::WorkingSet && placed.insert(obj.id()) {
+                order.push(obj.id());
+            }
+        }
+        for obj in &self.objects {
+            self.place_after_refs(obj.id(), &mut placed, &mut order);
+        }
+
+        let indices: Vec<usize> = order
+            .into_iter()
+            .filter_map(|id| self.id_index.get(id))
             .collect();
-        r
+        let mut objects: Vec<Option<Object>> = self.objects.drain(..).map(Some).collect();
+        self.objects = indices
+            .into_iter()
+            .filter_map(|index| objects[index].take())
+            .collect();
+        // Objects with a duplicate id are not indexed, they keep their relative order at the end
+        self.objects.extend(objects.into_iter().flatten());
+        self.rebuild_index();
+        self.size_cache.set(None);
     }
 
-    // Get typed objects by id
+    /// Append the objects referenced by `id` and then `id` itself to `order`, post order.
+    fn place_after_refs(
+        &self,
+        id: ObjectId,
+        placed: &mut BTreeSet<ObjectId>,
+        order: &mut Vec<ObjectId>,
+    ) {
+        let Some(obj) = self.object_by_id(id) else {
+            return;
+        };
+        // Marked before the children are visited, so cycles end here.
+        if !placed.insert(id) {
+            return;
+        }
+        for child in obj.child_ids() {
+            self.place_after_refs(child, placed, order);
+        }
+        order.push(id);
+    }
 
-    pub fn data_mask_object_by_id(&self, id: ObjectId) -> Option<&DataMask> {
-        match &self.object_by_id(id) {
-            Some(Object::DataMask(o)) => Some(o),
-            _ => None,
+    /// Remove the object with the given id and set all references to it to [`ObjectId::NULL`].
+    pub fn force_remove(&mut self, id: ObjectId) -> Option<Object> {
+        let index = self.id_index.get(id)?;
+        let removed = self.objects.remove(index);
+        self.rebuild_index();
+
+        for obj in &mut self.objects {
+            for reference in obj.child_ids_mut() {
+                if *reference == id {
+                    *reference = ObjectId::NULL;
+                }
+            }
         }
+
+        self.size_cache.set(None);
+        Some(removed)
     }
 
-    pub fn line_attributes_object_by_id(&self, id: ObjectId) -> Option<&LineAttributes> {
-        match &self.object_by_id(id) {
-            Some(Object::LineAttributes(o)) => Some(o),
-            _ => None,
+    /// Get all descendants of `root` together with their absolute position.
+    ///
+    /// The offsets of the [`ObjectRef`]s along the path are accumulated, with `root` at the
+    /// origin. The root itself is not included. Objects referenced more than once are listed
+    /// once for every reference. NULL references, references to missing objects and references
+    /// back to an ancestor are skipped.
+    pub fn flatten_refs(&self, root: ObjectId) -> Vec<(ObjectId, Point<i16>)> {
+        let mut result = Vec::new();
+        let mut path = vec![root];
+        self.flatten_refs_into(root, Point::default(), &mut path, &mut result);
+        result
+    }
+
+    /// Get the descendants of `mask` that do not fit within a mask of the given size.
+    ///
+    /// Objects partly outside the mask are reported as well. For objects without a size of
+    /// their own only the position is checked.
+    pub fn out_of_bounds_objects(&self, mask: ObjectId, width: u16, height: u16) -> Vec<ObjectId> {
+        let mut result = Vec::new();
+        for (id, position) in self.flatten_refs(mask) {
+            let size = self
+                .object_by_id(id)
+                .and_then(|o| o.dimensions())
+                .unwrap_or_default();
+            let right = position.x as i32 + size.x as i32;
+            let bottom = position.y as i32 + size.y as i32;
+            if (position.x < 0 || position.y < 0 || right > width as i32 || bottom > height as i32)
+                && !result.contains(&id)
+            {
+                result.push(id);
+            }
         }
+        result
     }
 
-    pub fn color_by_index(&self, index: u8) -> Colour {
-        self.colour_palette[self.colour_map[index as usize] as usize]
+    /// Get the area `id` covers on screen, as the offset and size relative to its own position.
+    ///
+    /// Objects with a size of their own, see [`Object::dimensions`], cover exactly that area.
+    /// This includes a `Container`, which clips its children to its declared size, however far
+    /// they extend. Other objects cover the union of their positioned children, e.g. a mask or
+    /// an `ObjectPointer`. Returns `None` if nothing is covered.
+    pub fn bounding_box(&self, id: ObjectId) -> Option<(Point<i16>, Point<u16>)> {
+        let (left, top, right, bottom) = self.bounds(id, &mut Vec::new())?;
+        Some((
+            Point {
+                x: left.clamp(i16::MIN.into(), i16::MAX.into()) as i16,
+                y: top.clamp(i16::MIN.into(), i16::MAX.into()) as i16,
+            },
+            Point {
+                x: (right - left).min(u16::MAX.into()) as u16,
+                y: (bottom - top).min(u16::MAX.into()) as u16,
+            },
+        ))
     }
-}
 
-impl Default for ObjectPool {
-    fn default() -> Self {
-        Self::new()
+    /// The left, top, right and bottom edge of [`ObjectPool::bounding_box`].
+    fn bounds(&self, id: ObjectId, path: &mut Vec<ObjectId>) -> Option<(i32, i32, i32, i32)> {
+        let obj = self.object_by_id(id)?;
+        if let Some(size) = obj.dimensions() {
+            return Some((0, 0, size.x.into(), size.y.into()));
+        }
+        if path.contains(&id) {
+            return None;
+        }
+
+        let children = match obj {
+            Object::ObjectPointer(o) => vec![(o.value, Point::default())],
+            _ => obj.object_refs().iter().map(|r| (r.id, r.offset)).collect(),
+        };
+        path.push(id);
+        let bounds = children
+            .into_iter()
+            .filter_map(|(child, offset)| {
+                let (left, top, right, bottom) = self.bounds(child, path)?;
+                let (x, y) = (i32::from(offset.x), i32::from(offset.y));
+                Some((left + x, top + y, right + x, bottom + y))
+            })
+            .reduce(|a, b| (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3)));
+        path.pop();
+        bounds
+    }
+
+    fn flatten_refs_into(
+        &self,
+        id: ObjectId,
+        origin: Point<i16>,
+        path: &mut Vec<ObjectId>,
+        result: &mut Vec<(ObjectId, Point<i16>)>,
+    ) {
+        let Some(obj) = self.object_by_id(id) else {
+            return;
+        };
+
+        for object_ref in obj.object_refs() {
+            if object_ref.id == ObjectId::NULL
+                || path.contains(&object_ref.id)
+                || self.object_by_id(object_ref.id).is_none()
+            {
+                continue;
+            }
+
+            let position = Point {
+                x: origin.x.saturating_add(object_ref.offset.x),
+                y: origin.y.saturating_add(object_ref.offset.y),
+            };
+            result.push((object_ref.id, position));
+
+            path.push(object_ref.id);
+            self.flatten_refs_into(object_ref.id, position, path, result);
+            path.pop();
+        }
+    }
+
+    /// Get the position of `target` relative to `root`, like [`ObjectPool::flatten_refs`] for
+    /// a single object.
+    ///
+    /// If `target` is referenced along more than one path, the first path depth first is used.
+    /// Returns `None` if `target` cannot be reached from `root`.
+    pub fn absolute_offset(&self, root: ObjectId, target: ObjectId) -> Option<Point<i16>> {
+        if root == target {
+            return self.object_by_id(root).map(|_| Point::default());
+        }
+        self.absolute_offset_from(root, target, Point::default(), &mut BTreeSet::from([root]))
+    }
+
+    fn absolute_offset_from(
+        &self,
+        id: ObjectId,
+        target: ObjectId,
+        origin: Point<i16>,
+        visited: &mut BTreeSet<ObjectId>,
+    ) -> Option<Point<i16>> {
+        for object_ref in self.object_by_id(id)?.object_refs() {
+            // An object that was searched before does not lead to the target along another path
+            if object_ref.id == ObjectId::NULL || visited.contains(&object_ref.id) {
+                continue;
+            }
+
+            let position = Point {
+                x: origin.x.saturating_add(object_ref.offset.x),
+                y: origin.y.saturating_add(object_ref.offset.y),
+            };
+            if object_ref.id == target {
+                return self.object_by_id(target).map(|_| position);
+            }
+
+            visited.insert(object_ref.id);
+            let found = self.absolute_offset_from(object_ref.id, target, position, visited);
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
+    /// Move the children of `mask` by `delta`.
+    ///
+    /// The delta is added to the offset of every [`ObjectRef`] of `mask`, saturating at the
+    /// bounds of `i16`. Objects placed by an [`ObjectRef`] move along with it, so their own
+    /// [`ObjectRef`]s are left alone. With `recursive` the [`ObjectRef`]s of the objects `mask`
+    /// reaches through other references, like the keys of its soft key mask, are shifted as
+    /// well, each object once. Nothing happens if `mask` is not in the pool.
+    pub fn shift_all_positions(&mut self, mask: ObjectId, delta: Point<i16>, recursive: bool) {
+        let mut ids = vec![mask];
+        if recursive {
+            let mut visited = BTreeSet::from([mask]);
+            let mut next = 0;
+            while let Some(obj) = ids.get(next).and_then(|&id| self.object_by_id(id)) {
+                next += 1;
+                let placed: BTreeSet<ObjectId> = obj.object_refs().iter().map(|r| r.id).collect();
+                for child in obj.child_ids() {
+                    if !placed.contains(&child)
+                        && self.object_by_id(child).is_some()
+                        && visited.insert(child)
+                    {
+                        ids.push(child);
+                    }
+                }
+            }
+        }
+
+        for id in ids {
+            if let Some(obj) = self.object_mut(id) {
+                for object_ref in obj.object_refs_mut() {
+                    object_ref.offset.x = object_ref.offset.x.saturating_add(delta.x);
+                    object_ref.offset.y = object_ref.offset.y.saturating_add(delta.y);
+                }
+            }
+        }
+        self.size_cache.set(None);
+    }
+
+    /// Get all objects reachable from `root` through [`Object::child_ids`], depth first.
+    ///
+    /// Every object is listed once, starting with `root` itself. References to missing objects
+    /// are skipped.
+    pub fn walk_from(&self, root: ObjectId) -> Vec<&Object> {
+        let mut result = Vec::new();
+        self.walk_once(root, &mut BTreeSet::new(), &mut result);
+        result
+    }
+
+    /// Add `id` and its descendants to `result`, skipping objects that were visited before.
+    fn walk_once<'a>(
+        &'a self,
+        id: ObjectId,
+        visited: &mut BTreeSet<ObjectId>,
+        result: &mut Vec<&'a Object>,
+    ) {
+        if !visited.insert(id) {
+            return;
+        }
+        let Some(obj) = self.object_by_id(id) else {
+            return;
+        };
+
+        result.push(obj);
+        for child in obj.child_ids() {
+            self.walk_once(child, visited, result);
+        }
+    }
+
+    /// Iterate over `root` and its descendants depth first, together with their depth below
+    /// `root`.
+    ///
+    /// Objects referenced more than once are listed for every reference, like
+    /// [`ObjectPool::tree_string`] does. References to missing objects and references back to
+    /// an ancestor are skipped.
+    pub fn iter_with_depth(&self, root: ObjectId) -> impl Iterator<Item = (&Object, u16)> + '_ {
+        DepthFirst {
+            pool: self,
+            stack: vec![(root, 0)],
+            path: Vec::new(),
+        }
+    }
+
+    /// Get the length of the deepest chain of references below `root`, 0 if `root` references
+    /// nothing.
+    ///
+    /// Some VTs limit how deep objects may be nested. References back to an ancestor are not
+    /// followed.
+    pub fn max_depth(&self, root: ObjectId) -> u16 {
+        self.depth_below(root, &mut Vec::new(), &mut BTreeMap::new())
+            .0
+    }
+
+    /// The depth of [`ObjectPool::max_depth`] and whether no reference back to an ancestor was
+    /// skipped below `id`.
+    ///
+    /// Only such depths do not depend on the path to `id`, they are remembered so shared
+    /// objects are visited once.
+    fn depth_below(
+        &self,
+        id: ObjectId,
+        path: &mut Vec<ObjectId>,
+        depths: &mut BTreeMap<ObjectId, u16>,
+    ) -> (u16, bool) {
+        if let Some(&depth) = depths.get(&id) {
+            return (depth, true);
+        }
+        let Some(obj) = self.object_by_id(id) else {
+            return (0, true);
+        };
+
+        path.push(id);
+        let (mut depth, mut complete) = (0, true);
+        for child in obj.child_ids() {
+            if self.object_by_id(child).is_none() {
+                continue;
+            }
+            if path.contains(&child) {
+                complete = false;
+                continue;
+            }
+            let (child_depth, child_complete) = self.depth_below(child, path, depths);
+            depth = depth.max(child_depth.saturating_add(1));
+            complete &= child_complete;
+        }
+        path.pop();
+        if complete {
+            depths.insert(id, depth);
+        }
+        (depth, complete)
+    }
+
+    /// Visit `id` and its descendants, depth first.
+    ///
+    /// The visitor gets the id, the depth and whether the id is a reference back to an ancestor,
+    /// in which case its children are not visited.
+    fn walk(
+        &self,
+        id: ObjectId,
+        depth: usize,
+        path: &mut Vec<ObjectId>,
+        visit: &mut dyn FnMut(ObjectId, usize, bool),
+    ) {
+        if path.contains(&id) {
+            visit(id, depth, true);
+            return;
+        }
+        visit(id, depth, false);
+
+        if let Some(obj) = self.object_by_id(id) {
+            path.push(id);
+            for child in obj.child_ids() {
+                self.walk(child, depth + 1, path, visit);
+            }
+            path.pop();
+        }
+    }
+
+    /// Get an indented outline of the objects reachable from `root`, for debugging.
+    ///
+    /// Each line holds the object type, the id and a few key attributes. References back to an
+    /// ancestor are marked with `(cycle)`, references to missing objects with `(missing)`.
+    pub fn tree_string(&self, root: ObjectId) -> String {
+        let mut result = String::new();
+        self.walk(root, 0, &mut Vec::new(), &mut |id, depth, cycle| {
+            let indent = "  ".repeat(depth);
+            let line = match self.object_by_id(id) {
+                Some(obj) if cycle => {
+                    format!("{:?} {} (cycle)", obj.object_type(), u16::from(id))
+                }
+                Some(obj) => {
+                    format!("{:?} {}{}", obj.object_type(), u16::from(id), describe(obj))
+                }
+                None => format!("{} (missing)", u16::from(id)),
+            };
+            result.push_str(&indent);
+            result.push_str(&line);
+            result.push('\n');
+        });
+        result
+    }
+
+    /// Get a hash of the objects in the pool.
+    ///
+    /// The hash does not depend on the order of the objects and is stable across runs and
+    /// platforms, so it can be stored to detect changes in a pool.
+    pub fn content_hash(&self) -> u64 {
+        self.objects
+            .iter()
+            .map(|o| fnv1a(&o.write()))
+            .fold(0, u64::wrapping_add)
+    }
+
+    /// Get the objects sorted by id, for comparisons that ignore the object order.
+    fn sorted_objects(&self) -> Vec<&Object> {
+        let mut objects: Vec<&Object> = self.objects.iter().collect();
+        objects.sort_by_key(|o| o.id());
+        objects
+    }
+
+    /// Get an object to change, the cached size is cleared as the change can affect it.
+    fn object_mut(&mut self, id: ObjectId) -> Option<&mut Object> {
+        let index = self.id_index.get(id)?;
+        self.size_cache.set(None);
+        self.objects.get_mut(index)
+    }
+
+    /// Apply VT commands in order, returning the result of each command.
+    ///
+    /// A failing command leaves the pool unchanged and does not stop the following commands.
+    pub fn apply_commands(&mut self, cmds: &[VtCommand]) -> Vec<Result<(), VtError>> {
+        cmds.iter()
+            .map(|cmd| match cmd {
+                VtCommand::ChangeNumericValue { id, value } => {
+                    self.change_numeric_value(*id, *value)
+                }
+                VtCommand::ChangeStringValue { id, value } => {
+                    self.change_string_value(*id, value.as_str())
+                }
+                VtCommand::ChangeActiveMask { working_set, mask } => {
+                    self.change_active_mask(*working_set, *mask)
+                }
+                VtCommand::ChangeAttribute { id, aid, value } => {
+                    self.change_attribute(*id, *aid, *value)
+                }
+                VtCommand::HideShow { id, show } => self.hide_show(*id, *show),
+            })
+            .collect()
+    }
+
+    /// Change the value of a variable, input, output or object pointer object.
+    pub fn change_numeric_value(&mut self, id: ObjectId, value: u32) -> Result<(), VtError> {
+        let obj = self.object_mut(id).ok_or(VtError::InvalidObjectId)?;
+        let to_u8 = |v: u32| u8::try_from(v).map_err(|_| VtError::InvalidValue);
+        let to_u16 = |v: u32| u16::try_from(v).map_err(|_| VtError::InvalidValue);
+
+        match obj {
+            Object::NumberVariable(o) => o.value = value,
+            Object::InputNumber(o) => {
+                if value < o.min_value || value > o.max_value {
+                    return Err(VtError::InvalidValue);
+                }
+                o.value = value;
+            }
+            Object::OutputNumber(o) => o.value = value,
+            Object::InputBoolean(o) => {
+                o.value = match value {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(VtError::InvalidValue),
+                }
+            }
+            Object::InputList(o) => o.value = to_u8(value)?,
+            Object::OutputList(o) => o.value = to_u8(value)?,
+            Object::OutputMeter(o) => o.value = to_u16(value)?,
+            Object::OutputLinearBarGraph(o) => o.value = to_u16(value)?,
+            Object::OutputArchedBarGraph(o) => o.value = to_u16(value)?,
+            Object::ObjectPointer(o) => o.value = to_u16(value)?.into(),
+            _ => return Err(VtError::InvalidObjectId),
+        }
+        Ok(())
+    }
+
+    /// Change the value of a string variable, input string or output string.
+    ///
+    /// The length of string variables and output strings is fixed, shorter values are padded
+    /// with spaces. Input strings accept values up to [`InputString::max_chars`].
+    pub fn change_string_value(
+        &mut self,
+        id: ObjectId,
+        value: impl Into<String>,
+    ) -> Result<(), VtError> {
+        let value: String = value.into();
+        let max_chars = match self.object_by_id(id) {
+            Some(Object::InputString(o)) => o.max_chars(self).map(usize::from),
+            Some(Object::OutputString(o)) => Some(o.value.chars().count()),
+            Some(Object::StringVariable(o)) => Some(o.value.chars().count()),
+            Some(_) | None => return Err(VtError::InvalidObjectId),
+        };
+        if max_chars.is_some_and(|max_chars| value.chars().count() > max_chars) {
+            return Err(VtError::StringTooLong);
+        }
+
+        match self.object_mut(id) {
+            Some(Object::InputString(o)) => o.value = value,
+            Some(Object::OutputString(o)) => o.value = pad(&value, &o.value),
+            Some(Object::StringVariable(o)) => o.value = pad(&value, &o.value),
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
+    /// Change the active mask of a working set to a data mask or alarm mask.
+    pub fn change_active_mask(
+        &mut self,
+        working_set: ObjectId,
+        mask: ObjectId,
+    ) -> Result<(), VtError> {
+        match self.object_by_id(mask) {
+            Some(Object::DataMask(_)) | Some(Object::AlarmMask(_)) => {}
+            _ => return Err(VtError::InvalidValue),
+        }
+        match self.object_mut(working_set) {
+            Some(Object::WorkingSet(o)) => {
+                o.active_mask = mask;
+                Ok(())
+            }
+            _ => Err(VtError::InvalidObjectId),
+        }
+    }
+
+    /// Change an attribute of an object by its attribute id (AID).
+    pub fn change_attribute(&mut self, id: ObjectId, aid: u8, value: u32) -> Result<(), VtError> {
+        self.object_mut(id)
+            .ok_or(VtError::InvalidObjectId)?
+            .set_attribute(aid, value)
+    }
+
+    /// Get an attribute of an object by its attribute id (AID).
+    ///
+    /// Returns `None` if the object does not exist or has no attribute with that id.
+    pub fn get_attribute(&self, id: ObjectId, aid: u8) -> Option<u32> {
+        self.object_by_id(id)?.attribute(aid)
+    }
+
+    /// Show or hide a container.
+    pub fn hide_show(&mut self, id: ObjectId, show: bool) -> Result<(), VtError> {
+        match self.object_mut(id) {
+            Some(Object::Container(o)) => {
+                o.hidden = !show;
+                Ok(())
+            }
+            _ => Err(VtError::InvalidObjectId),
+        }
+    }
+
+    // Get objects by type
+
+    pub fn working_set_object(&self) -> Option<&WorkingSet> {
+        match &self.objects_by_type(ObjectType::WorkingSet).first() {
+            Some(Object::WorkingSet(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    /// Get the one and only `WorkingSet` of the pool.
+    ///
+    /// Returns `None` if the pool contains zero or multiple `WorkingSet` objects.
+    pub fn default_working_set(&self) -> Option<&WorkingSet> {
+        let mut working_sets = self.objects.iter().filter_map(|o| match o {
+            Object::WorkingSet(o) => Some(o),
+            _ => None,
+        });
+
+        match (working_sets.next(), working_sets.next()) {
+            (Some(working_set), None) => Some(working_set),
+            _ => None,
+        }
+    }
+
+    pub fn working_set_object_id(&self) -> Option<ObjectId> {
+        self.default_working_set().map(|o| o.id)
+    }
+
+    pub fn data_mask_objects(&self) -> Vec<&DataMask> {
+        let r: Vec<&DataMask> = self
+            .objects_by_type(ObjectType::DataMask)
+            .iter()
+            .filter_map(|&o| match o {
+                Object::DataMask(o) => Some(o),
+                _ => None,
+            })
+            .collect();
+        r
+    }
+
+    pub fn picture_graphic_objects(&self) -> Vec<&PictureGraphic> {
+        let r: Vec<&PictureGraphic> = self
+            .objects_by_type(ObjectType::PictureGraphic)
+            .iter()
+            .filter_map(|&o| match o {
+                Object::PictureGraphic(o) => Some(o),
+                _ => None,
+            })
+            .collect();
+        r
+    }
+
+    // Get typed objects by id
+
+    pub fn data_mask_object_by_id(&self, id: ObjectId) -> Option<&DataMask> {
+        match &self.object_by_id(id) {
+            Some(Object::DataMask(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub fn line_attributes_object_by_id(&self, id: ObjectId) -> Option<&LineAttributes> {
+        match &self.object_by_id(id) {
+            Some(Object::LineAttributes(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    /// Get the text an `OutputNumber` displays.
+    ///
+    /// The value is taken from the referenced `NumberVariable` if there is one, otherwise the
+    /// value of the `OutputNumber` itself is used.
+    /// Returns `None` if `id` is not an `OutputNumber` or the variable reference is dangling.
+    pub fn resolve_output_number(&self, id: ObjectId) -> Option<String> {
+        let output_number = match self.object_by_id(id) {
+            Some(Object::OutputNumber(o)) => o,
+            _ => return None,
+        };
+
+        let value = if output_number.variable_reference == ObjectId::NULL {
+            output_number.value
+        } else {
+            match self.object_by_id(output_number.variable_reference) {
+                Some(Object::NumberVariable(o)) => o.value,
+                _ => return None,
+            }
+        };
+
+        Some(output_number.formatted(value))
+    }
+
+    /// Get the state an `InputBoolean` displays.
+    ///
+    /// The state is taken from the referenced `NumberVariable` if there is one, where any
+    /// non-zero value is `true`, otherwise the value of the `InputBoolean` itself is used.
+    /// Returns `None` if `id` is not an `InputBoolean` or the variable reference is dangling.
+    pub fn resolve_input_boolean(&self, id: ObjectId) -> Option<bool> {
+        let input_boolean = match self.object_by_id(id) {
+            Some(Object::InputBoolean(o)) => o,
+            _ => return None,
+        };
+
+        if input_boolean.variable_reference == ObjectId::NULL {
+            return Some(input_boolean.value);
+        }
+        match self.object_by_id(input_boolean.variable_reference) {
+            Some(Object::NumberVariable(o)) => Some(o.value != 0),
+            _ => None,
+        }
+    }
+
+    /// Get the text an `OutputString` displays.
+    ///
+    /// The text is taken from the referenced `StringVariable` if there is one, otherwise the
+    /// value of the `OutputString` itself is used. The text is truncated or padded with spaces
+    /// to the length of the `OutputString` value, an empty string stays empty.
+    /// Returns `None` if `id` is not an `OutputString` or the variable reference is dangling.
+    pub fn resolve_output_string(&self, id: ObjectId) -> Option<String> {
+        let output_string = match self.object_by_id(id) {
+            Some(Object::OutputString(o)) => o,
+            _ => return None,
+        };
+
+        let text = if output_string.variable_reference == ObjectId::NULL {
+            &output_string.value
+        } else {
+            match self.object_by_id(output_string.variable_reference) {
+                Some(Object::StringVariable(o)) => &o.value,
+                _ => return None,
+            }
+        };
+
+        let capacity = output_string.value.chars().count();
+        if text.is_empty() || capacity == 0 {
+            return Some(text.clone());
+        }
+        Some(format!("{:<1$.1$}", text, capacity))
+    }
+
+    /// Follow an `AuxiliaryControlDesignatorType2` to the object it designates.
+    ///
+    /// Pointer types that depend on auxiliary assignments are not known to the pool and
+    /// return `None`, like a missing or non-auxiliary referenced object.
+    pub fn resolve_aux_designator(&self, id: ObjectId) -> Option<&Object> {
+        let designator = match self.object_by_id(id)? {
+            Object::AuxiliaryControlDesignatorType2(o) => o,
+            _ => return None,
+        };
+
+        match designator.kind() {
+            PointerType::AuxiliaryObject => match self.object_by_id(designator.auxiliary_object_id)
+            {
+                obj @ Some(Object::AuxiliaryFunctionType2(_) | Object::AuxiliaryInputType2(_)) => {
+                    obj
+                }
+                _ => None,
+            },
+            PointerType::WorkingSet => self
+                .objects
+                .iter()
+                .find(|o| o.object_type() == ObjectType::WorkingSet),
+            PointerType::AssignedAuxiliaryObjects
+            | PointerType::AssignedWorkingSet
+            | PointerType::Reserved(_) => None,
+        }
+    }
+
+    /// The background colour index of objects created with the builders of the pool, grey
+    /// unless changed.
+    pub fn default_background_index(&self) -> u8 {
+        self.default_background_index
+    }
+
+    /// Set the background colour index for objects created with the builders of the pool.
+    ///
+    /// Objects already created are not changed.
+    pub fn set_default_background_index(&mut self, index: u8) {
+        self.default_background_index = index;
+    }
+
+    /// Get a [`ButtonBuilder`] with the default background colour of the pool.
+    ///
+    /// The built button still has to be added to the pool.
+    pub fn button_builder(&self, id: ObjectId) -> ButtonBuilder {
+        let mut builder = Button::builder(id);
+        builder.background_colour(self.default_background_index);
+        builder
+    }
+
+    pub fn color_by_index(&self, index: u8) -> Colour {
+        self.colour_palette[self.colour_map[index as usize] as usize]
+    }
+
+    /// Get the `ColourMap` and `ColourPalette` objects used to resolve colour indices.
+    ///
+    /// The objects referenced by the `WorkingSetSpecialControls` take precedence, otherwise the
+    /// first of each type in the pool is used. Any other `ColourMap` or `ColourPalette` is
+    /// listed as ignored.
+    pub fn colour_resolution_order(&self) -> ResolutionPlan {
+        let controls = self.objects.iter().find_map(|o| match o {
+            Object::WorkingSetSpecialControls(o) => Some(o),
+            _ => None,
+        });
+        let active = |object_type: ObjectType, referenced: Option<ObjectId>| {
+            referenced
+                .filter(|&id| {
+                    self.object_by_id(id)
+                        .is_some_and(|o| o.object_type() == object_type)
+                })
+                .or_else(|| {
+                    self.objects
+                        .iter()
+                        .find(|o| o.object_type() == object_type)
+                        .map(Object::id)
+                })
+        };
+        let colour_map = active(ObjectType::ColourMap, controls.map(|c| c.id_of_colour_map));
+        let colour_palette = active(
+            ObjectType::ColourPalette,
+            controls.map(|c| c.id_of_colour_palette),
+        );
+
+        let ignored = self
+            .objects
+            .iter()
+            .filter(|o| match o.object_type() {
+                ObjectType::ColourMap => Some(o.id()) != colour_map,
+                ObjectType::ColourPalette => Some(o.id()) != colour_palette,
+                _ => false,
+            })
+            .map(Object::id)
+            .collect();
+
+        ResolutionPlan {
+            colour_map,
+            colour_palette,
+            ignored,
+        }
+    }
+
+    /// Bake the `ColourPalette` and `ColourMap` objects into the palette used by
+    /// [`ObjectPool::color_by_index`], returning the effective palette.
+    ///
+    /// The objects referenced by the `WorkingSetSpecialControls` are used, otherwise the first
+    /// of each in the pool. Colours missing from a partial palette or map keep their default.
+    /// Returns `None` and leaves the pool unchanged if it has neither object.
+    pub fn bake_palette(&mut self) -> Option<[Colour; 256]> {
+        let plan = self.colour_resolution_order();
+        let palette = match plan.colour_palette.and_then(|id| self.object_by_id(id)) {
+            Some(Object::ColourPalette(o)) => Some(o),
+            _ => None,
+        };
+        let map = match plan.colour_map.and_then(|id| self.object_by_id(id)) {
+            Some(Object::ColourMap(o)) => Some(o),
+            _ => None,
+        };
+        if palette.is_none() && map.is_none() {
+            return None;
+        }
+
+        let mut colours = Colour::COLOUR_PALETTE;
+        if let Some(palette) = palette {
+            colours
+                .iter_mut()
+                .zip(&palette.colours)
+                .for_each(|(c, p)| *c = *p);
+        }
+        let mut indices = default_colour_map();
+        if let Some(map) = map {
+            indices
+                .iter_mut()
+                .zip(&map.colour_map)
+                .for_each(|(i, m)| *i = *m);
+        }
+
+        let baked = indices.map(|i| colours[i as usize]);
+        self.colour_palette = baked;
+        self.colour_map = default_colour_map();
+        Some(baked)
+    }
+}
+
+/// The iterator of [`ObjectPool::iter_with_depth`].
+struct DepthFirst<'a> {
+    pool: &'a ObjectPool,
+    /// The objects still to visit with their depth, the next one last
+    stack: Vec<(ObjectId, u16)>,
+    /// The ancestors of the next object, to detect cycles
+    path: Vec<ObjectId>,
+}
+
+impl<'a> Iterator for DepthFirst<'a> {
+    type Item = (&'a Object, u16);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (id, depth) = self.stack.pop()?;
+            self.path.truncate(depth.into());
+            if self.path.contains(&id) {
+                continue;
+            }
+            let Some(obj) = self.pool.object_by_id(id) else {
+                continue;
+            };
+
+            self.path.push(id);
+            let children = obj.child_ids().into_iter().rev();
+            self.stack.extend(children.map(|child| (child, depth + 1)));
+            return Some((obj, depth));
+        }
+    }
+}
+
+/// Key attributes of an object for [`ObjectPool::tree_string`].
+fn describe(obj: &Object) -> String {
+    let size = |width: u16, height: u16| format!(" {}x{}", width, height);
+    match obj {
+        Object::Container(o) => size(o.width, o.height),
+        Object::Key(o) => format!(" key code {}", o.key_code),
+        Object::Button(o) => size(o.width, o.height),
+        Object::InputBoolean(o) => size(o.width, o.width),
+        Object::InputString(o) => format!("{} {:?}", size(o.width, o.height), o.value),
+        Object::InputNumber(o) => format!("{} {}", size(o.width, o.height), o.value),
+        Object::InputList(o) => format!("{} {}", size(o.width, o.height), o.value),
+        Object::OutputString(o) => format!("{} {:?}", size(o.width, o.height), o.value),
+        Object::OutputNumber(o) => format!("{} {}", size(o.width, o.height), o.value),
+        Object::OutputList(o) => format!("{} {}", size(o.width, o.height), o.value),
+        Object::OutputLine(o) => size(o.width, o.height),
+        Object::OutputRectangle(o) => size(o.width, o.height),
+        Object::OutputEllipse(o) => size(o.width, o.height),
+        Object::OutputPolygon(o) => size(o.width, o.height),
+        Object::PictureGraphic(o) => size(o.actual_width, o.actual_height),
+        Object::NumberVariable(o) => format!(" {}", o.value),
+        Object::StringVariable(o) => format!(" {:?}", o.value),
+        _ => String::new(),
+    }
+}
+
+/// Find the first command byte in a macro that is not a VT command allowed in macros.
+///
+/// Commands are 8 bytes long, except for Change String Value which carries its own length.
+fn unknown_macro_command(commands: &[u8]) -> Option<u8> {
+    let mut remaining = commands;
+    while let Some(&command) = remaining.first() {
+        let Some(length) = command_len(remaining) else {
+            return Some(command);
+        };
+        remaining = remaining.get(length..).unwrap_or_default();
+    }
+    None
+}
+
+/// Check that `value` can be written as is, see [`CharacterSet`].
+fn validate_string(id: ObjectId, value: &str) -> Result<(), ValidationError> {
+    match value
+        .chars()
+        .find(|&c| CharacterSet::Latin1.encode_char(c).is_none())
+    {
+        Some(character) => Err(ValidationError::UnencodableString { id, character }),
+        None => Ok(()),
+    }
+}
+
+/// The default colour map, every index maps to itself.
+fn default_colour_map() -> [u8; 256] {
+    let mut colour_map = [0xFFu8; 256];
+    for (i, index) in colour_map.iter_mut().enumerate() {
+        *index = i as u8;
+    }
+    colour_map
+}
+
+/// Pad `value` with spaces to the length of `current`.
+fn pad(value: &str, current: &str) -> String {
+    format!("{:<1$}", value, current.chars().count())
+}
+
+/// CRC-16/CCITT-FALSE, see [`ObjectPool::checksum`].
+fn crc16_ccitt(data: &[u8]) -> u16 {
+    data.iter().fold(0xFFFF, |mut crc, &byte| {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+/// 64-bit FNV-1a hash, used because its output is fixed, unlike the std hashers.
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xCBF2_9CE4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+/// Pools are equal when they contain the same objects, in any order, and the same colours.
+///
+/// `Eq` is not implemented, since some objects hold floating point attributes.
+impl PartialEq for ObjectPool {
+    fn eq(&self, other: &Self) -> bool {
+        self.objects.len() == other.objects.len()
+            && self.sorted_objects() == other.sorted_objects()
+            && self.colour_map == other.colour_map
+            && self.colour_palette == other.colour_palette
+    }
+}
+
+impl<'a> IntoIterator for &'a ObjectPool {
+    type Item = &'a Object;
+    type IntoIter = core::slice::Iter<'a, Object>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.objects.iter()
+    }
+}
+
+impl FromIterator<Object> for ObjectPool {
+    fn from_iter<T: IntoIterator<Item = Object>>(iter: T) -> Self {
+        let mut pool = ObjectPool::new();
+        for obj in iter {
+            pool.add(obj);
+        }
+        pool
+    }
+}
+
+impl Default for ObjectPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn working_set(id: u16) -> Object {
+        Object::WorkingSet(WorkingSet {
+            id: id.into(),
+            background_colour: 0,
+            selectable: true,
+            active_mask: ObjectId::NULL,
+            object_refs: Vec::new(),
+            macro_refs: Vec::new(),
+            language_codes: Vec::new(),
+        })
+    }
+
+    fn data_mask(id: u16, children: &[u16]) -> Object {
+        Object::DataMask(DataMask {
+            id: id.into(),
+            background_colour: 0,
+            soft_key_mask: ObjectId::NULL,
+            object_refs: children
+                .iter()
+                .map(|&c| ObjectRef {
+                    id: c.into(),
+                    offset: Point::default(),
+                })
+                .collect(),
+            macro_refs: Vec::new(),
+        })
+    }
+
+    fn output_number(id: u16, font_attributes: u16, variable_reference: u16) -> Object {
+        Object::OutputNumber(OutputNumber {
+            id: id.into(),
+            width: 50,
+            height: 20,
+            background_colour: 1,
+            font_attributes: font_attributes.into(),
+            options: 0,
+            variable_reference: variable_reference.into(),
+            value: 0,
+            offset: 0,
+            scale: 1.0,
+            nr_of_decimals: 0,
+            format: false,
+            justification: 0,
+            macro_refs: Vec::new(),
+        })
+    }
+
+    fn number_variable(id: u16, value: u32) -> Object {
+        Object::NumberVariable(NumberVariable {
+            id: id.into(),
+            value,
+        })
+    }
+
+    fn output_string(id: u16, variable_reference: u16, value: &str) -> Object {
+        Object::OutputString(OutputString {
+            id: id.into(),
+            width: 50,
+            height: 20,
+            background_colour: 1,
+            font_attributes: ObjectId::NULL,
+            options: 0,
+            variable_reference: variable_reference.into(),
+            justification: 0,
+            value: value.into(),
+            macro_refs: Vec::new(),
+        })
+    }
+
+    fn string_variable(id: u16, value: &str) -> Object {
+        Object::StringVariable(StringVariable {
+            id: id.into(),
+            value: value.into(),
+        })
+    }
+
+    fn font_attributes(id: u16) -> Object {
+        Object::FontAttributes(FontAttributes {
+            id: id.into(),
+            font_colour: 0,
+            font_size: 1,
+            font_type: 0,
+            font_style: 0,
+            macro_refs: Vec::new(),
+        })
+    }
+
+    fn output_list(id: u16, variable_reference: u16, list_items: &[u16]) -> Object {
+        Object::OutputList(OutputList {
+            id: id.into(),
+            width: 50,
+            height: 20,
+            variable_reference: variable_reference.into(),
+            value: 0,
+            list_items: list_items.iter().map(|&i| i.into()).collect(),
+            macro_refs: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn test_default_working_set() {
+        let mut pool = ObjectPool::new();
+        assert!(pool.default_working_set().is_none());
+        assert_eq!(None, pool.working_set_object_id());
+
+        pool.add(working_set(0));
+        assert_eq!(Some(ObjectId::from(0)), pool.working_set_object_id());
+
+        pool.add(working_set(1));
+        assert!(pool.default_working_set().is_none());
+        assert_eq!(None, pool.working_set_object_id());
+    }
+
+    #[test]
+    fn test_validate_working_set_count() {
+        let mut pool = ObjectPool::new();
+        assert_eq!(
+            Err(ValidationError::MissingWorkingSet),
+            pool.validate_working_set_count()
+        );
+
+        pool.add(working_set(0));
+        assert_eq!(Ok(()), pool.validate_working_set_count());
+
+        pool.add(working_set(1));
+        assert_eq!(
+            Err(ValidationError::MultipleWorkingSets(vec![
+                0.into(),
+                1.into()
+            ])),
+            pool.validate_working_set_count()
+        );
+    }
+
+    #[test]
+    fn test_objects_referencing() {
+        let mut pool = ObjectPool::new();
+        pool.add(data_mask(1000, &[12000, 37000]));
+        pool.add(output_number(12000, 23000, 21000));
+        pool.add(output_number(12001, 23000, 0xFFFF));
+        pool.add(output_list(37000, 21000, &[12001]));
+        pool.add(number_variable(21000, 0));
+        pool.add(font_attributes(23000));
+
+        assert_eq!(
+            vec![ObjectId::from(12000), ObjectId::from(12001)],
+            pool.objects_referencing(23000.into())
+        );
+        assert_eq!(
+            vec![ObjectId::from(12000), ObjectId::from(37000)],
+            pool.objects_referencing(21000.into())
+        );
+        assert_eq!(
+            vec![ObjectId::from(37000)],
+            pool.objects_referencing(12001.into())
+        );
+        assert!(pool.objects_referencing(1000.into()).is_empty());
+        assert!(pool.objects_referencing(ObjectId::NULL).is_empty());
+    }
+
+    #[test]
+    fn test_try_remove_referenced_object() {
+        let mut pool = ObjectPool::new();
+        pool.add(output_number(12000, 23000, 0xFFFF));
+        pool.add(output_number(12001, 23000, 0xFFFF));
+        pool.add(font_attributes(23000));
+
+        assert_eq!(
+            Err(vec![ObjectId::from(12000), ObjectId::from(12001)]),
+            pool.try_remove(23000.into()).map(|o| o.id())
+        );
+        assert!(pool.object_by_id(23000.into()).is_some());
+
+        assert_eq!(
+            Ok(ObjectId::from(12000)),
+            pool.try_remove(12000.into()).map(|o| o.id())
+        );
+        assert_eq!(
+            Err(Vec::new()),
+            pool.try_remove(12000.into()).map(|o| o.id())
+        );
+    }
+
+    #[test]
+    fn test_force_remove() {
+        let mut pool = ObjectPool::new();
+        pool.add(output_number(12000, 23000, 0xFFFF));
+        pool.add(font_attributes(23000));
+
+        let removed = pool.force_remove(23000.into());
+        assert_eq!(Some(ObjectId::from(23000)), removed.map(|o| o.id()));
+        assert!(pool.objects_referencing(23000.into()).is_empty());
+        match pool.object_by_id(12000.into()) {
+            Some(Object::OutputNumber(o)) => assert_eq!(ObjectId::NULL, o.font_attributes),
+            _ => panic!("OutputNumber missing"),
+        }
+        assert!(pool.force_remove(23000.into()).is_none());
+    }
+
+    #[test]
+    fn test_resolve_output_number() {
+        let mut pool = ObjectPool::new();
+        let mut inline = output_number(12000, 0xFFFF, 0xFFFF);
+        if let Object::OutputNumber(o) = &mut inline {
+            o.value = 1234;
+            o.offset = -34;
+            o.scale = 0.1;
+            o.nr_of_decimals = 1;
+        }
+        pool.add(inline);
+        pool.add(output_number(12001, 0xFFFF, 21000));
+        pool.add(output_number(12002, 0xFFFF, 21001));
+        pool.add(number_variable(21000, 42));
+
+        assert_eq!(
+            Some(String::from("120.0")),
+            pool.resolve_output_number(12000.into())
+        );
+        assert_eq!(
+            Some(String::from("42")),
+            pool.resolve_output_number(12001.into())
+        );
+        assert_eq!(None, pool.resolve_output_number(12002.into()));
+        assert_eq!(None, pool.resolve_output_number(21000.into()));
+    }
+
+    #[test]
+    fn test_resolve_output_string() {
+        let mut pool = ObjectPool::new();
+        pool.add(output_string(11000, 0xFFFF, "Hello"));
+        pool.add(output_string(11001, 22000, "     "));
+        pool.add(output_string(11002, 22001, "     "));
+        pool.add(output_string(11003, 22002, "     "));
+        pool.add(output_string(11004, 22003, "     "));
+        pool.add(string_variable(22000, "Hi"));
+        pool.add(string_variable(22001, "Hello world"));
+        pool.add(string_variable(22002, ""));
+
+        assert_eq!(
+            Some(String::from("Hello")),
+            pool.resolve_output_string(11000.into())
+        );
+        assert_eq!(
+            Some(String::from("Hi   ")),
+            pool.resolve_output_string(11001.into())
+        );
+        assert_eq!(
+            Some(String::from("Hello")),
+            pool.resolve_output_string(11002.into())
+        );
+        assert_eq!(
+            Some(String::new()),
+            pool.resolve_output_string(11003.into())
+        );
+        assert_eq!(None, pool.resolve_output_string(11004.into()));
+    }
+
+    #[test]
+    fn test_objects_round_trip() {
+        let mut pool = ObjectPool::new();
+        pool.add(data_mask(1000, &[12000]));
+        pool.add(output_number(12000, 23000, 21000));
+        pool.add(output_string(11000, 0xFFFF, "Hello"));
+        pool.add(number_variable(21000, 42));
+        pool.add(font_attributes(23000));
+
+        let parsed = ObjectPool::from_iop(pool.as_iop());
+
+        for id in [1000, 12000, 11000, 21000, 23000] {
+            assert_eq!(pool.object_by_id(id.into()), parsed.object_by_id(id.into()));
+        }
+    }
+
+    /// Metrics of a font half as wide as the monospace font.
+    struct Half;
+
+    impl FontMetrics for Half {
+        fn char_width(&self, font: &FontAttributes, _c: char) -> Option<u16> {
+            Some(font.font_dimensions()?.x / 2)
+        }
+    }
+
+    #[test]
+    fn test_input_string_max_chars() {
+        let mut pool = ObjectPool::new();
+        pool.add(font_attributes(23000)); // 8x8 font
+
+        let mut input_string = InputString {
+            id: 8000.into(),
+            width: 40,
+            height: 20,
+            background_colour: 1,
+            font_attributes: 23000.into(),
+            input_attributes: ObjectId::NULL,
+            options: 0,
+            variable_reference: ObjectId::NULL,
+            justification: 0,
+            value: String::new(),
+            enabled: true,
+            macro_refs: Vec::new(),
+        };
+
+        assert_eq!(Some(5), input_string.max_chars(&pool));
+        assert_eq!(Ok(()), input_string.set_value(&pool, "Hello"));
+        assert_eq!(
+            Err(VtError::StringTooLong),
+            input_string.set_value(&pool, "Hello world")
+        );
+        assert_eq!("Hello", input_string.value);
+
+        assert_eq!(
+            Some(5),
+            input_string.fitting_chars(&pool, &Monospace, "Hello world")
+        );
+        let mut registry = FontRegistry::new();
+        registry.register(0, Half);
+        assert_eq!(
+            Some(10),
+            input_string.fitting_chars(&pool, &registry, "Hello world")
+        );
+        assert_eq!(Some(3), input_string.fitting_chars(&pool, &registry, "abc"));
+
+        input_string.font_attributes = ObjectId::NULL;
+        assert_eq!(None, input_string.max_chars(&pool));
+        assert_eq!(None, input_string.fitting_chars(&pool, &Monospace, "a"));
+    }
+
+    #[test]
+    fn test_statistics() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(data_mask(1000, &[12000, 12001]));
+        pool.add(output_number(12000, 23000, 0xFFFF));
+        pool.add(output_number(12001, 23000, 0xFFFF));
+        pool.add(font_attributes(23000));
+
+        let stats = pool.statistics();
+        assert_eq!(
+            vec![
+                (ObjectType::WorkingSet, 1),
+                (ObjectType::DataMask, 1),
+                (ObjectType::OutputNumber, 2),
+                (ObjectType::FontAttributes, 1),
+            ],
+            stats.object_counts
+        );
+        assert_eq!(2, stats.count(ObjectType::OutputNumber));
+        assert_eq!(0, stats.count(ObjectType::Button));
+        assert_eq!(5, stats.total_objects);
+        assert_eq!(pool.as_iop().len(), stats.total_bytes);
+        assert_eq!(2, stats.distinct_colours);
+    }
+
+    #[test]
+    fn test_flatten_refs() {
+        let container = |id: u16, children: &[(u16, i16, i16)]| {
+            Object::Container(Container {
+                id: id.into(),
+                width: 100,
+                height: 100,
+                hidden: false,
+                object_refs: children
+                    .iter()
+                    .map(|&(c, x, y)| ObjectRef {
+                        id: c.into(),
+                        offset: Point { x, y },
+                    })
+                    .collect(),
+                macro_refs: Vec::new(),
+            })
+        };
+
+        let mut pool = ObjectPool::new();
+        pool.add(container(
+            3000,
+            &[(3001, 10, 20), (0xFFFF, 1, 1), (3003, 5, 5)],
+        ));
+        pool.add(container(3001, &[(3002, 1, 2), (3000, 0, 0)]));
+        pool.add(container(3002, &[(3001, 3, 3)]));
+
+        assert_eq!(
+            vec![
+                (ObjectId::from(3001), Point { x: 10, y: 20 }),
+                (ObjectId::from(3002), Point { x: 11, y: 22 }),
+            ],
+            pool.flatten_refs(3000.into())
+        );
+        assert!(pool.flatten_refs(4000.into()).is_empty());
+
+        let offset = |root: u16, target: u16| pool.absolute_offset(root.into(), target.into());
+        assert_eq!(Some(Point { x: 11, y: 22 }), offset(3000, 3002));
+        assert_eq!(Some(Point { x: 0, y: 0 }), offset(3000, 3000));
+        assert_eq!(Some(Point { x: 3, y: 3 }), offset(3002, 3001));
+        assert_eq!(None, offset(3000, 3003));
+        assert_eq!(None, offset(3001, 4000));
+        assert_eq!(None, offset(4000, 4000));
+    }
+
+    #[test]
+    fn test_objects_access() {
+        let mut pool = ObjectPool::with_capacity(3);
+        pool.add(working_set(0));
+        pool.add(number_variable(21000, 1));
+        pool.add(number_variable(21001, 2));
+        assert_eq!(3, pool.objects().len());
+        assert_eq!(ObjectId::from(21000), pool.objects()[1].id());
+
+        assert!(pool.try_remove(21000.into()).is_ok());
+        assert_eq!(
+            Some(&number_variable(21001, 2)),
+            pool.object_by_id(21001.into())
+        );
+        assert!(pool.object_by_id(21000.into()).is_none());
+
+        let objects = pool.into_objects();
+        assert_eq!(vec![working_set(0), number_variable(21001, 2)], objects);
+    }
+
+    #[test]
+    fn test_tree_string() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(data_mask(1000, &[12000, 1001, 37000]));
+        pool.add(data_mask(1001, &[1000]));
+        pool.add(output_number(12000, 23000, 21000));
+        pool.add(number_variable(21000, 42));
+        pool.add(font_attributes(23000));
+
+        assert_eq!(
+            "DataMask 1000\n\
+             \x20 OutputNumber 12000 50x20 0\n\
+             \x20   FontAttributes 23000\n\
+             \x20   NumberVariable 21000 42\n\
+             \x20 DataMask 1001\n\
+             \x20   DataMask 1000 (cycle)\n\
+             \x20 37000 (missing)\n",
+            pool.tree_string(1000.into())
+        );
+
+        let walked: Vec<u16> = pool
+            .walk_from(1000.into())
+            .iter()
+            .map(|o| o.id().into())
+            .collect();
+        assert_eq!(vec![1000, 12000, 23000, 21000, 1001], walked);
+    }
+
+    #[test]
+    fn test_iter_with_depth() {
+        let mut pool = ObjectPool::new();
+        pool.add(data_mask(1000, &[12000, 1001, 37000, 12000]));
+        pool.add(data_mask(1001, &[1000]));
+        pool.add(output_number(12000, 23000, 21000));
+        pool.add(number_variable(21000, 42));
+        pool.add(font_attributes(23000));
+
+        let mut lines = Vec::new();
+        for (obj, depth) in pool.iter_with_depth(1000.into()) {
+            lines.push((u16::from(obj.id()), depth));
+        }
+        assert_eq!(
+            vec![
+                (1000, 0),
+                (12000, 1),
+                (23000, 2),
+                (21000, 2),
+                (1001, 1),
+                (12000, 1),
+                (23000, 2),
+                (21000, 2),
+            ],
+            lines
+        );
+        assert_eq!(
+            11,
+            pool.iter_with_depth(1000.into())
+                .map(|(_, d)| d)
+                .sum::<u16>()
+        );
+        assert_eq!(0, pool.iter_with_depth(37000.into()).count());
+    }
+
+    #[test]
+    fn test_pool_equality_and_content_hash() {
+        let mut pool_a = ObjectPool::new();
+        pool_a.add(working_set(0));
+        pool_a.add(number_variable(21000, 1));
+
+        let mut pool_b = ObjectPool::new();
+        pool_b.add(number_variable(21000, 1));
+        pool_b.add(working_set(0));
+
+        assert_eq!(pool_a, pool_b);
+        assert_eq!(pool_a.content_hash(), pool_b.content_hash());
+        assert_eq!(0xCBF2_9CE4_8422_2325, fnv1a(&[]));
+
+        pool_b.add(number_variable(21001, 2));
+        assert_ne!(pool_a, pool_b);
+        assert_ne!(pool_a.content_hash(), pool_b.content_hash());
+
+        let mut pool_c = ObjectPool::new();
+        pool_c.add(working_set(0));
+        pool_c.add(number_variable(21000, 2));
+        assert_ne!(pool_a, pool_c);
+        assert_ne!(pool_a.content_hash(), pool_c.content_hash());
+    }
+
+    #[test]
+    fn test_from_iop_with() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(number_variable(21000, 1));
+        let mut data = pool.as_iop();
+
+        let strict = ParseOptions { strict: true };
+        let (parsed, report) = ObjectPool::from_iop_with(data.clone(), strict).unwrap();
+        assert_eq!(pool, parsed);
+        assert!(report.is_empty());
+
+        // Trailing padding
+        data.extend([0, 0, 0]);
+        assert_eq!(
+            Err(ParseError::DataEmpty),
+            ObjectPool::from_iop_with(data.clone(), strict).map(|_| ())
+        );
+        let (parsed, report) =
+            ObjectPool::from_iop_with(data.clone(), ParseOptions::default()).unwrap();
+        assert_eq!(pool, parsed);
+        assert_eq!(
+            vec![ParseWarning {
+                id: ObjectId::from(0),
+                message: "skipped 3 bytes of unparsable data".into()
+            }],
+            report.warnings
+        );
+
+        // Truncated object
+        data.truncate(data.len() - 5);
+        assert!(ObjectPool::from_iop_with(data.clone(), strict).is_err());
+        let (parsed, report) = ObjectPool::from_iop_with(data, ParseOptions::default()).unwrap();
+        assert_eq!(1, parsed.objects().len());
+        assert_eq!(ObjectId::from(21000), report.warnings[0].id);
+    }
+
+    #[test]
+    fn test_parse_report() {
+        let mut pool = ObjectPool::new();
+        pool.add(data_mask(1000, &[12000, 37000]));
+        pool.add(output_number(12000, 23000, 0xFFFF));
+        pool.add(font_attributes(23000));
+        pool.add(Object::Macro(Macro {
+            id: 250.into(),
+            commands: vec![
+                0xA0, 0xE0, 0x2E, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, // Hide object 12000
+                0xB3, 0xE0, 0x2E, 0x02, 0x00, b'O', b'K', // Change string value
+                0x12, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            ],
+        }));
+        pool.add(Object::Macro(Macro {
+            id: 251.into(),
+            commands: vec![
+                0xA0, 0xE0, 0x2E, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, // Hide object 12000
+                0xA0, 0xE0, 0x2E, 0x01,
+            ],
+        }));
+        if let Some(Object::DataMask(o)) = pool.objects.get_mut(0) {
+            o.background_colour = 240;
+        }
+
+        let (_, report) =
+            ObjectPool::from_iop_with(pool.as_iop(), ParseOptions::default()).unwrap();
+        assert_eq!(
+            vec![
+                ParseWarning {
+                    id: 1000.into(),
+                    message: "uses reserved colour index 240".into()
+                },
+                ParseWarning {
+                    id: 1000.into(),
+                    message: "references undefined object 37000".into()
+                },
+                ParseWarning {
+                    id: 250.into(),
+                    message: "contains unknown command 0x12".into()
+                },
+                ParseWarning {
+                    id: 251.into(),
+                    message: "contains a truncated command at byte 8".into()
+                },
+            ],
+            report.warnings
+        );
+    }
+
+    #[test]
+    fn test_to_chunks() {
+        let mut pool = ObjectPool::new();
+        pool.add(number_variable(21000, 1)); // 7 bytes
+        pool.add(number_variable(21001, 2));
+        pool.add(string_variable(22000, "a long string value")); // 24 bytes
+        pool.add(number_variable(21002, 3));
+
+        let chunks = pool.to_chunks(16);
+        assert_eq!(
+            vec![(14, false), (24, true), (7, false)],
+            chunks
+                .iter()
+                .map(|c| (c.data.len(), c.oversized))
+                .collect::<Vec<_>>()
+        );
+        let joined: Vec<u8> = chunks.into_iter().flat_map(|c| c.data).collect();
+        assert_eq!(pool.as_iop(), joined);
+    }
+
+    #[test]
+    fn test_checksum() {
+        // The standard check value of CRC-16/CCITT-FALSE
+        assert_eq!(0x29B1, crc16_ccitt(b"123456789"));
+
+        let mut pool = ObjectPool::new();
+        assert_eq!(0xFFFF, pool.checksum());
+
+        pool.add(number_variable(21000, 1));
+        assert_eq!(0xC80E, pool.checksum());
+    }
+
+    #[test]
+    fn test_out_of_bounds_objects() {
+        let mut pool = ObjectPool::new();
+        pool.add(Object::DataMask(DataMask {
+            id: 1000.into(),
+            background_colour: 0,
+            soft_key_mask: ObjectId::NULL,
+            object_refs: [
+                (12000, 0, 0),
+                (12001, 160, 10),
+                (12002, -10, 0),
+                (12003, 300, 0),
+            ]
+            .iter()
+            .map(|&(id, x, y)| ObjectRef {
+                id: id.into(),
+                offset: Point { x, y },
+            })
+            .collect(),
+            macro_refs: Vec::new(),
+        }));
+        for id in 12000..=12003 {
+            pool.add(output_number(id, 23000, 0xFFFF)); // 50x20
+        }
+        pool.add(font_attributes(23000));
+
+        assert_eq!(
+            vec![
+                ObjectId::from(12001),
+                ObjectId::from(12002),
+                ObjectId::from(12003)
+            ],
+            pool.out_of_bounds_objects(1000.into(), 200, 200)
+        );
+        assert!(pool
+            .out_of_bounds_objects(1000.into(), 400, 200)
+            .contains(&ObjectId::from(12002)));
+    }
+
+    #[test]
+    fn test_apply_commands() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(data_mask(1000, &[3000]));
+        pool.add(data_mask(1001, &[]));
+        pool.add(Object::Container(Container {
+            id: 3000.into(),
+            width: 100,
+            height: 100,
+            hidden: false,
+            object_refs: Vec::new(),
+            macro_refs: Vec::new(),
+        }));
+        pool.add(number_variable(21000, 0));
+        pool.add(string_variable(22000, "Hello"));
+
+        let results = pool.apply_commands(&[
+            VtCommand::ChangeNumericValue {
+                id: 21000.into(),
+                value: 42,
+            },
+            VtCommand::ChangeStringValue {
+                id: 22000.into(),
+                value: "Hi".into(),
+            },
+            VtCommand::ChangeStringValue {
+                id: 22000.into(),
+                value: "Hello world".into(),
+            },
+            VtCommand::ChangeActiveMask {
+                working_set: 0.into(),
+                mask: 1001.into(),
+            },
+            VtCommand::ChangeActiveMask {
+                working_set: 0.into(),
+                mask: 21000.into(),
+            },
+            VtCommand::ChangeAttribute {
+                id: 1000.into(),
+                aid: 1,
+                value: 12,
+            },
+            VtCommand::ChangeAttribute {
+                id: 1000.into(),
+                aid: 9,
+                value: 12,
+            },
+            VtCommand::HideShow {
+                id: 3000.into(),
+                show: false,
+            },
+            VtCommand::HideShow {
+                id: 37000.into(),
+                show: true,
+            },
+        ]);
+
+        assert_eq!(
+            vec![
+                Ok(()),
+                Ok(()),
+                Err(VtError::StringTooLong),
+                Ok(()),
+                Err(VtError::InvalidValue),
+                Ok(()),
+                Err(VtError::InvalidAttributeId),
+                Ok(()),
+                Err(VtError::InvalidObjectId),
+            ],
+            results
+        );
+        assert_eq!(
+            Some(&number_variable(21000, 42)),
+            pool.object_by_id(21000.into())
+        );
+        assert_eq!(
+            Some(&string_variable(22000, "Hi   ")),
+            pool.object_by_id(22000.into())
+        );
+        assert_eq!(
+            Some(ObjectId::from(1001)),
+            pool.working_set_object().map(|o| o.active_mask)
+        );
+        match pool.object_by_id(1000.into()) {
+            Some(Object::DataMask(o)) => assert_eq!(12, o.background_colour),
+            _ => panic!("DataMask missing"),
+        }
+        assert_eq!(Some(12), pool.get_attribute(1000.into(), 1));
+        assert_eq!(None, pool.get_attribute(1000.into(), 9));
+        assert_eq!(None, pool.get_attribute(37000.into(), 1));
+        match pool.object_by_id(3000.into()) {
+            Some(Object::Container(o)) => assert!(o.hidden),
+            _ => panic!("Container missing"),
+        }
+    }
+
+    #[test]
+    fn test_bake_palette() {
+        let mut pool = ObjectPool::new();
+        assert_eq!(None, pool.bake_palette());
+
+        let colour = |r, g, b| Colour { r, g, b, a: 0xFF };
+        pool.add(Object::ColourPalette(ColourPalette {
+            id: 39000.into(),
+            options: 0,
+            colours: vec![colour(1, 2, 3), colour(4, 5, 6)],
+        }));
+        pool.add(Object::ColourMap(ColourMap {
+            id: 38000.into(),
+            colour_map: vec![1, 0],
+        }));
+
+        let palette = pool.bake_palette().unwrap();
+        assert_eq!(colour(4, 5, 6), palette[0]);
+        assert_eq!(colour(1, 2, 3), palette[1]);
+        assert_eq!(Colour::RED, palette[12]);
+        assert_eq!(colour(4, 5, 6), pool.color_by_index(0));
+        assert_eq!(Colour::RED, pool.color_by_index(12));
+    }
+
+    #[test]
+    fn test_colour_resolution_order() {
+        let mut pool = ObjectPool::new();
+        assert_eq!(
+            ResolutionPlan {
+                colour_map: None,
+                colour_palette: None,
+                ignored: Vec::new(),
+            },
+            pool.colour_resolution_order()
+        );
+
+        let palette = |id: u16, colour: Colour| {
+            Object::ColourPalette(ColourPalette {
+                id: id.into(),
+                options: 0,
+                colours: vec![colour],
+            })
+        };
+        pool.add(palette(39000, Colour::RED));
+        pool.add(palette(39001, Colour::BLUE));
+        pool.add(Object::ColourMap(ColourMap {
+            id: 38000.into(),
+            colour_map: vec![0],
+        }));
+        assert_eq!(
+            ResolutionPlan {
+                colour_map: Some(38000.into()),
+                colour_palette: Some(39000.into()),
+                ignored: vec![39001.into()],
+            },
+            pool.colour_resolution_order()
+        );
+
+        // The working set special controls take precedence
+        pool.add(Object::WorkingSetSpecialControls(
+            WorkingSetSpecialControls {
+                id: 40000.into(),
+                id_of_colour_map: ObjectId::NULL,
+                id_of_colour_palette: 39001.into(),
+                language_pairs: Vec::new(),
+            },
+        ));
+        let plan = pool.colour_resolution_order();
+        assert_eq!(Some(ObjectId::from(38000)), plan.colour_map);
+        assert_eq!(Some(ObjectId::from(39001)), plan.colour_palette);
+        assert_eq!(vec![ObjectId::from(39000)], plan.ignored);
+        assert_eq!(Some(Colour::BLUE), pool.bake_palette().map(|p| p[0]));
+    }
+
+    #[test]
+    fn test_from_iterator() {
+        let pool: ObjectPool = [
+            working_set(0),
+            data_mask(1000, &[]),
+            number_variable(21000, 1),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(3, pool.objects().len());
+        assert_eq!(
+            Some(&number_variable(21000, 1)),
+            pool.object_by_id(21000.into())
+        );
+        assert!(pool.duplicate_ids().is_empty());
+
+        let pool: ObjectPool = [
+            number_variable(21000, 1),
+            data_mask(1000, &[]),
+            number_variable(21000, 2),
+            number_variable(21000, 3),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(vec![ObjectId::from(21000)], pool.duplicate_ids());
+        assert_eq!(
+            Some(&number_variable(21000, 1)),
+            pool.object_by_id(21000.into())
+        );
+    }
+
+    #[test]
+    fn test_resolve_aux_designator() {
+        let designator = |id: u16, pointer_type: PointerType, aux: u16| {
+            Object::AuxiliaryControlDesignatorType2(AuxiliaryControlDesignatorType2 {
+                id: id.into(),
+                pointer_type: pointer_type.into(),
+                auxiliary_object_id: aux.into(),
+            })
+        };
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(Object::AuxiliaryFunctionType2(AuxiliaryFunctionType2 {
+            id: 31000.into(),
+            background_colour: 0,
+            function_attributes: 0,
+            object_refs: Vec::new(),
+        }));
+        pool.add(designator(33000, PointerType::AuxiliaryObject, 31000));
+        pool.add(designator(33001, PointerType::WorkingSet, 0xFFFF));
+        pool.add(designator(
+            33002,
+            PointerType::AssignedAuxiliaryObjects,
+            31000,
+        ));
+        pool.add(designator(33003, PointerType::AuxiliaryObject, 0));
+
+        let resolved = |id: u16| pool.resolve_aux_designator(id.into()).map(|o| o.id());
+        assert_eq!(Some(ObjectId::from(31000)), resolved(33000));
+        assert_eq!(Some(ObjectId::from(0)), resolved(33001));
+        assert_eq!(None, resolved(33002));
+        assert_eq!(None, resolved(33003));
+        assert_eq!(None, resolved(31000));
+    }
+
+    #[test]
+    fn test_replace_object() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(number_variable(21000, 1));
+        let size = pool.size();
+
+        assert_eq!(
+            Some(number_variable(21000, 1)),
+            pool.replace_object(number_variable(21000, 2))
+        );
+        assert_eq!(
+            Some(&number_variable(21000, 2)),
+            pool.object_by_id(21000.into())
+        );
+        assert_eq!(ObjectId::from(21000), pool.objects()[1].id());
+        assert_eq!(size, pool.size());
+
+        assert_eq!(None, pool.replace_object(string_variable(22000, "Hello")));
+        assert_eq!(3, pool.objects().len());
+        assert!(pool.object_by_id(22000.into()).is_some());
+    }
+
+    #[test]
+    fn test_used_colour_indices() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(output_number(12000, 23000, 0xFFFF));
+        pool.add(Object::PictureGraphic(PictureGraphic {
+            id: 20000.into(),
+            width: 2,
+            actual_width: 2,
+            actual_height: 2,
+            format: PictureGraphicFormat::EightBit.into(),
+            options: 0,
+            transparency_colour: 3,
+            data: vec![12, 12, 200, 12],
+            macro_refs: Vec::new(),
+        }));
+
+        assert_eq!(
+            BTreeSet::from([0, 1, 3, 12, 200]),
+            pool.used_colour_indices()
+        );
+    }
+
+    #[test]
+    fn test_objects_mut() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(output_number(12000, 23000, 21000));
+        pool.add(output_string(11000, 22000, "Hello"));
+
+        for obj in pool.objects_mut() {
+            match obj {
+                Object::OutputNumber(o) => o.width *= 2,
+                Object::OutputString(o) => o.width *= 2,
+                _ => {}
+            }
+        }
+
+        assert_eq!(
+            Some(Point { x: 100, y: 20 }),
+            pool.object_by_id(12000.into()).and_then(Object::dimensions)
+        );
+        assert!(matches!(
+            pool.object_by_id(11000.into()),
+            Some(Object::OutputString(o)) if o.width == 100
+        ));
+    }
+
+    #[test]
+    fn test_validate_active_mask() {
+        let mut pool = ObjectPool::new();
+        let mut ws = working_set(0);
+        if let Object::WorkingSet(o) = &mut ws {
+            o.active_mask = 1000.into();
+        }
+        pool.add(ws);
+        assert_eq!(
+            Err(ValidationError::InvalidActiveMask {
+                id: 0.into(),
+                active_mask: 1000.into(),
+                found: None,
+            }),
+            pool.validate_active_mask()
+        );
+
+        pool.add(Object::Button(
+            Button::builder(1000.into()).build().unwrap(),
+        ));
+        assert_eq!(
+            Err(ValidationError::InvalidActiveMask {
+                id: 0.into(),
+                active_mask: 1000.into(),
+                found: Some(ObjectType::Button),
+            }),
+            pool.validate_active_mask()
+        );
+
+        pool.replace_object(data_mask(1000, &[]));
+        assert_eq!(Ok(()), pool.validate_active_mask());
+    }
+
+    #[test]
+    fn test_validate_for_version() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(Object::ScalesGraphic(ScalesGraphic {
+            id: 48000.into(),
+            width: 100,
+            height: 20,
+            scale_type: 0,
+            options: 0,
+            value: 0,
+            macro_refs: Vec::new(),
+        }));
+
+        assert_eq!(Ok(()), pool.validate_for_version(VtVersion::V6));
+        assert_eq!(
+            Err(ValidationError::UnsupportedObject {
+                id: 48000.into(),
+                object_type: ObjectType::ScalesGraphic,
+                version: VtVersion::V5,
+            }),
+            pool.validate_for_version(VtVersion::V5)
+        );
+    }
+
+    #[test]
+    fn test_into_iterator() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(data_mask(1000, &[]));
+        pool.add(number_variable(21000, 1));
+
+        let mut count = 0;
+        for obj in &pool {
+            assert!(pool.object_by_id(obj.id()).is_some());
+            count += 1;
+        }
+        assert_eq!(3, count);
+        assert_eq!(
+            1,
+            (&pool)
+                .into_iter()
+                .filter(|o| o.object_type() == ObjectType::DataMask)
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_find_by_predicate() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(Object::Button(
+            Button::builder(5000.into())
+                .background_colour(7)
+                .build()
+                .unwrap(),
+        ));
+        pool.add(Object::Button(
+            Button::builder(5001.into()).build().unwrap(),
+        ));
+        pool.add(Object::DataMask(DataMask {
+            id: 1000.into(),
+            background_colour: 7,
+            soft_key_mask: ObjectId::NULL,
+            object_refs: Vec::new(),
+            macro_refs: Vec::new(),
+        }));
+
+        let found: Vec<ObjectId> = pool
+            .find_by_predicate(|o| match o {
+                Object::Button(b) => b.background_colour == 7,
+                Object::DataMask(d) => d.background_colour == 7,
+                _ => false,
+            })
+            .iter()
+            .map(|o| o.id())
+            .collect();
+        assert_eq!(vec![ObjectId::from(5000), ObjectId::from(1000)], found);
+        assert!(pool.find_by_predicate(|_| false).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_input_boolean() {
+        let input_boolean = |id: u16, variable_reference: u16, value: bool| {
+            Object::InputBoolean(InputBoolean {
+                id: id.into(),
+                background_colour: 1,
+                width: 20,
+                foreground_colour: 23000.into(),
+                variable_reference: variable_reference.into(),
+                value,
+                enabled: true,
+                macro_refs: Vec::new(),
+            })
+        };
+        let mut pool = ObjectPool::new();
+        pool.add(input_boolean(7000, 0xFFFF, true));
+        pool.add(input_boolean(7001, 21000, false));
+        pool.add(input_boolean(7002, 21001, true));
+        pool.add(input_boolean(7003, 37000, true));
+        pool.add(number_variable(21000, 2));
+        pool.add(number_variable(21001, 0));
+
+        assert_eq!(Some(true), pool.resolve_input_boolean(7000.into()));
+        assert_eq!(Some(true), pool.resolve_input_boolean(7001.into()));
+        assert_eq!(Some(false), pool.resolve_input_boolean(7002.into()));
+        assert_eq!(None, pool.resolve_input_boolean(7003.into()));
+        assert_eq!(None, pool.resolve_input_boolean(21000.into()));
+    }
+
+    #[test]
+    fn test_shift_all_positions() {
+        let mut pool = ObjectPool::new();
+        pool.add(Object::DataMask(DataMask {
+            id: 1000.into(),
+            background_colour: 0,
+            soft_key_mask: 4000.into(),
+            object_refs: vec![
+                ObjectRef {
+                    id: 3000.into(),
+                    offset: Point { x: 10, y: 20 },
+                },
+                ObjectRef {
+                    id: 3001.into(),
+                    offset: Point {
+                        x: i16::MAX - 5,
+                        y: 0,
+                    },
+                },
+            ],
+            macro_refs: Vec::new(),
+        }));
+        pool.add(Object::Container(Container {
+            id: 3000.into(),
+            width: 100,
+            height: 100,
+            hidden: false,
+            object_refs: vec![ObjectRef {
+                id: 3001.into(),
+                offset: Point { x: 1, y: 2 },
+            }],
+            macro_refs: Vec::new(),
+        }));
+        pool.add(output_string(3001, 0xFFFF, "a"));
+        pool.add(Object::SoftKeyMask(SoftKeyMask {
+            id: 4000.into(),
+            background_colour: 0,
+            objects: vec![5000.into()],
+            macro_refs: Vec::new(),
+        }));
+        pool.add(Object::Key(Key {
+            id: 5000.into(),
+            background_colour: 0,
+            key_code: 1,
+            object_refs: vec![ObjectRef {
+                id: 3001.into(),
+                offset: Point { x: 3, y: 4 },
+            }],
+            macro_refs: Vec::new(),
+        }));
+        let key_offset =
+            |pool: &ObjectPool| pool.object_by_id(5000.into()).unwrap().object_refs()[0].offset;
+
+        pool.shift_all_positions(1000.into(), Point { x: 10, y: -5 }, false);
+        assert_eq!(
+            vec![
+                (ObjectId::from(3000), Point { x: 20, y: 15 }),
+                (ObjectId::from(3001), Point { x: 21, y: 17 }),
+                (ObjectId::from(3001), Point { x: i16::MAX, y: -5 }),
+            ],
+            pool.flatten_refs(1000.into())
+        );
+
+        assert_eq!(Point { x: 3, y: 4 }, key_offset(&pool));
+
+        // Nested objects move along with their parent, only the key is shifted by itself
+        pool.shift_all_positions(1000.into(), Point { x: -20, y: 0 }, true);
+        assert_eq!(Point { x: -17, y: 4 }, key_offset(&pool));
+        assert_eq!(
+            vec![
+                (ObjectId::from(3000), Point { x: 0, y: 15 }),
+                (ObjectId::from(3001), Point { x: 1, y: 17 }),
+                (
+                    ObjectId::from(3001),
+                    Point {
+                        x: i16::MAX - 20,
+                        y: -5
+                    }
+                ),
+            ],
+            pool.flatten_refs(1000.into())
+        );
+
+        // An unknown mask is ignored
+        pool.shift_all_positions(4000.into(), Point { x: 1, y: 1 }, true);
+    }
+
+    #[test]
+    fn test_apply_patch() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(data_mask(1000, &[]));
+        pool.add(string_variable(22000, "old"));
+
+        let mut patch = ObjectPool::new();
+        patch.add(string_variable(22000, "new"));
+        patch.add(output_string(11000, 22000, ""));
+
+        let summary = pool.apply_patch(&patch);
+        assert_eq!(vec![ObjectId::from(22000)], summary.replaced);
+        assert_eq!(vec![ObjectId::from(11000)], summary.added);
+
+        assert_eq!(4, pool.objects().len());
+        assert_eq!(
+            Some("new".to_string()),
+            pool.resolve_output_string(11000.into())
+        );
+        assert!(pool.data_mask_object_by_id(1000.into()).is_some());
+        assert_eq!(pool.size(), pool.as_iop().len());
+    }
+
+    #[test]
+    fn test_clone_object() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(data_mask(1, &[2]));
+        pool.add(Object::Button(
+            Button::builder(2.into())
+                .width(80)
+                .add_object_ref(4.into(), Point { x: 1, y: 1 })
+                .build()
+                .unwrap(),
+        ));
+        pool.add(output_string(4, 0xFFFF, "OK"));
+
+        assert_eq!(Some(ObjectId::from(3)), pool.next_free_id());
+        assert_eq!(Some(ObjectId::from(3)), pool.clone_object(2.into()));
+        assert_eq!(Some(ObjectId::from(5)), pool.next_free_id());
+
+        match (pool.object_by_id(2.into()), pool.object_by_id(3.into())) {
+            (Some(Object::Button(original)), Some(Object::Button(copy))) => {
+                assert_eq!(ObjectId::from(3), copy.id);
+                assert_eq!(80, copy.width);
+                assert_eq!(original.object_refs, copy.object_refs);
+            }
+            _ => panic!("expected two buttons"),
+        }
+        assert_eq!(5, pool.objects().len());
+        assert_eq!(pool.size(), pool.as_iop().len());
+
+        assert_eq!(None, pool.clone_object(100.into()));
+    }
+
+    #[test]
+    fn test_to_bytes_into() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(data_mask(1000, &[11000]));
+        pool.add(output_string(11000, 0xFFFF, "Hello"));
+        let expected = pool.as_iop();
+
+        let mut buf = vec![0u8; expected.len()];
+        assert_eq!(Ok(expected.len()), pool.to_bytes_into(&mut buf));
+        assert_eq!(expected, buf);
+
+        let mut buf = vec![0u8; expected.len() + 4];
+        assert_eq!(Ok(expected.len()), pool.to_bytes_into(&mut buf));
+        assert_eq!(expected[..], buf[..expected.len()]);
+
+        let mut buf = vec![0u8; expected.len() - 1];
+        assert_eq!(
+            Err(BufferTooSmall {
+                needed: expected.len()
+            }),
+            pool.to_bytes_into(&mut buf)
+        );
+        assert!(buf.iter().all(|&b| b == 0));
+
+        // An out of date cached size is caught while writing
+        pool.size_cache.set(Some(expected.len() - 1));
+        assert_eq!(
+            Err(BufferTooSmall {
+                needed: expected.len()
+            }),
+            pool.to_bytes_into(&mut buf)
+        );
+        assert_eq!(expected.len(), pool.size());
+    }
+
+    #[test]
+    fn test_lint_non_selectable_working_set() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(data_mask(1000, &[]));
+        let (_, report) =
+            ObjectPool::from_iop_with(pool.as_iop(), ParseOptions::default()).unwrap();
+        assert_eq!(Vec::<ParseWarning>::new(), report.warnings);
+
+        if let Some(Object::WorkingSet(o)) = pool.objects.get_mut(0) {
+            o.selectable = false;
+        }
+        let (_, report) =
+            ObjectPool::from_iop_with(pool.as_iop(), ParseOptions::default()).unwrap();
+        assert_eq!(
+            vec![ParseWarning {
+                id: 0.into(),
+                message: "is not selectable and has no valid active mask".into()
+            }],
+            report.warnings
+        );
+
+        if let Some(Object::WorkingSet(o)) = pool.objects.get_mut(0) {
+            o.active_mask = 1000.into();
+        }
+        let (_, report) =
+            ObjectPool::from_iop_with(pool.as_iop(), ParseOptions::default()).unwrap();
+        assert_eq!(Vec::<ParseWarning>::new(), report.warnings);
+    }
+
+    #[test]
+    fn test_dangling_references() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(data_mask(1000, &[11000]));
+        pool.add(output_string(11000, 0xFFFF, ""));
+        assert!(!pool.has_dangling_references());
+        assert_eq!(Ok(()), pool.validate_references());
+
+        pool.add(data_mask(1001, &[11000, 11001, 11002]));
+        assert!(pool.has_dangling_references());
+        assert_eq!(
+            Err(ValidationError::DanglingReferences(vec![
+                (1001.into(), 11001.into()),
+                (1001.into(), 11002.into()),
+            ])),
+            pool.validate_references()
+        );
+    }
+
+    #[test]
+    fn test_default_background_index() {
+        let mut pool = ObjectPool::new();
+        let button = pool.button_builder(5000.into()).build().unwrap();
+        assert_eq!(8, button.background_colour);
+
+        pool.set_default_background_index(12);
+        assert_eq!(12, pool.default_background_index());
+        let button = pool.button_builder(5001.into()).build().unwrap();
+        assert_eq!(12, button.background_colour);
+
+        let button = pool
+            .button_builder(5002.into())
+            .background_colour(3)
+            .build()
+            .unwrap();
+        assert_eq!(3, button.background_colour);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut pool = ObjectPool::new();
+        pool.add(working_set(0));
+        pool.add(data_mask(1000, &[11000, 12000]));
+        pool.add(output_string(11000, 0xFFFF, "a"));
+        pool.add(font_attributes(23000));
+        pool.add(output_number(12000, 23000, 0xFFFF));
+        pool.size();
+
+        pool.retain(|o| {
+            matches!(
+                o.object_type(),
+                ObjectType::OutputString | ObjectType::OutputNumber
+            )
+        });
+
+        let ids: Vec<ObjectId> = pool.objects().iter().map(Object::id).collect();
+        assert_eq!(vec![ObjectId::from(11000), ObjectId::from(12000)], ids);
+        assert!(pool.object_by_id(12000.into()).is_some());
+        assert!(pool.object_by_id(1000.into()).is_none());
+        assert_eq!(pool.size(), pool.as_iop().len());
+        // The reference to the font attributes is kept
+        assert!(pool.has_dangling_references());
+    }
+
+    #[test]
+    fn test_used_font_ids() {
+        let mut pool = ObjectPool::new();
+        pool.add(font_attributes(23000));
+        pool.add(font_attributes(23001));
+        pool.add(font_attributes(23002));
+        pool.add(output_number(12000, 23000, 0xFFFF));
+        pool.add(output_number(12001, 23000, 0xFFFF));
+        pool.add(output_string(11000, 0xFFFF, "a"));
+        pool.add(Object::InputBoolean(InputBoolean {
+            id: 7000.into(),
+            background_colour: 0,
+            width: 20,
+            foreground_colour: 23002.into(),
+            variable_reference: ObjectId::NULL,
+            value: false,
+            enabled: true,
+            macro_refs: Vec::new(),
+        }));
+
+        assert_eq!(
+            BTreeSet::from([ObjectId::from(23000), ObjectId::from(23002)]),
+            pool.used_font_ids()
+        );
+
+        let used = pool.used_font_ids();
+        pool.retain(|o| o.object_type() != ObjectType::FontAttributes || used.contains(&o.id()));
+        assert!(pool.object_by_id(23001.into()).is_none());
+        assert!(pool.object_by_id(23002.into()).is_some());
+        assert!(!pool.has_dangling_references());
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let mut pointer = data_mask(1001, &[]);
+        if let Object::DataMask(mask) = &mut pointer {
+            mask.object_refs.push(ObjectRef {
+                id: 1000.into(),
+                offset: Point::default(),
+            });
+        }
+        let mut pool: ObjectPool = [
+            output_number(12000, 23000, 21000),
+            data_mask(1000, &[12000, 1001]),
+            number_variable(21000, 5),
+            working_set(0),
+            pointer,
+            font_attributes(23000),
+        ]
+        .into_iter()
+        .collect();
+
+        pool.topological_sort();
+
+        let ids: Vec<u16> = pool.objects().iter().map(|o| o.id().into()).collect();
+        assert_eq!(ids, [0, 23000, 21000, 12000, 1001, 1000]);
+        for obj in pool.objects().iter().skip(1) {
+            let position = |id| pool.objects().iter().position(|o| o.id() == id);
+            for child in obj.child_ids() {
+                if child != 1000.into() {
+                    assert!(position(child) < position(obj.id()));
+                }
+            }
+        }
+        assert_eq!(Some(3), pool.id_index.get(12000.into()));
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let mut pool = ObjectPool::new();
+        pool.add(Object::Container(Container {
+            id: 3000.into(),
+            width: 40,
+            height: 20,
+            hidden: false,
+            object_refs: vec![ObjectRef {
+                id: 12000.into(),
+                offset: Point { x: 30, y: -5 },
+            }],
+            macro_refs: Vec::new(),
+        }));
+        pool.add(output_number(12000, 23000, 21000));
+        pool.add(data_mask(1000, &[3000, 12000]));
+        pool.add(number_variable(21000, 0));
+
+        // The number at x 30 extends 40 pixels past the right edge of the container
+        assert_eq!(
+            Some((Point::default(), Point { x: 40, y: 20 })),
+            pool.bounding_box(3000.into())
+        );
+        assert_eq!(None, pool.bounding_box(21000.into()));
+
+        if let Some(Object::DataMask(mut mask)) = pool.object_by_id(1000.into()).cloned() {
+            mask.object_refs[0].offset = Point { x: 10, y: 10 };
+            mask.object_refs[1].offset = Point { x: -5, y: 0 };
+            pool.replace_object(Object::DataMask(mask));
+        }
+        assert_eq!(
+            Some((Point { x: -5, y: 0 }, Point { x: 55, y: 30 })),
+            pool.bounding_box(1000.into())
+        );
+    }
+
+    #[test]
+    fn test_minimal() {
+        let pool = ObjectPool::minimal(0.into(), 1000.into());
+
+        assert_eq!(2, pool.objects().len());
+        assert_eq!(Some(0.into()), pool.working_set_object_id());
+        assert_eq!(
+            Some(1000.into()),
+            pool.working_set_object().map(|ws| ws.active_mask)
+        );
+        assert_eq!(Ok(()), pool.validate_working_set_count());
+        assert_eq!(Ok(()), pool.validate_active_mask());
+        assert_eq!(Ok(()), pool.validate_references());
+        assert_eq!(pool, ObjectPool::from_iop(pool.as_iop()));
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let container = |id: u16, children: &[u16]| {
+            Object::Container(Container {
+                id: id.into(),
+                width: 100,
+                height: 100,
+                hidden: false,
+                object_refs: children
+                    .iter()
+                    .map(|&c| ObjectRef {
+                        id: c.into(),
+                        offset: Point::default(),
+                    })
+                    .collect(),
+                macro_refs: Vec::new(),
+            })
+        };
+        let mut pool = ObjectPool::new();
+        pool.add(data_mask(1000, &[3000, 3003]));
+        pool.add(container(3000, &[3001]));
+        pool.add(container(3001, &[3002]));
+        pool.add(container(3002, &[3001]));
+        pool.add(container(3003, &[]));
+
+        assert_eq!(3, pool.max_depth(1000.into()));
+        assert_eq!(1, pool.max_depth(3001.into()));
+        assert_eq!(0, pool.max_depth(3003.into()));
+        assert_eq!(0, pool.max_depth(37000.into()));
+
+        // Every container references the next one twice, 2^40 paths in total
+        let mut pool = ObjectPool::new();
+        for level in 0..40 {
+            pool.add(container(3000 + level, &[3001 + level, 3001 + level]));
+        }
+        pool.add(container(3040, &[]));
+        assert_eq!(40, pool.max_depth(3000.into()));
+    }
+
+    #[test]
+    fn test_max_depth_diamond() {
+        // 1000 -> 3000 -> 3002 -> 12000 and 1000 -> 3001 -> 12000
+        let mut pool = ObjectPool::new();
+        pool.add(data_mask(1000, &[3000, 3001]));
+        pool.add(data_mask(3000, &[3002]));
+        pool.add(data_mask(3001, &[12000]));
+        pool.add(data_mask(3002, &[12000]));
+        pool.add(output_number(12000, 23000, 21000));
+        pool.add(font_attributes(23000));
+
+        assert_eq!(4, pool.max_depth(1000.into()));
+        assert_eq!(2, pool.max_depth(3001.into()));
+    }
+
+    #[test]
+    fn test_max_depth_cycle_order() {
+        // 1 -> [2, 3], 2 -> [3, 4], 3 -> [2], 4 -> 5 -> 6, deepest path 1 -> 3 -> 2 -> 4 -> 5 -> 6
+        let mut pool = ObjectPool::new();
+        pool.add(data_mask(1, &[2, 3]));
+        pool.add(data_mask(2, &[3, 4]));
+        pool.add(data_mask(3, &[2]));
+        pool.add(data_mask(4, &[5]));
+        pool.add(data_mask(5, &[6]));
+        pool.add(data_mask(6, &[]));
+
+        let deepest = pool.iter_with_depth(1.into()).map(|(_, d)| d).max();
+        assert_eq!(Some(5), deepest);
+        assert_eq!(5, pool.max_depth(1.into()));
+    }
+
+    #[test]
+    fn test_validate_strings() {
+        let mut pool = ObjectPool::minimal(0.into(), 1000.into());
+        pool.add(output_string(11000, 0xFFFF, "5 €"));
+        assert_eq!(
+            Err(vec![ValidationError::UnencodableString {
+                id: 11000.into(),
+                character: '€',
+            }]),
+            pool.validate()
+        );
+
+        let stored = CharacterSet::Latin9.encode_str("5 €").unwrap();
+        pool.replace_object(output_string(11000, 0xFFFF, &stored));
+        assert_eq!(Ok(()), pool.validate());
+    }
+
+    #[test]
+    fn test_validate() {
+        let pool = ObjectPool::minimal(0.into(), 1000.into());
+        assert_eq!(Ok(()), pool.validate());
+
+        let mut pool = ObjectPool::new();
+        pool.add(data_mask(1000, &[1001, 37000]));
+        pool.add(data_mask(1001, &[1002]));
+        pool.add(data_mask(1002, &[1001]));
+        pool.add(number_variable(21000, 0));
+        pool.add(number_variable(21000, 1));
+        pool.add(Object::PictureGraphic(PictureGraphic {
+            id: 20000.into(),
+            width: 2,
+            actual_width: 2,
+            actual_height: 2,
+            format: 2,
+            options: 0,
+            transparency_colour: 0,
+            data: vec![0; 3],
+            macro_refs: Vec::new(),
+        }));
+
+        assert_eq!(
+            Err(vec![
+                ValidationError::MissingWorkingSet,
+                ValidationError::DuplicateId(21000.into()),
+                ValidationError::DanglingReferences(vec![(1000.into(), 37000.into())]),
+                ValidationError::ReferenceCycle(vec![1001.into(), 1002.into()]),
+                ValidationError::InvalidPictureGraphicSize {
+                    id: 20000.into(),
+                    expected: 4,
+                    found: 3,
+                },
+            ]),
+            pool.validate()
+        );
+    }
+
+    #[test]
+    fn test_commands_clear_size_cache() {
+        let mut pool = ObjectPool::new();
+        pool.add(Object::InputString(InputString {
+            id: 1.into(),
+            width: 200,
+            height: 20,
+            background_colour: 0,
+            font_attributes: 23000.into(),
+            input_attributes: ObjectId::NULL,
+            options: 0,
+            variable_reference: ObjectId::NULL,
+            justification: 0,
+            value: "ab".into(),
+            enabled: true,
+            macro_refs: Vec::new(),
+        }));
+        pool.add(font_attributes(23000));
+        pool.add(string_variable(22000, "Hello"));
+        let summed_size =
+            |pool: &ObjectPool| -> usize { pool.objects().iter().map(|o| o.write().len()).sum() };
+        assert_eq!(summed_size(&pool), pool.size());
+
+        pool.change_string_value(1.into(), "abcdefghij").unwrap();
+        assert_eq!(summed_size(&pool), pool.size());
+        let mut buf = vec![0; pool.size()];
+        assert_eq!(Ok(pool.size()), pool.to_bytes_into(&mut buf));
+
+        pool.change_string_value(22000.into(), "Hi").unwrap();
+        assert_eq!(summed_size(&pool), pool.size());
+    }
+
+    #[test]
+    fn test_walk_shared_subtrees() {
+        // Every mask references the next one twice, 2^40 paths to the last one
+        let mut pool = ObjectPool::new();
+        for level in 0..40 {
+            pool.add(data_mask(1000 + level, &[1001 + level, 1001 + level]));
+        }
+        pool.add(data_mask(1040, &[]));
+        pool.add(data_mask(2000, &[]));
+
+        assert_eq!(41, pool.walk_from(1000.into()).len());
+        assert_eq!(None, pool.absolute_offset(1000.into(), 2000.into()));
+        assert_eq!(
+            Some(Point::default()),
+            pool.absolute_offset(1000.into(), 1040.into())
+        );
     }
 }
@@ -0,0 +1,22 @@
+//! Re-exports of the commonly used object pool types.
+//!
+//! ```rust
+//! use ag_iso_stack::object_pool::prelude::*;
+//!
+//! let mut pool = ObjectPool::new();
+//! pool.add(Object::Button(Button::builder(ObjectId::from(5000)).build().unwrap()));
+//! assert_eq!(1, pool.objects_by_type(ObjectType::Button).len());
+//! ```
+
+pub use super::{
+    AlarmMask, Animation, AuxiliaryControlDesignatorType2, AuxiliaryFunctionType1,
+    AuxiliaryFunctionType2, AuxiliaryInputType1, AuxiliaryInputType2, Button, ButtonBuilder,
+    Colour, ColourMap, ColourPalette, Container, DataMask, ExtendedInputAttributes,
+    ExternalObjectDefinition, ExternalObjectPointer, ExternalReferenceName, FillAttributes,
+    FontAttributes, GraphicData, GraphicsContext, InputAttributes, InputBoolean, InputList,
+    InputNumber, InputString, Key, KeyGroup, LineAttributes, Macro, MacroRef, NumberVariable,
+    Object, ObjectId, ObjectLabel, ObjectLabelReferenceList, ObjectPointer, ObjectPool, ObjectRef,
+    ObjectType, OutputArchedBarGraph, OutputEllipse, OutputLine, OutputLinearBarGraph, OutputList,
+    OutputMeter, OutputNumber, OutputPolygon, OutputRectangle, OutputString, PictureGraphic, Point,
+    ScalesGraphic, SoftKeyMask, StringVariable, WindowMask, WorkingSet, WorkingSetSpecialControls,
+};
@@ -2,795 +2,1098 @@ use super::*;
 
 impl Object {
     pub fn read(data: &mut dyn Iterator<Item = u8>) -> Result<Self, ParseError> {
-        let id = Self::read_u16(data)?.into();
+        let id = Self::read_object_id(data)?;
         let object_type = Self::read_u8(data)?.try_into()?;
 
         match object_type {
-            ObjectType::WorkingSet => {
-                let mut o = WorkingSet {
-                    id,
-                    background_colour: Self::read_u8(data)?,
-                    selectable: Self::read_bool(data)?,
-                    active_mask: Self::read_u16(data)?.into(),
-                    object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                    language_codes: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.object_refs
-                    .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                for _ in 0..o.language_codes.capacity() {
-                    o.language_codes.push(Self::read_string(2, data)?)
-                }
-
-                Ok(Object::WorkingSet(o))
-            }
-            ObjectType::DataMask => {
-                let mut o = DataMask {
-                    id,
-                    background_colour: Self::read_u8(data)?,
-                    soft_key_mask: Self::read_u16(data)?.into(),
-                    object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.object_refs
-                    .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::DataMask(o))
-            }
-            ObjectType::AlarmMask => {
-                let mut o = AlarmMask {
-                    id,
-                    background_colour: Self::read_u8(data)?,
-                    soft_key_mask: Self::read_u16(data)?.into(),
-                    priority: Self::read_u8(data)?,
-                    acoustic_signal: Self::read_u8(data)?,
-                    object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.object_refs
-                    .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::AlarmMask(o))
-            }
-            ObjectType::Container => {
-                let mut o = Container {
-                    id,
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    hidden: Self::read_bool(data)?,
-                    object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.object_refs
-                    .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::Container(o))
-            }
-            ObjectType::SoftKeyMask => {
-                let mut o = SoftKeyMask {
-                    id,
-                    background_colour: Self::read_u8(data)?,
-                    objects: Vec::with_capacity(Self::read_u8(data)?.into()),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.objects
-                    .extend(Self::read_objects(data, o.objects.capacity())?);
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::SoftKeyMask(o))
-            }
-            ObjectType::Key => {
-                let mut o = Key {
-                    id,
-                    background_colour: Self::read_u8(data)?,
-                    key_code: Self::read_u8(data)?,
-                    object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.object_refs
-                    .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::Key(o))
-            }
-            ObjectType::Button => {
-                let mut o = Button {
-                    id,
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    background_colour: Self::read_u8(data)?,
-                    border_colour: Self::read_u8(data)?,
-                    key_code: Self::read_u8(data)?,
-                    options: Self::read_u8(data)?,
-                    object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.object_refs
-                    .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::Button(o))
-            }
+            ObjectType::WorkingSet => Ok(Object::WorkingSet(Self::read_working_set(id, data)?)),
+            ObjectType::DataMask => Ok(Object::DataMask(Self::read_data_mask(id, data)?)),
+            ObjectType::AlarmMask => Ok(Object::AlarmMask(Self::read_alarm_mask(id, data)?)),
+            ObjectType::Container => Ok(Object::Container(Self::read_container(id, data)?)),
+            ObjectType::SoftKeyMask => Ok(Object::SoftKeyMask(Self::read_soft_key_mask(id, data)?)),
+            ObjectType::Key => Ok(Object::Key(Self::read_key(id, data)?)),
+            ObjectType::Button => Ok(Object::Button(Self::read_button(id, data)?)),
             ObjectType::InputBoolean => {
-                let mut o = InputBoolean {
-                    id,
-                    background_colour: Self::read_u8(data)?,
-                    width: Self::read_u16(data)?,
-                    foreground_colour: Self::read_u16(data)?.into(),
-                    variable_reference: Self::read_u16(data)?.into(),
-                    value: Self::read_bool(data)?,
-                    enabled: Self::read_bool(data)?,
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::InputBoolean(o))
-            }
-            ObjectType::InputString => {
-                let mut o = InputString {
-                    id,
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    background_colour: Self::read_u8(data)?,
-                    font_attributes: Self::read_u16(data)?.into(),
-                    input_attributes: Self::read_u16(data)?.into(),
-                    options: Self::read_u8(data)?,
-                    variable_reference: Self::read_u16(data)?.into(),
-                    justification: Self::read_u8(data)?,
-                    value: Self::read_string(Self::read_u8(data)?.into(), data)?,
-                    enabled: Self::read_bool(data)?,
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::InputString(o))
-            }
-            ObjectType::InputNumber => {
-                let mut o = InputNumber {
-                    id,
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    background_colour: Self::read_u8(data)?,
-                    font_attributes: Self::read_u16(data)?.into(),
-                    options: Self::read_u8(data)?,
-                    variable_reference: Self::read_u16(data)?.into(),
-                    value: Self::read_u32(data)?,
-                    min_value: Self::read_u32(data)?,
-                    max_value: Self::read_u32(data)?,
-                    offset: Self::read_i32(data)?,
-                    scale: Self::read_f32(data)?,
-                    nr_of_decimals: Self::read_u8(data)?,
-                    format: Self::read_bool(data)?,
-                    justification: Self::read_u8(data)?,
-                    options2: Self::read_u8(data)?,
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::InputNumber(o))
-            }
-            ObjectType::InputList => {
-                let mut o = InputList {
-                    id,
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    variable_reference: Self::read_u16(data)?.into(),
-                    value: Self::read_u8(data)?,
-                    list_items: Vec::with_capacity(Self::read_u8(data)?.into()),
-                    options: Self::read_u8(data)?,
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.list_items
-                    .extend(Self::read_objects(data, o.list_items.capacity())?);
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::InputList(o))
+                Ok(Object::InputBoolean(Self::read_input_boolean(id, data)?))
             }
+            ObjectType::InputString => Ok(Object::InputString(Self::read_input_string(id, data)?)),
+            ObjectType::InputNumber => Ok(Object::InputNumber(Self::read_input_number(id, data)?)),
+            ObjectType::InputList => Ok(Object::InputList(Self::read_input_list(id, data)?)),
             ObjectType::OutputString => {
-                let mut o = OutputString {
-                    id,
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    background_colour: Self::read_u8(data)?,
-                    font_attributes: Self::read_u16(data)?.into(),
-                    options: Self::read_u8(data)?,
-                    variable_reference: Self::read_u16(data)?.into(),
-                    justification: Self::read_u8(data)?,
-                    value: Self::read_string(Self::read_u16(data)?.into(), data)?,
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::OutputString(o))
+                Ok(Object::OutputString(Self::read_output_string(id, data)?))
             }
             ObjectType::OutputNumber => {
-                let mut o = OutputNumber {
-                    id,
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    background_colour: Self::read_u8(data)?,
-                    font_attributes: Self::read_u16(data)?.into(),
-                    options: Self::read_u8(data)?,
-                    variable_reference: Self::read_u16(data)?.into(),
-                    value: Self::read_u32(data)?,
-                    offset: Self::read_i32(data)?,
-                    scale: Self::read_f32(data)?,
-                    nr_of_decimals: Self::read_u8(data)?,
-                    format: Self::read_bool(data)?,
-                    justification: Self::read_u8(data)?,
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::OutputNumber(o))
-            }
-            ObjectType::OutputLine => {
-                let mut o = OutputLine {
-                    id,
-                    line_attributes: Self::read_u16(data)?.into(),
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    line_direction: Self::read_u8(data)?,
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::OutputLine(o))
-            }
-            ObjectType::OutputRectangle => {
-                let mut o = OutputRectangle {
-                    id,
-                    line_attributes: Self::read_u16(data)?.into(),
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    line_suppression: Self::read_u8(data)?,
-                    fill_attributes: Self::read_u16(data)?.into(),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::OutputRectangle(o))
+                Ok(Object::OutputNumber(Self::read_output_number(id, data)?))
             }
+            ObjectType::OutputLine => Ok(Object::OutputLine(Self::read_output_line(id, data)?)),
+            ObjectType::OutputRectangle => Ok(Object::OutputRectangle(
+                Self::read_output_rectangle(id, data)?,
+            )),
             ObjectType::OutputEllipse => {
-                let mut o = OutputEllipse {
-                    id,
-                    line_attributes: Self::read_u16(data)?.into(),
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    ellipse_type: Self::read_u8(data)?,
-                    start_angle: Self::read_u8(data)?,
-                    end_angle: Self::read_u8(data)?,
-                    fill_attributes: Self::read_u16(data)?.into(),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::OutputEllipse(o))
+                Ok(Object::OutputEllipse(Self::read_output_ellipse(id, data)?))
             }
             ObjectType::OutputPolygon => {
-                let mut o = OutputPolygon {
-                    id,
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    line_attributes: Self::read_u16(data)?.into(),
-                    fill_attributes: Self::read_u16(data)?.into(),
-                    polygon_type: Self::read_u8(data)?,
-                    points: Vec::with_capacity(Self::read_u8(data)?.into()),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.points
-                    .extend(Self::read_points(data, o.points.capacity())?);
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::OutputPolygon(o))
+                Ok(Object::OutputPolygon(Self::read_output_polygon(id, data)?))
             }
-            ObjectType::OutputMeter => {
-                let mut o = OutputMeter {
-                    id,
-                    width: Self::read_u16(data)?,
-                    needle_colour: Self::read_u8(data)?,
-                    border_colour: Self::read_u8(data)?,
-                    arc_and_tick_colour: Self::read_u8(data)?,
-                    options: Self::read_u8(data)?,
-                    nr_of_ticks: Self::read_u8(data)?,
-                    start_angle: Self::read_u8(data)?,
-                    end_angle: Self::read_u8(data)?,
-                    min_value: Self::read_u16(data)?,
-                    max_value: Self::read_u16(data)?,
-                    variable_reference: Self::read_u16(data)?.into(),
-                    value: Self::read_u16(data)?,
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::OutputMeter(o))
+            ObjectType::OutputMeter => Ok(Object::OutputMeter(Self::read_output_meter(id, data)?)),
+            ObjectType::OutputLinearBarGraph => Ok(Object::OutputLinearBarGraph(
+                Self::read_output_linear_bar_graph(id, data)?,
+            )),
+            ObjectType::OutputArchedBarGraph => Ok(Object::OutputArchedBarGraph(
+                Self::read_output_arched_bar_graph(id, data)?,
+            )),
+            ObjectType::PictureGraphic => Ok(Object::PictureGraphic(Self::read_picture_graphic(
+                id, data,
+            )?)),
+            ObjectType::NumberVariable => Ok(Object::NumberVariable(Self::read_number_variable(
+                id, data,
+            )?)),
+            ObjectType::StringVariable => Ok(Object::StringVariable(Self::read_string_variable(
+                id, data,
+            )?)),
+            ObjectType::FontAttributes => Ok(Object::FontAttributes(Self::read_font_attributes(
+                id, data,
+            )?)),
+            ObjectType::LineAttributes => Ok(Object::LineAttributes(Self::read_line_attributes(
+                id, data,
+            )?)),
+            ObjectType::FillAttributes => Ok(Object::FillAttributes(Self::read_fill_attributes(
+                id, data,
+            )?)),
+            ObjectType::InputAttributes => Ok(Object::InputAttributes(
+                Self::read_input_attributes(id, data)?,
+            )),
+            ObjectType::ObjectPointer => {
+                Ok(Object::ObjectPointer(Self::read_object_pointer(id, data)?))
             }
-            ObjectType::OutputLinearBarGraph => {
-                let mut o = OutputLinearBarGraph {
-                    id,
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    colour: Self::read_u8(data)?,
-                    target_line_colour: Self::read_u8(data)?,
-                    options: Self::read_u8(data)?,
-                    nr_of_ticks: Self::read_u8(data)?,
-                    min_value: Self::read_u16(data)?,
-                    max_value: Self::read_u16(data)?,
-                    variable_reference: Self::read_u16(data)?.into(),
-                    value: Self::read_u16(data)?,
-                    target_value_variable_reference: Self::read_u16(data)?.into(),
-                    target_value: Self::read_u16(data)?,
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::OutputLinearBarGraph(o))
+            ObjectType::Macro => Ok(Object::Macro(Self::read_macro(id, data)?)),
+            ObjectType::AuxiliaryFunctionType1 => Ok(Object::AuxiliaryFunctionType1(
+                Self::read_auxiliary_function_type1(id, data)?,
+            )),
+            ObjectType::AuxiliaryInputType1 => Ok(Object::AuxiliaryInputType1(
+                Self::read_auxiliary_input_type1(id, data)?,
+            )),
+            ObjectType::AuxiliaryFunctionType2 => Ok(Object::AuxiliaryFunctionType2(
+                Self::read_auxiliary_function_type2(id, data)?,
+            )),
+            ObjectType::AuxiliaryInputType2 => Ok(Object::AuxiliaryInputType2(
+                Self::read_auxiliary_input_type2(id, data)?,
+            )),
+            ObjectType::AuxiliaryControlDesignatorType2 => {
+                Ok(Object::AuxiliaryControlDesignatorType2(
+                    Self::read_auxiliary_control_designator_type2(id, data)?,
+                ))
             }
-            ObjectType::OutputArchedBarGraph => {
-                let mut o = OutputArchedBarGraph {
-                    id,
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    colour: Self::read_u8(data)?,
-                    target_line_colour: Self::read_u8(data)?,
-                    options: Self::read_u8(data)?,
-                    start_angle: Self::read_u8(data)?,
-                    end_angle: Self::read_u8(data)?,
-                    bar_graph_width: Self::read_u16(data)?,
-                    min_value: Self::read_u16(data)?,
-                    max_value: Self::read_u16(data)?,
-                    variable_reference: Self::read_u16(data)?.into(),
-                    value: Self::read_u16(data)?,
-                    target_value_variable_reference: Self::read_u16(data)?.into(),
-                    target_value: Self::read_u16(data)?,
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::OutputArchedBarGraph(o))
+            ObjectType::WindowMask => Ok(Object::WindowMask(Self::read_window_mask(id, data)?)),
+            ObjectType::KeyGroup => Ok(Object::KeyGroup(Self::read_key_group(id, data)?)),
+            ObjectType::GraphicsContext => Ok(Object::GraphicsContext(
+                Self::read_graphics_context(id, data)?,
+            )),
+            ObjectType::OutputList => Ok(Object::OutputList(Self::read_output_list(id, data)?)),
+            ObjectType::ExtendedInputAttributes => Ok(Object::ExtendedInputAttributes(
+                Self::read_extended_input_attributes(id, data)?,
+            )),
+            ObjectType::ColourMap => Ok(Object::ColourMap(Self::read_colour_map(id, data)?)),
+            ObjectType::ObjectLabelReferenceList => Ok(Object::ObjectLabelReferenceList(
+                Self::read_object_label_reference_list(id, data)?,
+            )),
+            ObjectType::ExternalObjectDefinition => Ok(Object::ExternalObjectDefinition(
+                Self::read_external_object_definition(id, data)?,
+            )),
+            ObjectType::ExternalReferenceName => Ok(Object::ExternalReferenceName(
+                Self::read_external_reference_name(id, data)?,
+            )),
+            ObjectType::ExternalObjectPointer => Ok(Object::ExternalObjectPointer(
+                Self::read_external_object_pointer(id, data)?,
+            )),
+            ObjectType::Animation => Ok(Object::Animation(Self::read_animation(id, data)?)),
+            ObjectType::ColourPalette => {
+                Ok(Object::ColourPalette(Self::read_colour_palette(id, data)?))
             }
-            ObjectType::PictureGraphic => {
-                let mut o = PictureGraphic {
-                    id,
-                    width: Self::read_u16(data)?,
-                    actual_width: Self::read_u16(data)?,
-                    actual_height: Self::read_u16(data)?,
-                    format: Self::read_u8(data)?,
-                    options: Self::read_u8(data)?,
-                    transparency_colour: Self::read_u8(data)?,
-                    data: Vec::with_capacity(Self::read_u32(data)? as usize),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.data.extend(Self::read_bytes(data, o.data.capacity())?);
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::PictureGraphic(o))
+            ObjectType::GraphicData => Ok(Object::GraphicData(Self::read_graphic_data(id, data)?)),
+            ObjectType::WorkingSetSpecialControls => Ok(Object::WorkingSetSpecialControls(
+                Self::read_working_set_special_controls(id, data)?,
+            )),
+            ObjectType::ScalesGraphic => {
+                Ok(Object::ScalesGraphic(Self::read_scales_graphic(id, data)?))
             }
-            ObjectType::NumberVariable => {
-                let o = NumberVariable {
-                    id,
-                    value: Self::read_u32(data)?,
-                };
+        }
+    }
 
-                Ok(Object::NumberVariable(o))
-            }
-            ObjectType::StringVariable => {
-                let o = StringVariable {
-                    id,
-                    value: Self::read_string(Self::read_u16(data)?.into(), data)?,
-                };
+    fn read_working_set(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<WorkingSet, ParseError> {
+        let mut o = WorkingSet {
+            id,
+            background_colour: Self::read_u8(data)?,
+            selectable: Self::read_bool(data)?,
+            active_mask: Self::read_object_id(data)?,
+            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            language_codes: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.object_refs
+            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        for _ in 0..o.language_codes.capacity() {
+            o.language_codes.push(Self::read_string(2, data)?)
+        }
 
-                Ok(Object::StringVariable(o))
-            }
-            ObjectType::FontAttributes => {
-                let mut o = FontAttributes {
-                    id,
-                    font_colour: Self::read_u8(data)?,
-                    font_size: Self::read_u8(data)?,
-                    font_type: Self::read_u8(data)?,
-                    font_style: Self::read_u8(data)?,
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::FontAttributes(o))
-            }
-            ObjectType::LineAttributes => {
-                let mut o = LineAttributes {
-                    id,
-                    line_colour: Self::read_u8(data)?,
-                    line_width: Self::read_u8(data)?,
-                    line_art: Self::read_u16(data)?,
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::LineAttributes(o))
-            }
-            ObjectType::FillAttributes => {
-                let mut o = FillAttributes {
-                    id,
-                    fill_type: Self::read_u8(data)?,
-                    fill_colour: Self::read_u8(data)?,
-                    fill_pattern: Self::read_u16(data)?.into(),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::FillAttributes(o))
-            }
-            ObjectType::InputAttributes => {
-                let mut o = InputAttributes {
-                    id,
-                    validation_type: Self::read_u8(data)?,
-                    validation_string: Self::read_string(Self::read_u8(data)?.into(), data)?,
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
+        Ok(o)
+    }
 
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+    fn read_data_mask(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<DataMask, ParseError> {
+        let mut o = DataMask {
+            id,
+            background_colour: Self::read_u8(data)?,
+            soft_key_mask: Self::read_object_id(data)?,
+            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.object_refs
+            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                Ok(Object::InputAttributes(o))
-            }
-            ObjectType::ObjectPointer => {
-                let o = ObjectPointer {
-                    id,
-                    value: Self::read_u16(data)?.into(),
-                };
+    fn read_alarm_mask(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<AlarmMask, ParseError> {
+        let mut o = AlarmMask {
+            id,
+            background_colour: Self::read_u8(data)?,
+            soft_key_mask: Self::read_object_id(data)?,
+            priority: Self::read_u8(data)?,
+            acoustic_signal: Self::read_u8(data)?,
+            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.object_refs
+            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                Ok(Object::ObjectPointer(o))
-            }
-            ObjectType::Macro => {
-                let mut o = Macro {
-                    id,
-                    commands: Vec::with_capacity(Self::read_u16(data)?.into()),
-                };
+    fn read_container(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<Container, ParseError> {
+        let mut o = Container {
+            id,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            hidden: Self::read_bool(data)?,
+            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.object_refs
+            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                o.commands
-                    .extend(Self::read_bytes(data, o.commands.capacity())?);
+    fn read_soft_key_mask(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<SoftKeyMask, ParseError> {
+        let mut o = SoftKeyMask {
+            id,
+            background_colour: Self::read_u8(data)?,
+            objects: Vec::with_capacity(Self::read_u8(data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.objects
+            .extend(Self::read_objects(data, o.objects.capacity())?);
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                Ok(Object::Macro(o))
-            }
-            ObjectType::AuxiliaryFunctionType1 => {
-                let mut o = AuxiliaryFunctionType1 {
-                    id,
-                    background_colour: Self::read_u8(data)?,
-                    function_type: Self::read_u8(data)?,
-                    object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.object_refs
-                    .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-
-                Ok(Object::AuxiliaryFunctionType1(o))
-            }
-            ObjectType::AuxiliaryInputType1 => {
-                let mut o = AuxiliaryInputType1 {
-                    id,
-                    background_colour: Self::read_u8(data)?,
-                    function_type: Self::read_u8(data)?,
-                    input_id: Self::read_u8(data)?,
-                    object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.object_refs
-                    .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-
-                Ok(Object::AuxiliaryInputType1(o))
-            }
-            ObjectType::AuxiliaryFunctionType2 => {
-                let mut o = AuxiliaryFunctionType2 {
-                    id,
-                    background_colour: Self::read_u8(data)?,
-                    function_attributes: Self::read_u8(data)?,
-                    object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.object_refs
-                    .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-
-                Ok(Object::AuxiliaryFunctionType2(o))
-            }
-            ObjectType::AuxiliaryInputType2 => {
-                let mut o = AuxiliaryInputType2 {
-                    id,
-                    background_colour: Self::read_u8(data)?,
-                    function_attributes: Self::read_u8(data)?,
-                    object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.object_refs
-                    .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-
-                Ok(Object::AuxiliaryInputType2(o))
-            }
-            ObjectType::AuxiliaryControlDesignatorType2 => {
-                let o = AuxiliaryControlDesignatorType2 {
-                    id,
-                    pointer_type: Self::read_u8(data)?,
-                    auxiliary_object_id: Self::read_u16(data)?.into(),
-                };
+    fn read_key(id: ObjectId, data: &mut dyn Iterator<Item = u8>) -> Result<Key, ParseError> {
+        let mut o = Key {
+            id,
+            background_colour: Self::read_u8(data)?,
+            key_code: Self::read_u8(data)?,
+            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.object_refs
+            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                Ok(Object::AuxiliaryControlDesignatorType2(o))
-            }
-            ObjectType::WindowMask => {
-                let mut o = WindowMask {
-                    id,
-                    width: Self::read_u8(data)?,
-                    height: Self::read_u8(data)?,
-                    window_type: Self::read_u8(data)?,
-                    background_colour: Self::read_u8(data)?,
-                    options: Self::read_u8(data)?,
-                    name: Self::read_u16(data)?.into(),
-                    window_title: Self::read_u16(data)?.into(),
-                    window_icon: Self::read_u16(data)?.into(),
-                    objects: Vec::with_capacity(Self::read_u8(data)?.into()),
-                    object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.objects
-                    .extend(Self::read_objects(data, o.objects.capacity())?);
-                o.object_refs
-                    .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::WindowMask(o))
-            }
-            ObjectType::KeyGroup => {
-                let mut o = KeyGroup {
-                    id,
-                    options: Self::read_u8(data)?,
-                    name: Self::read_u16(data)?.into(),
-                    key_group_icon: Self::read_u16(data)?.into(),
-                    objects: Vec::with_capacity(Self::read_u8(data)?.into()),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.objects
-                    .extend(Self::read_objects(data, o.objects.capacity())?);
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::KeyGroup(o))
-            }
-            ObjectType::GraphicsContext => {
-                let o = GraphicsContext {
-                    id,
-                    viewport_width: Self::read_u16(data)?,
-                    viewport_height: Self::read_u16(data)?,
-                    viewport_x: Self::read_i16(data)?,
-                    viewport_y: Self::read_i16(data)?,
-                    canvas_width: Self::read_u16(data)?,
-                    canvas_height: Self::read_u16(data)?,
-                    viewport_zoom: Self::read_f32(data)?,
-                    graphics_cursor_x: Self::read_i16(data)?,
-                    graphics_cursor_y: Self::read_i16(data)?,
-                    foreground_colour: Self::read_u8(data)?,
-                    background_colour: Self::read_u8(data)?,
-                    font_attributes_object: Self::read_u16(data)?.into(),
-                    line_attributes_object: Self::read_u16(data)?.into(),
-                    fill_attributes_object: Self::read_u16(data)?.into(),
-                    format: Self::read_u8(data)?,
-                    options: Self::read_u8(data)?,
-                    transparency_colour: Self::read_u8(data)?,
-                };
-
-                Ok(Object::GraphicsContext(o))
-            }
-            ObjectType::OutputList => {
-                let mut o = OutputList {
-                    id,
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    variable_reference: Self::read_u16(data)?.into(),
-                    value: Self::read_u8(data)?,
-                    list_items: Vec::with_capacity(Self::read_u8(data)?.into()),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.list_items
-                    .extend(Self::read_objects(data, o.list_items.capacity())?);
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::OutputList(o))
-            }
-            ObjectType::ExtendedInputAttributes => {
-                let o = ExtendedInputAttributes {
-                    id,
-                    validation_type: Self::read_u8(data)?,
-                    nr_of_code_planes: Self::read_u8(data)?,
-                };
+    fn read_button(id: ObjectId, data: &mut dyn Iterator<Item = u8>) -> Result<Button, ParseError> {
+        let mut o = Button {
+            id,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            background_colour: Self::read_u8(data)?,
+            border_colour: Self::read_u8(data)?,
+            key_code: Self::read_u8(data)?,
+            options: Self::read_u8(data)?,
+            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.object_refs
+            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                Ok(Object::ExtendedInputAttributes(o))
-            }
-            ObjectType::ColourMap => {
-                let mut o = ColourMap {
-                    id,
-                    colour_map: Vec::with_capacity(Self::read_u16(data)?.into()),
-                };
+    fn read_input_boolean(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<InputBoolean, ParseError> {
+        let mut o = InputBoolean {
+            id,
+            background_colour: Self::read_u8(data)?,
+            width: Self::read_u16(data)?,
+            foreground_colour: Self::read_object_id(data)?,
+            variable_reference: Self::read_object_id(data)?,
+            value: Self::read_bool(data)?,
+            enabled: Self::read_bool(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                o.colour_map
-                    .extend(Self::read_bytes(data, o.colour_map.capacity())?);
+    fn read_input_string(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<InputString, ParseError> {
+        let mut o = InputString {
+            id,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            background_colour: Self::read_u8(data)?,
+            font_attributes: Self::read_object_id(data)?,
+            input_attributes: Self::read_object_id(data)?,
+            options: Self::read_u8(data)?,
+            variable_reference: Self::read_object_id(data)?,
+            justification: Self::read_u8(data)?,
+            value: Self::read_string_u8(data)?,
+            enabled: Self::read_bool(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                Ok(Object::ColourMap(o))
-            }
-            ObjectType::ObjectLabelReferenceList => {
-                let mut o = ObjectLabelReferenceList {
-                    id,
-                    object_labels: Vec::with_capacity(Self::read_u16(data)?.into()),
-                };
+    fn read_input_number(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<InputNumber, ParseError> {
+        let mut o = InputNumber {
+            id,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            background_colour: Self::read_u8(data)?,
+            font_attributes: Self::read_object_id(data)?,
+            options: Self::read_u8(data)?,
+            variable_reference: Self::read_object_id(data)?,
+            value: Self::read_u32(data)?,
+            min_value: Self::read_u32(data)?,
+            max_value: Self::read_u32(data)?,
+            offset: Self::read_i32(data)?,
+            scale: Self::read_f32(data)?,
+            nr_of_decimals: Self::read_u8(data)?,
+            format: Self::read_bool(data)?,
+            justification: Self::read_u8(data)?,
+            options2: Self::read_u8(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                o.object_labels
-                    .extend(Self::read_object_labels(data, o.object_labels.capacity())?);
+    fn read_input_list(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<InputList, ParseError> {
+        let mut o = InputList {
+            id,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            variable_reference: Self::read_object_id(data)?,
+            value: Self::read_u8(data)?,
+            list_items: Vec::with_capacity(Self::read_u8(data)?.into()),
+            options: Self::read_u8(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.list_items
+            .extend(Self::read_objects(data, o.list_items.capacity())?);
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                Ok(Object::ObjectLabelReferenceList(o))
-            }
-            ObjectType::ExternalObjectDefinition => {
-                let mut o = ExternalObjectDefinition {
-                    id,
-                    options: Self::read_u8(data)?,
-                    name: Self::read_name(data)?,
-                    objects: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
+    fn read_output_string(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<OutputString, ParseError> {
+        let mut o = OutputString {
+            id,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            background_colour: Self::read_u8(data)?,
+            font_attributes: Self::read_object_id(data)?,
+            options: Self::read_u8(data)?,
+            variable_reference: Self::read_object_id(data)?,
+            justification: Self::read_u8(data)?,
+            value: Self::read_string_u16(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                o.objects
-                    .extend(Self::read_objects(data, o.objects.capacity())?);
+    fn read_output_number(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<OutputNumber, ParseError> {
+        let mut o = OutputNumber {
+            id,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            background_colour: Self::read_u8(data)?,
+            font_attributes: Self::read_object_id(data)?,
+            options: Self::read_u8(data)?,
+            variable_reference: Self::read_object_id(data)?,
+            value: Self::read_u32(data)?,
+            offset: Self::read_i32(data)?,
+            scale: Self::read_f32(data)?,
+            nr_of_decimals: Self::read_u8(data)?,
+            format: Self::read_bool(data)?,
+            justification: Self::read_u8(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                Ok(Object::ExternalObjectDefinition(o))
-            }
-            ObjectType::ExternalReferenceName => {
-                let o = ExternalReferenceName {
-                    id,
-                    options: Self::read_u8(data)?,
-                    name: Self::read_name(data)?,
-                };
-
-                Ok(Object::ExternalReferenceName(o))
-            }
-            ObjectType::ExternalObjectPointer => {
-                let o = ExternalObjectPointer {
-                    id,
-                    default_object_id: Self::read_u16(data)?.into(),
-                    external_reference_name_id: Self::read_u16(data)?.into(),
-                    external_object_id: Self::read_u16(data)?.into(),
-                };
-
-                Ok(Object::ExternalObjectPointer(o))
-            }
-            ObjectType::Animation => {
-                let mut o = Animation {
-                    id,
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    refresh_interval: Self::read_u16(data)?,
-                    value: Self::read_u8(data)?,
-                    enabled: Self::read_bool(data)?,
-                    first_child_index: Self::read_u8(data)?,
-                    last_child_index: Self::read_u8(data)?,
-                    default_child_index: Self::read_u8(data)?,
-                    options: Self::read_u8(data)?,
-                    object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.object_refs
-                    .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::Animation(o))
-            }
-            ObjectType::ColourPalette => {
-                let mut o = ColourPalette {
-                    id,
-                    options: Self::read_u16(data)?,
-                    colours: Vec::with_capacity(Self::read_u16(data)?.into()),
-                };
+    fn read_output_line(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<OutputLine, ParseError> {
+        let mut o = OutputLine {
+            id,
+            line_attributes: Self::read_object_id(data)?,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            line_direction: Self::read_u8(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                o.colours
-                    .extend(Self::read_colours(data, o.colours.capacity())?);
+    fn read_output_rectangle(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<OutputRectangle, ParseError> {
+        let mut o = OutputRectangle {
+            id,
+            line_attributes: Self::read_object_id(data)?,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            line_suppression: Self::read_u8(data)?,
+            fill_attributes: Self::read_object_id(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                Ok(Object::ColourPalette(o))
-            }
-            ObjectType::GraphicData => {
-                let mut o = GraphicData {
-                    id,
-                    format: Self::read_u8(data)?,
-                    data: Vec::with_capacity(Self::read_u32(data)?.try_into().unwrap()),
-                };
+    fn read_output_ellipse(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<OutputEllipse, ParseError> {
+        let mut o = OutputEllipse {
+            id,
+            line_attributes: Self::read_object_id(data)?,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            ellipse_type: Self::read_u8(data)?,
+            start_angle: Self::read_u8(data)?,
+            end_angle: Self::read_u8(data)?,
+            fill_attributes: Self::read_object_id(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                o.data.extend(Self::read_bytes(data, o.data.capacity())?);
+    fn read_output_polygon(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<OutputPolygon, ParseError> {
+        let mut o = OutputPolygon {
+            id,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            line_attributes: Self::read_object_id(data)?,
+            fill_attributes: Self::read_object_id(data)?,
+            polygon_type: Self::read_u8(data)?,
+            points: Vec::with_capacity(Self::read_u8(data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.points
+            .extend(Self::read_points(data, o.points.capacity())?);
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
 
-                Ok(Object::GraphicData(o))
-            }
-            ObjectType::WorkingSetSpecialControls => {
-                let mut o = WorkingSetSpecialControls {
-                    id,
-                    id_of_colour_map: Self::read_u16(data)?.into(),
-                    id_of_colour_palette: Self::read_u16(data)?.into(),
-                    language_pairs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.language_pairs.extend(Self::read_language_pairs(
-                    data,
-                    o.language_pairs.capacity(),
-                )?);
-
-                Ok(Object::WorkingSetSpecialControls(o))
-            }
-            ObjectType::ScalesGraphic => {
-                let mut o = ScalesGraphic {
-                    id,
-                    width: Self::read_u16(data)?,
-                    height: Self::read_u16(data)?,
-                    scale_type: Self::read_u8(data)?,
-                    options: Self::read_u8(data)?,
-                    value: Self::read_u16(data)?,
-                    macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
-                };
-
-                o.macro_refs
-                    .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
-
-                Ok(Object::ScalesGraphic(o))
-            }
-        }
+    fn read_output_meter(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<OutputMeter, ParseError> {
+        let mut o = OutputMeter {
+            id,
+            width: Self::read_u16(data)?,
+            needle_colour: Self::read_u8(data)?,
+            border_colour: Self::read_u8(data)?,
+            arc_and_tick_colour: Self::read_u8(data)?,
+            options: Self::read_u8(data)?,
+            nr_of_ticks: Self::read_u8(data)?,
+            start_angle: Self::read_u8(data)?,
+            end_angle: Self::read_u8(data)?,
+            min_value: Self::read_u16(data)?,
+            max_value: Self::read_u16(data)?,
+            variable_reference: Self::read_object_id(data)?,
+            value: Self::read_u16(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_output_linear_bar_graph(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<OutputLinearBarGraph, ParseError> {
+        let mut o = OutputLinearBarGraph {
+            id,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            colour: Self::read_u8(data)?,
+            target_line_colour: Self::read_u8(data)?,
+            options: Self::read_u8(data)?,
+            nr_of_ticks: Self::read_u8(data)?,
+            min_value: Self::read_u16(data)?,
+            max_value: Self::read_u16(data)?,
+            variable_reference: Self::read_object_id(data)?,
+            value: Self::read_u16(data)?,
+            target_value_variable_reference: Self::read_object_id(data)?,
+            target_value: Self::read_u16(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_output_arched_bar_graph(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<OutputArchedBarGraph, ParseError> {
+        let mut o = OutputArchedBarGraph {
+            id,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            colour: Self::read_u8(data)?,
+            target_line_colour: Self::read_u8(data)?,
+            options: Self::read_u8(data)?,
+            start_angle: Self::read_u8(data)?,
+            end_angle: Self::read_u8(data)?,
+            bar_graph_width: Self::read_u16(data)?,
+            min_value: Self::read_u16(data)?,
+            max_value: Self::read_u16(data)?,
+            variable_reference: Self::read_object_id(data)?,
+            value: Self::read_u16(data)?,
+            target_value_variable_reference: Self::read_object_id(data)?,
+            target_value: Self::read_u16(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_picture_graphic(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<PictureGraphic, ParseError> {
+        let mut o = PictureGraphic {
+            id,
+            width: Self::read_u16(data)?,
+            actual_width: Self::read_u16(data)?,
+            actual_height: Self::read_u16(data)?,
+            format: Self::read_u8(data)?,
+            options: Self::read_u8(data)?,
+            transparency_colour: Self::read_u8(data)?,
+            data: Vec::new(),
+            macro_refs: Vec::new(),
+        };
+
+        let len = Self::read_length_u32(data)?;
+        let nr_of_macros = Self::read_u8(data)?.into();
+        o.data = Self::read_bytes(data, len)?;
+        o.macro_refs = Self::read_macro_refs(data, nr_of_macros)?;
+
+        Ok(o)
+    }
+
+    fn read_number_variable(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<NumberVariable, ParseError> {
+        let o = NumberVariable {
+            id,
+            value: Self::read_u32(data)?,
+        };
+
+        Ok(o)
+    }
+
+    fn read_string_variable(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<StringVariable, ParseError> {
+        let o = StringVariable {
+            id,
+            value: Self::read_string_u16(data)?,
+        };
+
+        Ok(o)
+    }
+
+    fn read_font_attributes(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<FontAttributes, ParseError> {
+        let mut o = FontAttributes {
+            id,
+            font_colour: Self::read_u8(data)?,
+            font_size: Self::read_u8(data)?,
+            font_type: Self::read_u8(data)?,
+            font_style: Self::read_u8(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_line_attributes(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<LineAttributes, ParseError> {
+        let mut o = LineAttributes {
+            id,
+            line_colour: Self::read_u8(data)?,
+            line_width: Self::read_u8(data)?,
+            line_art: Self::read_u16(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_fill_attributes(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<FillAttributes, ParseError> {
+        let mut o = FillAttributes {
+            id,
+            fill_type: Self::read_u8(data)?,
+            fill_colour: Self::read_u8(data)?,
+            fill_pattern: Self::read_object_id(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_input_attributes(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<InputAttributes, ParseError> {
+        let mut o = InputAttributes {
+            id,
+            validation_type: Self::read_u8(data)?,
+            validation_string: Self::read_string_u8(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_object_pointer(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<ObjectPointer, ParseError> {
+        let o = ObjectPointer {
+            id,
+            value: Self::read_object_id(data)?,
+        };
+
+        Ok(o)
+    }
+
+    fn read_macro(id: ObjectId, data: &mut dyn Iterator<Item = u8>) -> Result<Macro, ParseError> {
+        let mut o = Macro {
+            id,
+            commands: Vec::with_capacity(Self::read_u16(data)?.into()),
+        };
+
+        o.commands
+            .extend(Self::read_bytes(data, o.commands.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_auxiliary_function_type1(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<AuxiliaryFunctionType1, ParseError> {
+        let mut o = AuxiliaryFunctionType1 {
+            id,
+            background_colour: Self::read_u8(data)?,
+            function_type: Self::read_u8(data)?,
+            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.object_refs
+            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_auxiliary_input_type1(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<AuxiliaryInputType1, ParseError> {
+        let mut o = AuxiliaryInputType1 {
+            id,
+            background_colour: Self::read_u8(data)?,
+            function_type: Self::read_u8(data)?,
+            input_id: Self::read_u8(data)?,
+            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.object_refs
+            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_auxiliary_function_type2(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<AuxiliaryFunctionType2, ParseError> {
+        let mut o = AuxiliaryFunctionType2 {
+            id,
+            background_colour: Self::read_u8(data)?,
+            function_attributes: Self::read_u8(data)?,
+            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.object_refs
+            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_auxiliary_input_type2(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<AuxiliaryInputType2, ParseError> {
+        let mut o = AuxiliaryInputType2 {
+            id,
+            background_colour: Self::read_u8(data)?,
+            function_attributes: Self::read_u8(data)?,
+            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.object_refs
+            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_auxiliary_control_designator_type2(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<AuxiliaryControlDesignatorType2, ParseError> {
+        let o = AuxiliaryControlDesignatorType2 {
+            id,
+            pointer_type: Self::read_u8(data)?,
+            auxiliary_object_id: Self::read_object_id(data)?,
+        };
+
+        Ok(o)
+    }
+
+    fn read_window_mask(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<WindowMask, ParseError> {
+        let mut o = WindowMask {
+            id,
+            width: Self::read_u8(data)?,
+            height: Self::read_u8(data)?,
+            window_type: Self::read_u8(data)?,
+            background_colour: Self::read_u8(data)?,
+            options: Self::read_u8(data)?,
+            name: Self::read_object_id(data)?,
+            window_title: Self::read_object_id(data)?,
+            window_icon: Self::read_object_id(data)?,
+            objects: Vec::with_capacity(Self::read_u8(data)?.into()),
+            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.objects
+            .extend(Self::read_objects(data, o.objects.capacity())?);
+        o.object_refs
+            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_key_group(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<KeyGroup, ParseError> {
+        let mut o = KeyGroup {
+            id,
+            options: Self::read_u8(data)?,
+            name: Self::read_object_id(data)?,
+            key_group_icon: Self::read_object_id(data)?,
+            objects: Vec::with_capacity(Self::read_u8(data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.objects
+            .extend(Self::read_objects(data, o.objects.capacity())?);
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_graphics_context(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<GraphicsContext, ParseError> {
+        let o = GraphicsContext {
+            id,
+            viewport_width: Self::read_u16(data)?,
+            viewport_height: Self::read_u16(data)?,
+            viewport_x: Self::read_i16(data)?,
+            viewport_y: Self::read_i16(data)?,
+            canvas_width: Self::read_u16(data)?,
+            canvas_height: Self::read_u16(data)?,
+            viewport_zoom: Self::read_f32(data)?,
+            graphics_cursor_x: Self::read_i16(data)?,
+            graphics_cursor_y: Self::read_i16(data)?,
+            foreground_colour: Self::read_u8(data)?,
+            background_colour: Self::read_u8(data)?,
+            font_attributes_object: Self::read_object_id(data)?,
+            line_attributes_object: Self::read_object_id(data)?,
+            fill_attributes_object: Self::read_object_id(data)?,
+            format: Self::read_u8(data)?,
+            options: Self::read_u8(data)?,
+            transparency_colour: Self::read_u8(data)?,
+        };
+
+        Ok(o)
+    }
+
+    fn read_output_list(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<OutputList, ParseError> {
+        let mut o = OutputList {
+            id,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            variable_reference: Self::read_object_id(data)?,
+            value: Self::read_u8(data)?,
+            list_items: Vec::with_capacity(Self::read_u8(data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.list_items
+            .extend(Self::read_objects(data, o.list_items.capacity())?);
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_extended_input_attributes(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<ExtendedInputAttributes, ParseError> {
+        let mut o = ExtendedInputAttributes {
+            id,
+            validation_type: Self::read_u8(data)?,
+            code_planes: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.code_planes
+            .extend(Self::read_code_planes(data, o.code_planes.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_colour_map(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<ColourMap, ParseError> {
+        let mut o = ColourMap {
+            id,
+            colour_map: Vec::with_capacity(Self::read_u16(data)?.into()),
+        };
+
+        o.colour_map
+            .extend(Self::read_bytes(data, o.colour_map.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_object_label_reference_list(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<ObjectLabelReferenceList, ParseError> {
+        let mut o = ObjectLabelReferenceList {
+            id,
+            object_labels: Vec::with_capacity(Self::read_u16(data)?.into()),
+        };
+
+        o.object_labels
+            .extend(Self::read_object_labels(data, o.object_labels.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_external_object_definition(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<ExternalObjectDefinition, ParseError> {
+        let mut o = ExternalObjectDefinition {
+            id,
+            options: Self::read_u8(data)?,
+            name: Self::read_name(data)?,
+            objects: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.objects
+            .extend(Self::read_objects(data, o.objects.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_external_reference_name(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<ExternalReferenceName, ParseError> {
+        let o = ExternalReferenceName {
+            id,
+            options: Self::read_u8(data)?,
+            name: Self::read_name(data)?,
+        };
+
+        Ok(o)
+    }
+
+    fn read_external_object_pointer(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<ExternalObjectPointer, ParseError> {
+        let o = ExternalObjectPointer {
+            id,
+            default_object_id: Self::read_object_id(data)?,
+            external_reference_name_id: Self::read_object_id(data)?,
+            external_object_id: Self::read_object_id(data)?,
+        };
+
+        Ok(o)
+    }
+
+    fn read_animation(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<Animation, ParseError> {
+        let mut o = Animation {
+            id,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            refresh_interval: Self::read_u16(data)?,
+            value: Self::read_u8(data)?,
+            enabled: Self::read_bool(data)?,
+            first_child_index: Self::read_u8(data)?,
+            last_child_index: Self::read_u8(data)?,
+            default_child_index: Self::read_u8(data)?,
+            options: Self::read_u8(data)?,
+            object_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.object_refs
+            .extend(Self::read_object_refs(data, o.object_refs.capacity())?);
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_colour_palette(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<ColourPalette, ParseError> {
+        let mut o = ColourPalette {
+            id,
+            options: Self::read_u16(data)?,
+            colours: Vec::with_capacity(Self::read_u16(data)?.into()),
+        };
+
+        o.colours
+            .extend(Self::read_colours(data, o.colours.capacity())?);
+
+        Ok(o)
+    }
+
+    fn read_graphic_data(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<GraphicData, ParseError> {
+        let mut o = GraphicData {
+            id,
+            format: Self::read_u8(data)?,
+            data: Vec::new(),
+        };
+
+        let len = Self::read_length_u32(data)?;
+        o.data = Self::read_bytes(data, len)?;
+
+        Ok(o)
+    }
+
+    fn read_working_set_special_controls(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<WorkingSetSpecialControls, ParseError> {
+        let mut o = WorkingSetSpecialControls {
+            id,
+            id_of_colour_map: Self::read_object_id(data)?,
+            id_of_colour_palette: Self::read_object_id(data)?,
+            language_pairs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.language_pairs.extend(Self::read_language_pairs(
+            data,
+            o.language_pairs.capacity(),
+        )?);
+
+        Ok(o)
+    }
+
+    fn read_scales_graphic(
+        id: ObjectId,
+        data: &mut dyn Iterator<Item = u8>,
+    ) -> Result<ScalesGraphic, ParseError> {
+        let mut o = ScalesGraphic {
+            id,
+            width: Self::read_u16(data)?,
+            height: Self::read_u16(data)?,
+            scale_type: Self::read_u8(data)?,
+            options: Self::read_u8(data)?,
+            value: Self::read_u16(data)?,
+            macro_refs: Vec::with_capacity(Self::read_u8(data)?.into()),
+        };
+
+        o.macro_refs
+            .extend(Self::read_macro_refs(data, o.macro_refs.capacity())?);
+
+        Ok(o)
     }
 
     fn read_objects(
@@ -799,7 +1102,7 @@ impl Object {
     ) -> Result<Vec<ObjectId>, ParseError> {
         let mut objs = Vec::new();
         for _ in 0..nr_of_objects {
-            objs.push(Self::read_u16(data)?.into());
+            objs.push(Self::read_object_id(data)?);
         }
         Ok(objs)
     }
@@ -810,7 +1113,7 @@ impl Object {
         let mut refs = Vec::new();
         for _ in 0..nr_of_objects {
             refs.push(ObjectRef {
-                id: Self::read_u16(data)?.into(),
+                id: Self::read_object_id(data)?,
                 offset: Point {
                     x: Self::read_i16(data)?,
                     y: Self::read_i16(data)?,
@@ -848,10 +1151,15 @@ impl Object {
     ) -> Result<Vec<Point<u16>>, ParseError> {
         let mut objs = Vec::new();
         for _ in 0..nr_of_points {
-            objs.push(Point {
-                x: Self::read_u16(data)?,
-                y: Self::read_u16(data)?,
-            })
+            match (Self::read_u16(data), Self::read_u16(data)) {
+                (Ok(x), Ok(y)) => objs.push(Point { x, y }),
+                _ => {
+                    return Err(ParseError::PointCountMismatch {
+                        declared: nr_of_points,
+                        found: objs.len(),
+                    })
+                }
+            }
         }
         Ok(objs)
     }
@@ -877,10 +1185,10 @@ impl Object {
         let mut objs = Vec::new();
         for _ in 0..nr_of_objects {
             objs.push(ObjectLabel {
-                id: Self::read_u16(data)?.into(),
-                string_variable_reference: Self::read_u16(data)?.into(),
+                id: Self::read_object_id(data)?,
+                string_variable_reference: Self::read_object_id(data)?,
                 font_type: Self::read_u8(data)?,
-                graphic_representation: Self::read_u16(data)?.into(),
+                graphic_representation: Self::read_object_id(data)?,
             })
         }
         Ok(objs)
@@ -896,6 +1204,26 @@ impl Object {
         Ok(objs)
     }
 
+    fn read_code_planes(
+        data: &mut dyn Iterator<Item = u8>,
+        nr_of_objects: usize,
+    ) -> Result<Vec<CodePlane>, ParseError> {
+        let mut objs = Vec::new();
+        for _ in 0..nr_of_objects {
+            let number = Self::read_u8(data)?;
+            let nr_of_ranges = Self::read_u8(data)?;
+            let mut character_ranges = Vec::new();
+            for _ in 0..nr_of_ranges {
+                character_ranges.push(Self::read_u16(data)?..=Self::read_u16(data)?);
+            }
+            objs.push(CodePlane {
+                number,
+                character_ranges,
+            })
+        }
+        Ok(objs)
+    }
+
     fn read_bool(data: &mut dyn Iterator<Item = u8>) -> Result<bool, ParseError> {
         match data.next() {
             Some(d) => Ok(d != 0),
@@ -918,6 +1246,12 @@ impl Object {
 
         Ok(u16::from_le_bytes([a.unwrap(), b.unwrap()]))
     }
+    fn read_object_id(data: &mut dyn Iterator<Item = u8>) -> Result<ObjectId, ParseError> {
+        match (data.next(), data.next()) {
+            (Some(a), Some(b)) => Ok(ObjectId::from_le_bytes([a, b])),
+            _ => Err(ParseError::DataEmpty),
+        }
+    }
     fn read_i16(data: &mut dyn Iterator<Item = u8>) -> Result<i16, ParseError> {
         let a: Option<u8> = data.next();
         let b: Option<u8> = data.next();
@@ -979,17 +1313,42 @@ impl Object {
             d.unwrap(),
         ]))
     }
+    /// Read the 32 bit length of a byte array.
+    ///
+    /// Lengths beyond the remaining data are rejected when the iterator knows how much is left.
+    /// Callers must not reserve memory for the length up front, a corrupt length could ask for
+    /// gigabytes.
+    fn read_length_u32(data: &mut dyn Iterator<Item = u8>) -> Result<usize, ParseError> {
+        let len = usize::try_from(Self::read_u32(data)?).map_err(|_| ParseError::DataEmpty)?;
+        match data.size_hint() {
+            (_, Some(remaining)) if remaining < len => Err(ParseError::DataEmpty),
+            _ => Ok(len),
+        }
+    }
+    /// Read a string of `len` characters.
+    ///
+    /// Padding spaces and NUL characters are kept, so the string is written back unchanged.
     fn read_string(len: usize, data: &mut dyn Iterator<Item = u8>) -> Result<String, ParseError> {
         let mut s = String::new();
         for _ in 0..len {
             if let Some(c) = data.next() {
-                s.push(c as char);
+                s.push(CharacterSet::Latin1.decode_byte(c));
             } else {
                 return Err(ParseError::DataEmpty);
             };
         }
         Ok(s)
     }
+    /// Read a string after a one byte length.
+    fn read_string_u8(data: &mut dyn Iterator<Item = u8>) -> Result<String, ParseError> {
+        let len = Self::read_u8(data)?;
+        Self::read_string(len.into(), data)
+    }
+    /// Read a string after a two byte length.
+    fn read_string_u16(data: &mut dyn Iterator<Item = u8>) -> Result<String, ParseError> {
+        let len = Self::read_u16(data)?;
+        Self::read_string(len.into(), data)
+    }
     fn read_name(data: &mut dyn Iterator<Item = u8>) -> Result<Name, ParseError> {
         let name: [Option<u8>; 8] = [
             data.next(),
@@ -1006,6 +1365,382 @@ impl Object {
             return Err(ParseError::DataEmpty);
         }
 
-        Ok(Name::from(u64::from_le_bytes(name.map(|v| v.unwrap()))))
+        Ok(Name::new(u64::from_le_bytes(name.map(|v| v.unwrap()))))
+    }
+}
+
+impl Object {
+    /// Get the id, type and length in bytes of the first object in `data` without decoding it.
+    ///
+    /// The length includes the id and type, and follows the same layout as [`Object::read`].
+    pub(super) fn read_header(data: &[u8]) -> Result<(ObjectId, ObjectType, usize), ParseError> {
+        let mut s = Skipper { data, pos: 0 };
+        let id = ObjectId::from_le_bytes(s.bytes()?);
+        let object_type = ObjectType::try_from(s.bytes::<1>()?[0])?;
+
+        match object_type {
+            ObjectType::WorkingSet => {
+                s.skip(4)?;
+                let (refs, macros, languages) = (s.u8()?, s.u8()?, s.u8()?);
+                s.skip(refs * 6 + macros * 2 + languages * 2)?;
+            }
+            ObjectType::DataMask => s.refs_and_macros(3)?,
+            ObjectType::AlarmMask => s.refs_and_macros(5)?,
+            ObjectType::Container => s.refs_and_macros(5)?,
+            ObjectType::SoftKeyMask => s.objects_and_macros(1)?,
+            ObjectType::Key => s.refs_and_macros(2)?,
+            ObjectType::Button => s.refs_and_macros(8)?,
+            ObjectType::InputBoolean => s.macros(9)?,
+            ObjectType::InputString => {
+                s.skip(13)?;
+                let len = s.u8()?;
+                s.skip(len + 1)?;
+                s.macros(0)?;
+            }
+            ObjectType::InputNumber => s.macros(34)?,
+            ObjectType::InputList => {
+                s.skip(7)?;
+                let items = s.u8()?;
+                s.skip(1)?;
+                let macros = s.u8()?;
+                s.skip(items * 2 + macros * 2)?;
+            }
+            ObjectType::OutputString => {
+                s.skip(11)?;
+                let len = s.u16()?;
+                s.skip(len)?;
+                s.macros(0)?;
+            }
+            ObjectType::OutputNumber => s.macros(25)?,
+            ObjectType::OutputLine => s.macros(7)?,
+            ObjectType::OutputRectangle => s.macros(9)?,
+            ObjectType::OutputEllipse => s.macros(11)?,
+            ObjectType::OutputPolygon => {
+                s.skip(9)?;
+                let (points, macros) = (s.u8()?, s.u8()?);
+                s.skip(points * 4 + macros * 2)?;
+            }
+            ObjectType::OutputMeter => s.macros(17)?,
+            ObjectType::OutputLinearBarGraph => s.macros(20)?,
+            ObjectType::OutputArchedBarGraph => s.macros(23)?,
+            ObjectType::PictureGraphic => {
+                s.skip(9)?;
+                let (len, macros) = (s.u32()?, s.u8()?);
+                s.skip(len)?;
+                s.skip(macros * 2)?;
+            }
+            ObjectType::NumberVariable => s.skip(4)?,
+            ObjectType::StringVariable => {
+                let len = s.u16()?;
+                s.skip(len)?;
+            }
+            ObjectType::FontAttributes => s.macros(4)?,
+            ObjectType::LineAttributes => s.macros(4)?,
+            ObjectType::FillAttributes => s.macros(4)?,
+            ObjectType::InputAttributes => {
+                s.skip(1)?;
+                let len = s.u8()?;
+                s.skip(len)?;
+                s.macros(0)?;
+            }
+            ObjectType::ObjectPointer => s.skip(2)?,
+            ObjectType::Macro => {
+                let len = s.u16()?;
+                s.skip(len)?;
+            }
+            ObjectType::AuxiliaryFunctionType1 => s.refs(2)?,
+            ObjectType::AuxiliaryInputType1 => s.refs(3)?,
+            ObjectType::AuxiliaryFunctionType2 => s.refs(2)?,
+            ObjectType::AuxiliaryInputType2 => s.refs(2)?,
+            ObjectType::AuxiliaryControlDesignatorType2 => s.skip(3)?,
+            ObjectType::WindowMask => {
+                s.skip(11)?;
+                let (objects, refs, macros) = (s.u8()?, s.u8()?, s.u8()?);
+                s.skip(objects * 2 + refs * 6 + macros * 2)?;
+            }
+            ObjectType::KeyGroup => s.objects_and_macros(5)?,
+            ObjectType::GraphicsContext => s.skip(31)?,
+            ObjectType::OutputList => s.objects_and_macros(7)?,
+            ObjectType::ExtendedInputAttributes => {
+                s.skip(1)?;
+                for _ in 0..s.u8()? {
+                    s.skip(1)?;
+                    let ranges = s.u8()?;
+                    s.skip(ranges * 4)?;
+                }
+            }
+            ObjectType::ColourMap => {
+                let len = s.u16()?;
+                s.skip(len)?;
+            }
+            ObjectType::ObjectLabelReferenceList => {
+                let labels = s.u16()?;
+                s.skip(labels * 7)?;
+            }
+            ObjectType::ExternalObjectDefinition => {
+                s.skip(9)?;
+                let objects = s.u8()?;
+                s.skip(objects * 2)?;
+            }
+            ObjectType::ExternalReferenceName => s.skip(9)?,
+            ObjectType::ExternalObjectPointer => s.skip(6)?,
+            ObjectType::Animation => s.refs_and_macros(12)?,
+            ObjectType::ColourPalette => {
+                s.skip(2)?;
+                let colours = s.u16()?;
+                s.skip(colours * 4)?;
+            }
+            ObjectType::GraphicData => {
+                s.skip(1)?;
+                let len = s.u32()?;
+                s.skip(len)?;
+            }
+            ObjectType::WorkingSetSpecialControls => {
+                s.skip(4)?;
+                let pairs = s.u8()?;
+                s.skip(pairs * 4)?;
+            }
+            ObjectType::ScalesGraphic => s.macros(8)?,
+        }
+
+        Ok((id, object_type, s.pos))
+    }
+}
+
+/// Cursor that skips over the fields of a serialized object.
+struct Skipper<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Skipper<'_> {
+    fn skip(&mut self, len: usize) -> Result<(), ParseError> {
+        self.pos = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or(ParseError::DataEmpty)?;
+        Ok(())
+    }
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], ParseError> {
+        let start = self.pos;
+        self.skip(N)?;
+        Ok(self.data[start..self.pos].try_into().unwrap())
+    }
+    fn u8(&mut self) -> Result<usize, ParseError> {
+        Ok(self.bytes::<1>()?[0].into())
+    }
+    fn u16(&mut self) -> Result<usize, ParseError> {
+        Ok(u16::from_le_bytes(self.bytes()?).into())
+    }
+    fn u32(&mut self) -> Result<usize, ParseError> {
+        usize::try_from(u32::from_le_bytes(self.bytes()?)).map_err(|_| ParseError::DataEmpty)
+    }
+
+    /// Skip fixed fields followed by a list of macro references.
+    fn macros(&mut self, fixed: usize) -> Result<(), ParseError> {
+        self.skip(fixed)?;
+        let macros = self.u8()?;
+        self.skip(macros * 2)
+    }
+    /// Skip fixed fields followed by a list of object references.
+    fn refs(&mut self, fixed: usize) -> Result<(), ParseError> {
+        self.skip(fixed)?;
+        let refs = self.u8()?;
+        self.skip(refs * 6)
+    }
+    /// Skip fixed fields followed by lists of object references and macro references.
+    fn refs_and_macros(&mut self, fixed: usize) -> Result<(), ParseError> {
+        self.skip(fixed)?;
+        let (refs, macros) = (self.u8()?, self.u8()?);
+        self.skip(refs * 6 + macros * 2)
+    }
+    /// Skip fixed fields followed by lists of object ids and macro references.
+    fn objects_and_macros(&mut self, fixed: usize) -> Result<(), ParseError> {
+        self.skip(fixed)?;
+        let (objects, macros) = (self.u8()?, self.u8()?);
+        self.skip(objects * 2 + macros * 2)
+    }
+}
+
+/// Parse a single object of a known type.
+///
+/// The data starts with the object id and type, like in a serialized pool. Bytes after the
+/// object are ignored.
+macro_rules! impl_try_from_bytes {
+    ($($object:ident => $read:ident),* $(,)?) => {
+        $(
+            impl TryFrom<&[u8]> for $object {
+                type Error = ParseError;
+
+                fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+                    let mut data = value.iter().copied();
+                    let id = Object::read_object_id(&mut data)?;
+                    let object_type: ObjectType = Object::read_u8(&mut data)?.try_into()?;
+                    if object_type != ObjectType::$object {
+                        return Err(ParseError::UnexpectedObjectType(object_type));
+                    }
+                    Object::$read(id, &mut data)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_bytes! {
+    WorkingSet => read_working_set,
+    DataMask => read_data_mask,
+    AlarmMask => read_alarm_mask,
+    Container => read_container,
+    SoftKeyMask => read_soft_key_mask,
+    Key => read_key,
+    Button => read_button,
+    InputBoolean => read_input_boolean,
+    InputString => read_input_string,
+    InputNumber => read_input_number,
+    InputList => read_input_list,
+    OutputString => read_output_string,
+    OutputNumber => read_output_number,
+    OutputLine => read_output_line,
+    OutputRectangle => read_output_rectangle,
+    OutputEllipse => read_output_ellipse,
+    OutputPolygon => read_output_polygon,
+    OutputMeter => read_output_meter,
+    OutputLinearBarGraph => read_output_linear_bar_graph,
+    OutputArchedBarGraph => read_output_arched_bar_graph,
+    PictureGraphic => read_picture_graphic,
+    NumberVariable => read_number_variable,
+    StringVariable => read_string_variable,
+    FontAttributes => read_font_attributes,
+    LineAttributes => read_line_attributes,
+    FillAttributes => read_fill_attributes,
+    InputAttributes => read_input_attributes,
+    ObjectPointer => read_object_pointer,
+    Macro => read_macro,
+    AuxiliaryFunctionType1 => read_auxiliary_function_type1,
+    AuxiliaryInputType1 => read_auxiliary_input_type1,
+    AuxiliaryFunctionType2 => read_auxiliary_function_type2,
+    AuxiliaryInputType2 => read_auxiliary_input_type2,
+    AuxiliaryControlDesignatorType2 => read_auxiliary_control_designator_type2,
+    WindowMask => read_window_mask,
+    KeyGroup => read_key_group,
+    GraphicsContext => read_graphics_context,
+    OutputList => read_output_list,
+    ExtendedInputAttributes => read_extended_input_attributes,
+    ColourMap => read_colour_map,
+    ObjectLabelReferenceList => read_object_label_reference_list,
+    ExternalObjectDefinition => read_external_object_definition,
+    ExternalReferenceName => read_external_reference_name,
+    ExternalObjectPointer => read_external_object_pointer,
+    Animation => read_animation,
+    ColourPalette => read_colour_palette,
+    GraphicData => read_graphic_data,
+    WorkingSetSpecialControls => read_working_set_special_controls,
+    ScalesGraphic => read_scales_graphic,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_bytes() {
+        let data: &[u8] = &[
+            0xE8, 0x03, 1, // DataMask 1000
+            12, 0xFF, 0xFF, // background colour, no soft key mask
+            1, 0, // one object ref, no macros
+            0xE0, 0x2E, 10, 0, 20, 0, // object 12000 at (10, 20)
+        ];
+
+        let data_mask = DataMask::try_from(data).unwrap();
+        assert_eq!(ObjectId::from(1000), data_mask.id);
+        assert_eq!(12, data_mask.background_colour);
+        assert_eq!(ObjectId::NULL, data_mask.soft_key_mask);
+        assert_eq!(ObjectId::from(12000), data_mask.object_refs[0].id);
+        assert_eq!(Point { x: 10, y: 20 }, data_mask.object_refs[0].offset);
+
+        assert_eq!(
+            Err(ParseError::UnexpectedObjectType(ObjectType::DataMask)),
+            AlarmMask::try_from(data)
+        );
+        assert_eq!(Err(ParseError::DataEmpty), DataMask::try_from(&data[..10]));
+        assert_eq!(Err(ParseError::DataEmpty), DataMask::try_from(&[][..]));
+    }
+
+    #[test]
+    fn test_polygon_point_count_mismatch() {
+        let data: &[u8] = &[
+            0x90, 0x65, 16, // OutputPolygon 26000
+            100, 0, 50, 0, // width, height
+            0xFF, 0xFF, 0xFF, 0xFF, // no line or fill attributes
+            0, 4, 0, // polygon type, 4 points, no macros
+            0, 0, 0, 0, // (0, 0)
+            100, 0, 0, 0, // (100, 0)
+            50, 0, 50, 0, // (50, 50)
+        ];
+        assert_eq!(
+            Err(ParseError::PointCountMismatch {
+                declared: 4,
+                found: 3
+            }),
+            OutputPolygon::try_from(data)
+        );
+
+        let mut data = data.to_vec();
+        data[12] = 3;
+        let polygon = OutputPolygon::try_from(&data[..]).unwrap();
+        assert_eq!(3, polygon.point_count());
+        assert_eq!(Ok(()), polygon.validate());
+        assert_eq!(data, polygon.to_bytes());
+    }
+
+    #[test]
+    fn test_huge_length_is_rejected() {
+        let data: &[u8] = &[
+            0x10, 0x27, 20, // PictureGraphic 10000
+            100, 0, 100, 0, 100, 0, // width, actual width and height
+            2, 0, 0, // format, options, transparency colour
+            0xFF, 0xFF, 0xFF, 0xFF, // 4GB of data
+            0, 1, 2, 3, 4, 5, 6, 7,
+        ];
+        assert_eq!(Err(ParseError::DataEmpty), PictureGraphic::try_from(data));
+
+        // Without a known length the data is read until it runs out
+        let mut iter = data.iter().copied();
+        let mut unknown_length = core::iter::from_fn(|| iter.next());
+        assert_eq!(
+            Err(ParseError::DataEmpty),
+            Object::read(&mut unknown_length).map(|_| ())
+        );
+
+        let data: &[u8] = &[
+            0x10, 0xB4, 46, // GraphicData 46096
+            0,  // PNG
+            0xFF, 0xFF, 0xFF, 0x7F, 1, 2, 3,
+        ];
+        assert_eq!(Err(ParseError::DataEmpty), GraphicData::try_from(data));
+    }
+
+    #[test]
+    fn test_read_header_matches_read() {
+        for &object_type in ObjectType::ALL {
+            let mut data = vec![0xE8, 0x03, object_type.discriminant()];
+            data.resize(256, 0);
+
+            let mut iter = data.iter().copied();
+            Object::read(&mut iter).unwrap();
+            let len = data.len() - iter.count();
+
+            assert_eq!(
+                Ok((ObjectId::from(1000), object_type, len)),
+                Object::read_header(&data),
+                "{:?}",
+                object_type
+            );
+        }
+        assert_eq!(
+            Err(ParseError::DataEmpty),
+            Object::read_header(&[0xE8, 0x03, 1])
+        );
     }
 }
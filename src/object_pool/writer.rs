@@ -3,646 +3,770 @@ use super::*;
 impl Object {
     pub fn write(&self) -> Vec<u8> {
         let mut data = Vec::new();
+        self.write_into(&mut data);
+        data
+    }
 
+    /// Append the serialized object to `data`, to reuse one buffer for many objects.
+    pub fn write_into(&self, data: &mut Vec<u8>) {
         match self {
-            Object::WorkingSet(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::WorkingSet);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u8(&mut data, o.selectable);
-                Self::write_u16(&mut data, o.active_mask);
-                Self::write_u8(&mut data, o.object_refs.len() as u8);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-                Self::write_u8(&mut data, o.language_codes.len() as u8);
-
-                Self::write_object_refs(&mut data, &o.object_refs);
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-                Self::write_language_codes(&mut data, &o.language_codes);
-            }
-            Object::DataMask(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::DataMask);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u16(&mut data, o.soft_key_mask);
-                Self::write_u8(&mut data, o.object_refs.len() as u8);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_object_refs(&mut data, &o.object_refs);
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::AlarmMask(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::AlarmMask);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u16(&mut data, o.soft_key_mask);
-                Self::write_u8(&mut data, o.priority);
-                Self::write_u8(&mut data, o.acoustic_signal);
-                Self::write_u8(&mut data, o.object_refs.len() as u8);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_object_refs(&mut data, &o.object_refs);
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::Container(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::Container);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u8(&mut data, o.hidden);
-                Self::write_u8(&mut data, o.object_refs.len() as u8);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_object_refs(&mut data, &o.object_refs);
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::SoftKeyMask(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::SoftKeyMask);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u8(&mut data, o.objects.len() as u8);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_objects(&mut data, &o.objects);
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::Key(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::Key);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u8(&mut data, o.key_code);
-                Self::write_u8(&mut data, o.object_refs.len() as u8);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_object_refs(&mut data, &o.object_refs);
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::Button(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::Button);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u8(&mut data, o.border_colour);
-                Self::write_u8(&mut data, o.key_code);
-                Self::write_u8(&mut data, o.options);
-                Self::write_u8(&mut data, o.object_refs.len() as u8);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_object_refs(&mut data, &o.object_refs);
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::InputBoolean(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::InputBoolean);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.foreground_colour);
-                Self::write_u16(&mut data, o.variable_reference);
-                Self::write_u8(&mut data, o.value);
-                Self::write_u8(&mut data, o.enabled);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::InputString(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::InputString);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u16(&mut data, o.font_attributes);
-                Self::write_u16(&mut data, o.input_attributes);
-                Self::write_u8(&mut data, o.options);
-                Self::write_u16(&mut data, o.variable_reference);
-                Self::write_u8(&mut data, o.justification);
-                Self::write_string(&mut data, &o.value);
-                Self::write_u8(&mut data, o.enabled);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::InputNumber(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::InputNumber);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u16(&mut data, o.font_attributes);
-                Self::write_u8(&mut data, o.options);
-                Self::write_u16(&mut data, o.variable_reference);
-                Self::write_u32(&mut data, o.value);
-                Self::write_u32(&mut data, o.min_value);
-                Self::write_u32(&mut data, o.max_value);
-                Self::write_i32(&mut data, o.offset);
-                Self::write_f32(&mut data, o.scale);
-                Self::write_u8(&mut data, o.nr_of_decimals);
-                Self::write_u8(&mut data, o.format);
-                Self::write_u8(&mut data, o.justification);
-                Self::write_u8(&mut data, o.options2);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::InputList(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::InputList);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u16(&mut data, o.variable_reference);
-                Self::write_u8(&mut data, o.value);
-                Self::write_u8(&mut data, o.list_items.len() as u8);
-                Self::write_u8(&mut data, o.options);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_objects(&mut data, &o.list_items);
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::OutputString(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::OutputString);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u16(&mut data, o.font_attributes);
-                Self::write_u8(&mut data, o.options);
-                Self::write_u16(&mut data, o.variable_reference);
-                Self::write_u8(&mut data, o.justification);
-                Self::write_u16(&mut data, o.value.len() as u16);
-                Self::write_string(&mut data, &o.value);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::OutputNumber(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::OutputNumber);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u16(&mut data, o.font_attributes);
-                Self::write_u8(&mut data, o.options);
-                Self::write_u16(&mut data, o.variable_reference);
-                Self::write_u32(&mut data, o.value);
-                Self::write_i32(&mut data, o.offset);
-                Self::write_f32(&mut data, o.scale);
-                Self::write_u8(&mut data, o.nr_of_decimals);
-                Self::write_u8(&mut data, o.format);
-                Self::write_u8(&mut data, o.justification);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::OutputLine(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::OutputLine);
-                Self::write_u16(&mut data, o.line_attributes);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u8(&mut data, o.line_direction);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::OutputRectangle(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::OutputRectangle);
-                Self::write_u16(&mut data, o.line_attributes);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u8(&mut data, o.line_suppression);
-                Self::write_u16(&mut data, o.fill_attributes);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::OutputEllipse(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::OutputEllipse);
-                Self::write_u16(&mut data, o.line_attributes);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u8(&mut data, o.ellipse_type);
-                Self::write_u8(&mut data, o.start_angle);
-                Self::write_u8(&mut data, o.end_angle);
-                Self::write_u16(&mut data, o.fill_attributes);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::OutputPolygon(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::OutputPolygon);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u16(&mut data, o.line_attributes);
-                Self::write_u16(&mut data, o.fill_attributes);
-                Self::write_u8(&mut data, o.polygon_type);
-                Self::write_u8(&mut data, o.points.len() as u8);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_points(&mut data, &o.points);
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::OutputMeter(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::OutputMeter);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u8(&mut data, o.needle_colour);
-                Self::write_u8(&mut data, o.border_colour);
-                Self::write_u8(&mut data, o.arc_and_tick_colour);
-                Self::write_u8(&mut data, o.options);
-                Self::write_u8(&mut data, o.nr_of_ticks);
-                Self::write_u8(&mut data, o.start_angle);
-                Self::write_u8(&mut data, o.end_angle);
-                Self::write_u16(&mut data, o.min_value);
-                Self::write_u16(&mut data, o.max_value);
-                Self::write_u16(&mut data, o.variable_reference);
-                Self::write_u16(&mut data, o.value);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::OutputLinearBarGraph(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::OutputLinearBarGraph);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u8(&mut data, o.colour);
-                Self::write_u8(&mut data, o.target_line_colour);
-                Self::write_u8(&mut data, o.options);
-                Self::write_u8(&mut data, o.nr_of_ticks);
-                Self::write_u16(&mut data, o.min_value);
-                Self::write_u16(&mut data, o.max_value);
-                Self::write_u16(&mut data, o.variable_reference);
-                Self::write_u16(&mut data, o.value);
-                Self::write_u16(&mut data, o.target_value_variable_reference);
-                Self::write_u16(&mut data, o.target_value);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::OutputArchedBarGraph(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::OutputArchedBarGraph);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u8(&mut data, o.colour);
-                Self::write_u8(&mut data, o.target_line_colour);
-                Self::write_u8(&mut data, o.options);
-                Self::write_u8(&mut data, o.start_angle);
-                Self::write_u8(&mut data, o.end_angle);
-                Self::write_u16(&mut data, o.bar_graph_width);
-                Self::write_u16(&mut data, o.min_value);
-                Self::write_u16(&mut data, o.max_value);
-                Self::write_u16(&mut data, o.variable_reference);
-                Self::write_u16(&mut data, o.value);
-                Self::write_u16(&mut data, o.target_value_variable_reference);
-                Self::write_u16(&mut data, o.target_value);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::PictureGraphic(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::PictureGraphic);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.actual_width);
-                Self::write_u16(&mut data, o.actual_height);
-                Self::write_u8(&mut data, o.format);
-                Self::write_u8(&mut data, o.options);
-                Self::write_u8(&mut data, o.transparency_colour);
-                Self::write_u32(&mut data, o.data.len() as u32);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_bytes(&mut data, &o.data);
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::NumberVariable(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::NumberVariable);
-                Self::write_u32(&mut data, o.value);
-            }
-            Object::StringVariable(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::StringVariable);
-                Self::write_string(&mut data, &o.value);
-            }
-            Object::FontAttributes(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::FontAttributes);
-                Self::write_u8(&mut data, o.font_colour);
-                Self::write_u8(&mut data, o.font_size);
-                Self::write_u8(&mut data, o.font_type);
-                Self::write_u8(&mut data, o.font_style);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::LineAttributes(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::LineAttributes);
-                Self::write_u8(&mut data, o.line_colour);
-                Self::write_u8(&mut data, o.line_width);
-                Self::write_u16(&mut data, o.line_art);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::FillAttributes(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::FillAttributes);
-                Self::write_u8(&mut data, o.fill_type);
-                Self::write_u8(&mut data, o.fill_colour);
-                Self::write_u16(&mut data, o.fill_pattern);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::InputAttributes(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::InputAttributes);
-                Self::write_u8(&mut data, o.validation_type);
-                Self::write_string(&mut data, &o.validation_string);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::ObjectPointer(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::ObjectPointer);
-                Self::write_u16(&mut data, o.value);
-            }
-            Object::Macro(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::Macro);
-                Self::write_u16(&mut data, o.commands.len() as u16);
-
-                Self::write_bytes(&mut data, &o.commands);
-            }
-            Object::AuxiliaryFunctionType1(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::AuxiliaryFunctionType1);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u8(&mut data, o.function_type);
-                Self::write_u8(&mut data, o.object_refs.len() as u8);
-
-                Self::write_object_refs(&mut data, &o.object_refs);
-            }
-            Object::AuxiliaryInputType1(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::AuxiliaryInputType1);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u8(&mut data, o.function_type);
-                Self::write_u8(&mut data, o.input_id);
-                Self::write_u8(&mut data, o.object_refs.len() as u8);
-
-                Self::write_object_refs(&mut data, &o.object_refs);
-            }
-            Object::AuxiliaryFunctionType2(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::AuxiliaryFunctionType2);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u8(&mut data, o.function_attributes);
-                Self::write_u8(&mut data, o.object_refs.len() as u8);
-
-                Self::write_object_refs(&mut data, &o.object_refs);
-            }
-            Object::AuxiliaryInputType2(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::AuxiliaryInputType2);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u8(&mut data, o.function_attributes);
-                Self::write_u8(&mut data, o.object_refs.len() as u8);
-
-                Self::write_object_refs(&mut data, &o.object_refs);
-            }
+            Object::WorkingSet(o) => Self::write_working_set(data, o),
+            Object::DataMask(o) => Self::write_data_mask(data, o),
+            Object::AlarmMask(o) => Self::write_alarm_mask(data, o),
+            Object::Container(o) => Self::write_container(data, o),
+            Object::SoftKeyMask(o) => Self::write_soft_key_mask(data, o),
+            Object::Key(o) => Self::write_key(data, o),
+            Object::Button(o) => Self::write_button(data, o),
+            Object::InputBoolean(o) => Self::write_input_boolean(data, o),
+            Object::InputString(o) => Self::write_input_string(data, o),
+            Object::InputNumber(o) => Self::write_input_number(data, o),
+            Object::InputList(o) => Self::write_input_list(data, o),
+            Object::OutputString(o) => Self::write_output_string(data, o),
+            Object::OutputNumber(o) => Self::write_output_number(data, o),
+            Object::OutputLine(o) => Self::write_output_line(data, o),
+            Object::OutputRectangle(o) => Self::write_output_rectangle(data, o),
+            Object::OutputEllipse(o) => Self::write_output_ellipse(data, o),
+            Object::OutputPolygon(o) => Self::write_output_polygon(data, o),
+            Object::OutputMeter(o) => Self::write_output_meter(data, o),
+            Object::OutputLinearBarGraph(o) => Self::write_output_linear_bar_graph(data, o),
+            Object::OutputArchedBarGraph(o) => Self::write_output_arched_bar_graph(data, o),
+            Object::PictureGraphic(o) => Self::write_picture_graphic(data, o),
+            Object::NumberVariable(o) => Self::write_number_variable(data, o),
+            Object::StringVariable(o) => Self::write_string_variable(data, o),
+            Object::FontAttributes(o) => Self::write_font_attributes(data, o),
+            Object::LineAttributes(o) => Self::write_line_attributes(data, o),
+            Object::FillAttributes(o) => Self::write_fill_attributes(data, o),
+            Object::InputAttributes(o) => Self::write_input_attributes(data, o),
+            Object::ObjectPointer(o) => Self::write_object_pointer(data, o),
+            Object::Macro(o) => Self::write_macro(data, o),
+            Object::AuxiliaryFunctionType1(o) => Self::write_auxiliary_function_type1(data, o),
+            Object::AuxiliaryInputType1(o) => Self::write_auxiliary_input_type1(data, o),
+            Object::AuxiliaryFunctionType2(o) => Self::write_auxiliary_function_type2(data, o),
+            Object::AuxiliaryInputType2(o) => Self::write_auxiliary_input_type2(data, o),
             Object::AuxiliaryControlDesignatorType2(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::AuxiliaryControlDesignatorType2);
-                Self::write_u8(&mut data, o.pointer_type);
-                Self::write_u16(&mut data, o.auxiliary_object_id);
-            }
-            Object::WindowMask(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::WindowMask);
-                Self::write_u8(&mut data, o.width);
-                Self::write_u8(&mut data, o.height);
-                Self::write_u8(&mut data, o.window_type);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u8(&mut data, o.options);
-                Self::write_u16(&mut data, o.name);
-                Self::write_u16(&mut data, o.window_title);
-                Self::write_u16(&mut data, o.window_icon);
-                Self::write_u8(&mut data, o.objects.len() as u8);
-                Self::write_u8(&mut data, o.object_refs.len() as u8);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_objects(&mut data, &o.objects);
-                Self::write_object_refs(&mut data, &o.object_refs);
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::KeyGroup(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::KeyGroup);
-                Self::write_u8(&mut data, o.options);
-                Self::write_u16(&mut data, o.name);
-                Self::write_u16(&mut data, o.key_group_icon);
-                Self::write_u8(&mut data, o.objects.len() as u8);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_objects(&mut data, &o.objects);
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::GraphicsContext(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::GraphicsContext);
-                Self::write_u16(&mut data, o.viewport_width);
-                Self::write_u16(&mut data, o.viewport_height);
-                Self::write_i16(&mut data, o.viewport_x);
-                Self::write_i16(&mut data, o.viewport_y);
-                Self::write_u16(&mut data, o.canvas_width);
-                Self::write_u16(&mut data, o.canvas_height);
-                Self::write_f32(&mut data, o.viewport_zoom);
-                Self::write_i16(&mut data, o.graphics_cursor_x);
-                Self::write_i16(&mut data, o.graphics_cursor_y);
-                Self::write_u8(&mut data, o.foreground_colour);
-                Self::write_u8(&mut data, o.background_colour);
-                Self::write_u16(&mut data, o.font_attributes_object);
-                Self::write_u16(&mut data, o.line_attributes_object);
-                Self::write_u16(&mut data, o.fill_attributes_object);
-                Self::write_u8(&mut data, o.format);
-                Self::write_u8(&mut data, o.options);
-                Self::write_u8(&mut data, o.transparency_colour);
+                Self::write_auxiliary_control_designator_type2(data, o)
             }
-            Object::OutputList(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::OutputList);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u16(&mut data, o.variable_reference);
-                Self::write_u8(&mut data, o.value);
-                Self::write_u8(&mut data, o.list_items.len() as u8);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_objects(&mut data, &o.list_items);
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::ExtendedInputAttributes(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::ExtendedInputAttributes);
-                Self::write_u8(&mut data, o.validation_type);
-                Self::write_u8(&mut data, o.nr_of_code_planes);
-                // TODO
+            Object::WindowMask(o) => Self::write_window_mask(data, o),
+            Object::KeyGroup(o) => Self::write_key_group(data, o),
+            Object::GraphicsContext(o) => Self::write_graphics_context(data, o),
+            Object::OutputList(o) => Self::write_output_list(data, o),
+            Object::ExtendedInputAttributes(o) => Self::write_extended_input_attributes(data, o),
+            Object::ColourMap(o) => Self::write_colour_map(data, o),
+            Object::ObjectLabelReferenceList(o) => Self::write_object_label_reference_list(data, o),
+            Object::ExternalObjectDefinition(o) => Self::write_external_object_definition(data, o),
+            Object::ExternalReferenceName(o) => Self::write_external_reference_name(data, o),
+            Object::ExternalObjectPointer(o) => Self::write_external_object_pointer(data, o),
+            Object::Animation(o) => Self::write_animation(data, o),
+            Object::ColourPalette(o) => Self::write_colour_palette(data, o),
+            Object::GraphicData(o) => Self::write_graphic_data(data, o),
+            Object::WorkingSetSpecialControls(o) => {
+                Self::write_working_set_special_controls(data, o)
             }
-            Object::ColourMap(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::ColourMap);
-                Self::write_u16(&mut data, o.colour_map.len() as u16);
+            Object::ScalesGraphic(o) => Self::write_scales_graphic(data, o),
+        }
+    }
 
-                Self::write_bytes(&mut data, &o.colour_map);
-            }
-            Object::ObjectLabelReferenceList(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::ObjectLabelReferenceList);
-                Self::write_u16(&mut data, o.object_labels.len() as u16);
+    fn write_working_set(data: &mut Vec<u8>, o: &WorkingSet) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::WorkingSet);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u8(data, o.selectable);
+        Self::write_u16(data, o.active_mask);
+        Self::write_count_u8(data, o.object_refs.len());
+        Self::write_count_u8(data, o.macro_refs.len());
+        Self::write_count_u8(data, o.language_codes.len());
 
-                Self::write_object_labels(&mut data, &o.object_labels);
-            }
-            Object::ExternalObjectDefinition(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::ExternalObjectDefinition);
-                Self::write_u8(&mut data, o.options);
-                Self::write_name(&mut data, o.name);
-                Self::write_u8(&mut data, o.objects.len() as u8);
-
-                Self::write_objects(&mut data, &o.objects);
-            }
-            Object::ExternalReferenceName(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::ExternalReferenceName);
-                Self::write_u8(&mut data, o.options);
-                Self::write_name(&mut data, o.name);
-            }
-            Object::ExternalObjectPointer(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::ExternalObjectPointer);
-                Self::write_u16(&mut data, o.default_object_id);
-                Self::write_u16(&mut data, o.external_reference_name_id);
-                Self::write_u16(&mut data, o.external_object_id);
-            }
-            Object::Animation(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::Animation);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u16(&mut data, o.refresh_interval);
-                Self::write_u8(&mut data, o.value);
-                Self::write_u8(&mut data, o.enabled);
-                Self::write_u8(&mut data, o.first_child_index);
-                Self::write_u8(&mut data, o.last_child_index);
-                Self::write_u8(&mut data, o.default_child_index);
-                Self::write_u8(&mut data, o.options);
-                Self::write_u8(&mut data, o.object_refs.len() as u8);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_object_refs(&mut data, &o.object_refs);
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-            Object::ColourPalette(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::ColourPalette);
-                Self::write_u16(&mut data, o.options);
-                Self::write_u16(&mut data, o.colours.len() as u16);
+        Self::write_object_refs(data, &o.object_refs);
+        Self::write_macro_refs(data, &o.macro_refs);
+        Self::write_language_codes(data, &o.language_codes);
+    }
 
-                Self::write_colours(&mut data, &o.colours);
-            }
-            Object::GraphicData(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::GraphicData);
-                Self::write_u8(&mut data, o.format);
-                Self::write_u32(&mut data, o.data.len() as u32);
+    fn write_data_mask(data: &mut Vec<u8>, o: &DataMask) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::DataMask);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u16(data, o.soft_key_mask);
+        Self::write_count_u8(data, o.object_refs.len());
+        Self::write_count_u8(data, o.macro_refs.len());
 
-                Self::write_bytes(&mut data, &o.data);
-            }
-            Object::WorkingSetSpecialControls(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::WorkingSetSpecialControls);
-                Self::write_u16(&mut data, o.id_of_colour_map);
-                Self::write_u16(&mut data, o.id_of_colour_palette);
-                Self::write_u8(&mut data, o.language_pairs.len() as u8);
+        Self::write_object_refs(data, &o.object_refs);
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
 
-                Self::write_language_pairs(&mut data, &o.language_pairs);
-            }
-            Object::ScalesGraphic(o) => {
-                Self::write_u16(&mut data, o.id);
-                Self::write_u8(&mut data, ObjectType::ScalesGraphic);
-                Self::write_u16(&mut data, o.width);
-                Self::write_u16(&mut data, o.height);
-                Self::write_u8(&mut data, o.scale_type);
-                Self::write_u8(&mut data, o.options);
-                Self::write_u16(&mut data, o.value);
-                Self::write_u8(&mut data, o.macro_refs.len() as u8);
-
-                Self::write_macro_refs(&mut data, &o.macro_refs);
-            }
-        }
-        data
+    fn write_alarm_mask(data: &mut Vec<u8>, o: &AlarmMask) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::AlarmMask);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u16(data, o.soft_key_mask);
+        Self::write_u8(data, o.priority);
+        Self::write_u8(data, o.acoustic_signal);
+        Self::write_count_u8(data, o.object_refs.len());
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_object_refs(data, &o.object_refs);
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_container(data: &mut Vec<u8>, o: &Container) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::Container);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u8(data, o.hidden);
+        Self::write_count_u8(data, o.object_refs.len());
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_object_refs(data, &o.object_refs);
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_soft_key_mask(data: &mut Vec<u8>, o: &SoftKeyMask) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::SoftKeyMask);
+        Self::write_u8(data, o.background_colour);
+        Self::write_count_u8(data, o.objects.len());
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_objects(data, &o.objects);
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_key(data: &mut Vec<u8>, o: &Key) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::Key);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u8(data, o.key_code);
+        Self::write_count_u8(data, o.object_refs.len());
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_object_refs(data, &o.object_refs);
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_button(data: &mut Vec<u8>, o: &Button) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::Button);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u8(data, o.border_colour);
+        Self::write_u8(data, o.key_code);
+        Self::write_u8(data, o.options);
+        Self::write_count_u8(data, o.object_refs.len());
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_object_refs(data, &o.object_refs);
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_input_boolean(data: &mut Vec<u8>, o: &InputBoolean) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::InputBoolean);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.foreground_colour);
+        Self::write_u16(data, o.variable_reference);
+        Self::write_u8(data, o.value);
+        Self::write_u8(data, o.enabled);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_input_string(data: &mut Vec<u8>, o: &InputString) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::InputString);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u16(data, o.font_attributes);
+        Self::write_u16(data, o.input_attributes);
+        Self::write_u8(data, o.options);
+        Self::write_u16(data, o.variable_reference);
+        Self::write_u8(data, o.justification);
+        Self::write_string_u8(data, &o.value);
+        Self::write_u8(data, o.enabled);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_input_number(data: &mut Vec<u8>, o: &InputNumber) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::InputNumber);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u16(data, o.font_attributes);
+        Self::write_u8(data, o.options);
+        Self::write_u16(data, o.variable_reference);
+        Self::write_u32(data, o.value);
+        Self::write_u32(data, o.min_value);
+        Self::write_u32(data, o.max_value);
+        Self::write_i32(data, o.offset);
+        Self::write_f32(data, o.scale);
+        Self::write_u8(data, o.nr_of_decimals);
+        Self::write_u8(data, o.format);
+        Self::write_u8(data, o.justification);
+        Self::write_u8(data, o.options2);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_input_list(data: &mut Vec<u8>, o: &InputList) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::InputList);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u16(data, o.variable_reference);
+        Self::write_u8(data, o.value);
+        Self::write_count_u8(data, o.list_items.len());
+        Self::write_u8(data, o.options);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_objects(data, &o.list_items);
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_output_string(data: &mut Vec<u8>, o: &OutputString) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::OutputString);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u16(data, o.font_attributes);
+        Self::write_u8(data, o.options);
+        Self::write_u16(data, o.variable_reference);
+        Self::write_u8(data, o.justification);
+        Self::write_string_u16(data, &o.value);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_output_number(data: &mut Vec<u8>, o: &OutputNumber) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::OutputNumber);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u16(data, o.font_attributes);
+        Self::write_u8(data, o.options);
+        Self::write_u16(data, o.variable_reference);
+        Self::write_u32(data, o.value);
+        Self::write_i32(data, o.offset);
+        Self::write_f32(data, o.scale);
+        Self::write_u8(data, o.nr_of_decimals);
+        Self::write_u8(data, o.format);
+        Self::write_u8(data, o.justification);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_output_line(data: &mut Vec<u8>, o: &OutputLine) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::OutputLine);
+        Self::write_u16(data, o.line_attributes);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u8(data, o.line_direction);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_output_rectangle(data: &mut Vec<u8>, o: &OutputRectangle) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::OutputRectangle);
+        Self::write_u16(data, o.line_attributes);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u8(data, o.line_suppression);
+        Self::write_u16(data, o.fill_attributes);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_output_ellipse(data: &mut Vec<u8>, o: &OutputEllipse) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::OutputEllipse);
+        Self::write_u16(data, o.line_attributes);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u8(data, o.ellipse_type);
+        Self::write_u8(data, o.start_angle);
+        Self::write_u8(data, o.end_angle);
+        Self::write_u16(data, o.fill_attributes);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_output_polygon(data: &mut Vec<u8>, o: &OutputPolygon) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::OutputPolygon);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u16(data, o.line_attributes);
+        Self::write_u16(data, o.fill_attributes);
+        Self::write_u8(data, o.polygon_type);
+        // Keep the count and the points consistent, see `OutputPolygon::validate`
+        let points = &o.points[..o.points.len().min(u8::MAX.into())];
+        Self::write_u8(data, points.len() as u8);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_points(data, points);
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_output_meter(data: &mut Vec<u8>, o: &OutputMeter) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::OutputMeter);
+        Self::write_u16(data, o.width);
+        Self::write_u8(data, o.needle_colour);
+        Self::write_u8(data, o.border_colour);
+        Self::write_u8(data, o.arc_and_tick_colour);
+        Self::write_u8(data, o.options);
+        Self::write_u8(data, o.nr_of_ticks);
+        Self::write_u8(data, o.start_angle);
+        Self::write_u8(data, o.end_angle);
+        Self::write_u16(data, o.min_value);
+        Self::write_u16(data, o.max_value);
+        Self::write_u16(data, o.variable_reference);
+        Self::write_u16(data, o.value);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_output_linear_bar_graph(data: &mut Vec<u8>, o: &OutputLinearBarGraph) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::OutputLinearBarGraph);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u8(data, o.colour);
+        Self::write_u8(data, o.target_line_colour);
+        Self::write_u8(data, o.options);
+        Self::write_u8(data, o.nr_of_ticks);
+        Self::write_u16(data, o.min_value);
+        Self::write_u16(data, o.max_value);
+        Self::write_u16(data, o.variable_reference);
+        Self::write_u16(data, o.value);
+        Self::write_u16(data, o.target_value_variable_reference);
+        Self::write_u16(data, o.target_value);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_output_arched_bar_graph(data: &mut Vec<u8>, o: &OutputArchedBarGraph) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::OutputArchedBarGraph);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u8(data, o.colour);
+        Self::write_u8(data, o.target_line_colour);
+        Self::write_u8(data, o.options);
+        Self::write_u8(data, o.start_angle);
+        Self::write_u8(data, o.end_angle);
+        Self::write_u16(data, o.bar_graph_width);
+        Self::write_u16(data, o.min_value);
+        Self::write_u16(data, o.max_value);
+        Self::write_u16(data, o.variable_reference);
+        Self::write_u16(data, o.value);
+        Self::write_u16(data, o.target_value_variable_reference);
+        Self::write_u16(data, o.target_value);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
     }
 
-    fn write_objects(data: &mut Vec<u8>, objects: &Vec<ObjectId>) {
-        for d in objects {
+    fn write_picture_graphic(data: &mut Vec<u8>, o: &PictureGraphic) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::PictureGraphic);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.actual_width);
+        Self::write_u16(data, o.actual_height);
+        Self::write_u8(data, o.format);
+        Self::write_u8(data, o.options);
+        Self::write_u8(data, o.transparency_colour);
+        Self::write_u32(data, o.data.len() as u32);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_bytes(data, &o.data);
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_number_variable(data: &mut Vec<u8>, o: &NumberVariable) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::NumberVariable);
+        Self::write_u32(data, o.value);
+    }
+
+    fn write_string_variable(data: &mut Vec<u8>, o: &StringVariable) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::StringVariable);
+        Self::write_string_u16(data, &o.value);
+    }
+
+    fn write_font_attributes(data: &mut Vec<u8>, o: &FontAttributes) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::FontAttributes);
+        Self::write_u8(data, o.font_colour);
+        Self::write_u8(data, o.font_size);
+        Self::write_u8(data, o.font_type);
+        Self::write_u8(data, o.font_style);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_line_attributes(data: &mut Vec<u8>, o: &LineAttributes) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::LineAttributes);
+        Self::write_u8(data, o.line_colour);
+        Self::write_u8(data, o.line_width);
+        Self::write_u16(data, o.line_art);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_fill_attributes(data: &mut Vec<u8>, o: &FillAttributes) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::FillAttributes);
+        Self::write_u8(data, o.fill_type);
+        Self::write_u8(data, o.fill_colour);
+        Self::write_u16(data, o.fill_pattern);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_input_attributes(data: &mut Vec<u8>, o: &InputAttributes) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::InputAttributes);
+        Self::write_u8(data, o.validation_type);
+        Self::write_string_u8(data, &o.validation_string);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_object_pointer(data: &mut Vec<u8>, o: &ObjectPointer) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::ObjectPointer);
+        Self::write_u16(data, o.value);
+    }
+
+    fn write_macro(data: &mut Vec<u8>, o: &Macro) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::Macro);
+        let commands = &o.commands[..o.commands.len().min(u16::MAX.into())];
+        Self::write_u16(data, commands.len() as u16);
+
+        Self::write_bytes(data, commands);
+    }
+
+    fn write_auxiliary_function_type1(data: &mut Vec<u8>, o: &AuxiliaryFunctionType1) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::AuxiliaryFunctionType1);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u8(data, o.function_type);
+        Self::write_count_u8(data, o.object_refs.len());
+
+        Self::write_object_refs(data, &o.object_refs);
+    }
+
+    fn write_auxiliary_input_type1(data: &mut Vec<u8>, o: &AuxiliaryInputType1) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::AuxiliaryInputType1);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u8(data, o.function_type);
+        Self::write_u8(data, o.input_id);
+        Self::write_count_u8(data, o.object_refs.len());
+
+        Self::write_object_refs(data, &o.object_refs);
+    }
+
+    fn write_auxiliary_function_type2(data: &mut Vec<u8>, o: &AuxiliaryFunctionType2) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::AuxiliaryFunctionType2);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u8(data, o.function_attributes);
+        Self::write_count_u8(data, o.object_refs.len());
+
+        Self::write_object_refs(data, &o.object_refs);
+    }
+
+    fn write_auxiliary_input_type2(data: &mut Vec<u8>, o: &AuxiliaryInputType2) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::AuxiliaryInputType2);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u8(data, o.function_attributes);
+        Self::write_count_u8(data, o.object_refs.len());
+
+        Self::write_object_refs(data, &o.object_refs);
+    }
+
+    fn write_auxiliary_control_designator_type2(
+        data: &mut Vec<u8>,
+        o: &AuxiliaryControlDesignatorType2,
+    ) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::AuxiliaryControlDesignatorType2);
+        Self::write_u8(data, o.pointer_type);
+        Self::write_u16(data, o.auxiliary_object_id);
+    }
+
+    fn write_window_mask(data: &mut Vec<u8>, o: &WindowMask) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::WindowMask);
+        Self::write_u8(data, o.width);
+        Self::write_u8(data, o.height);
+        Self::write_u8(data, o.window_type);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u8(data, o.options);
+        Self::write_u16(data, o.name);
+        Self::write_u16(data, o.window_title);
+        Self::write_u16(data, o.window_icon);
+        Self::write_count_u8(data, o.objects.len());
+        Self::write_count_u8(data, o.object_refs.len());
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_objects(data, &o.objects);
+        Self::write_object_refs(data, &o.object_refs);
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_key_group(data: &mut Vec<u8>, o: &KeyGroup) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::KeyGroup);
+        Self::write_u8(data, o.options);
+        Self::write_u16(data, o.name);
+        Self::write_u16(data, o.key_group_icon);
+        Self::write_count_u8(data, o.objects.len());
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_objects(data, &o.objects);
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_graphics_context(data: &mut Vec<u8>, o: &GraphicsContext) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::GraphicsContext);
+        Self::write_u16(data, o.viewport_width);
+        Self::write_u16(data, o.viewport_height);
+        Self::write_i16(data, o.viewport_x);
+        Self::write_i16(data, o.viewport_y);
+        Self::write_u16(data, o.canvas_width);
+        Self::write_u16(data, o.canvas_height);
+        Self::write_f32(data, o.viewport_zoom);
+        Self::write_i16(data, o.graphics_cursor_x);
+        Self::write_i16(data, o.graphics_cursor_y);
+        Self::write_u8(data, o.foreground_colour);
+        Self::write_u8(data, o.background_colour);
+        Self::write_u16(data, o.font_attributes_object);
+        Self::write_u16(data, o.line_attributes_object);
+        Self::write_u16(data, o.fill_attributes_object);
+        Self::write_u8(data, o.format);
+        Self::write_u8(data, o.options);
+        Self::write_u8(data, o.transparency_colour);
+    }
+
+    fn write_output_list(data: &mut Vec<u8>, o: &OutputList) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::OutputList);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u16(data, o.variable_reference);
+        Self::write_u8(data, o.value);
+        Self::write_count_u8(data, o.list_items.len());
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_objects(data, &o.list_items);
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_extended_input_attributes(data: &mut Vec<u8>, o: &ExtendedInputAttributes) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::ExtendedInputAttributes);
+        Self::write_u8(data, o.validation_type);
+        Self::write_count_u8(data, o.code_planes.len());
+
+        Self::write_code_planes(data, &o.code_planes);
+    }
+
+    fn write_colour_map(data: &mut Vec<u8>, o: &ColourMap) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::ColourMap);
+        let colour_map = &o.colour_map[..o.colour_map.len().min(u16::MAX.into())];
+        Self::write_u16(data, colour_map.len() as u16);
+
+        Self::write_bytes(data, colour_map);
+    }
+
+    fn write_object_label_reference_list(data: &mut Vec<u8>, o: &ObjectLabelReferenceList) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::ObjectLabelReferenceList);
+        Self::write_count_u16(data, o.object_labels.len());
+
+        Self::write_object_labels(data, &o.object_labels);
+    }
+
+    fn write_external_object_definition(data: &mut Vec<u8>, o: &ExternalObjectDefinition) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::ExternalObjectDefinition);
+        Self::write_u8(data, o.options);
+        Self::write_name(data, o.name);
+        Self::write_count_u8(data, o.objects.len());
+
+        Self::write_objects(data, &o.objects);
+    }
+
+    fn write_external_reference_name(data: &mut Vec<u8>, o: &ExternalReferenceName) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::ExternalReferenceName);
+        Self::write_u8(data, o.options);
+        Self::write_name(data, o.name);
+    }
+
+    fn write_external_object_pointer(data: &mut Vec<u8>, o: &ExternalObjectPointer) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::ExternalObjectPointer);
+        Self::write_u16(data, o.default_object_id);
+        Self::write_u16(data, o.external_reference_name_id);
+        Self::write_u16(data, o.external_object_id);
+    }
+
+    fn write_animation(data: &mut Vec<u8>, o: &Animation) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::Animation);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u16(data, o.refresh_interval);
+        Self::write_u8(data, o.value);
+        Self::write_u8(data, o.enabled);
+        Self::write_u8(data, o.first_child_index);
+        Self::write_u8(data, o.last_child_index);
+        Self::write_u8(data, o.default_child_index);
+        Self::write_u8(data, o.options);
+        Self::write_count_u8(data, o.object_refs.len());
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_object_refs(data, &o.object_refs);
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_colour_palette(data: &mut Vec<u8>, o: &ColourPalette) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::ColourPalette);
+        Self::write_u16(data, o.options);
+        Self::write_count_u16(data, o.colours.len());
+
+        Self::write_colours(data, &o.colours);
+    }
+
+    fn write_graphic_data(data: &mut Vec<u8>, o: &GraphicData) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::GraphicData);
+        Self::write_u8(data, o.format);
+        Self::write_u32(data, o.data.len() as u32);
+
+        Self::write_bytes(data, &o.data);
+    }
+
+    fn write_working_set_special_controls(data: &mut Vec<u8>, o: &WorkingSetSpecialControls) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::WorkingSetSpecialControls);
+        Self::write_u16(data, o.id_of_colour_map);
+        Self::write_u16(data, o.id_of_colour_palette);
+        Self::write_count_u8(data, o.language_pairs.len());
+
+        Self::write_language_pairs(data, &o.language_pairs);
+    }
+
+    fn write_scales_graphic(data: &mut Vec<u8>, o: &ScalesGraphic) {
+        Self::write_u16(data, o.id);
+        Self::write_u8(data, ObjectType::ScalesGraphic);
+        Self::write_u16(data, o.width);
+        Self::write_u16(data, o.height);
+        Self::write_u8(data, o.scale_type);
+        Self::write_u8(data, o.options);
+        Self::write_u16(data, o.value);
+        Self::write_count_u8(data, o.macro_refs.len());
+
+        Self::write_macro_refs(data, &o.macro_refs);
+    }
+
+    fn write_objects(data: &mut Vec<u8>, objects: &[ObjectId]) {
+        for d in objects.iter().take(u8::MAX.into()) {
             Self::write_u16(data, *d);
         }
     }
-    fn write_object_refs(data: &mut Vec<u8>, object_refs: &Vec<ObjectRef>) {
-        for d in object_refs {
+    fn write_object_refs(data: &mut Vec<u8>, object_refs: &[ObjectRef]) {
+        for d in object_refs.iter().take(u8::MAX.into()) {
             Self::write_u16(data, d.id);
             Self::write_i16(data, d.offset.x);
             Self::write_i16(data, d.offset.y);
         }
     }
-    fn write_macro_refs(data: &mut Vec<u8>, macro_refs: &Vec<MacroRef>) {
-        for d in macro_refs {
+    fn write_macro_refs(data: &mut Vec<u8>, macro_refs: &[MacroRef]) {
+        for d in macro_refs.iter().take(u8::MAX.into()) {
             Self::write_u8(data, d.event_id);
             Self::write_u8(data, d.macro_id);
         }
     }
-    fn write_bytes(data: &mut Vec<u8>, bytes: &Vec<u8>) {
+    fn write_bytes(data: &mut Vec<u8>, bytes: &[u8]) {
         for d in bytes {
             Self::write_u8(data, *d);
         }
     }
-    fn write_language_codes(data: &mut Vec<u8>, language_codes: &Vec<String>) {
-        for d in language_codes {
-            Self::write_string(data, d);
+    fn write_language_codes(data: &mut Vec<u8>, language_codes: &[String]) {
+        for d in language_codes.iter().take(u8::MAX.into()) {
+            Self::write_fixed_string(data, d, 2);
         }
     }
-    fn write_points(data: &mut Vec<u8>, points: &Vec<Point<u16>>) {
+    fn write_points(data: &mut Vec<u8>, points: &[Point<u16>]) {
         for d in points {
             Self::write_u16(data, d.x);
             Self::write_u16(data, d.y);
         }
     }
-    fn write_colours(data: &mut Vec<u8>, colours: &Vec<Colour>) {
-        for d in colours {
+    fn write_colours(data: &mut Vec<u8>, colours: &[Colour]) {
+        for d in colours.iter().take(u16::MAX.into()) {
             Self::write_u8(data, d.b);
             Self::write_u8(data, d.g);
             Self::write_u8(data, d.r);
             Self::write_u8(data, d.a);
         }
     }
-    fn write_object_labels(data: &mut Vec<u8>, object_labels: &Vec<ObjectLabel>) {
-        for d in object_labels {
+    fn write_object_labels(data: &mut Vec<u8>, object_labels: &[ObjectLabel]) {
+        for d in object_labels.iter().take(u16::MAX.into()) {
             Self::write_u16(data, d.id);
             Self::write_u16(data, d.string_variable_reference);
             Self::write_u8(data, d.font_type);
             Self::write_u16(data, d.graphic_representation);
         }
     }
-    fn write_language_pairs(data: &mut Vec<u8>, language_pairs: &Vec<(String, String)>) {
-        for d in language_pairs {
-            Self::write_string(data, &d.0);
-            Self::write_string(data, &d.1);
+    fn write_code_planes(data: &mut Vec<u8>, code_planes: &[CodePlane]) {
+        for d in code_planes.iter().take(u8::MAX.into()) {
+            Self::write_u8(data, d.number);
+            Self::write_count_u8(data, d.character_ranges.len());
+            for range in d.character_ranges.iter().take(u8::MAX.into()) {
+                Self::write_u16(data, *range.start());
+                Self::write_u16(data, *range.end());
+            }
+        }
+    }
+
+    fn write_language_pairs(data: &mut Vec<u8>, language_pairs: &[(String, String)]) {
+        for d in language_pairs.iter().take(u8::MAX.into()) {
+            Self::write_fixed_string(data, &d.0, 2);
+            Self::write_fixed_string(data, &d.1, 2);
         }
     }
 
@@ -670,12 +794,247 @@ impl Object {
         let val: f32 = val.into();
         data.extend(val.to_le_bytes());
     }
-    fn write_string(data: &mut Vec<u8>, val: impl Into<String>) {
-        let val: String = val.into();
-        data.extend(val.as_bytes());
+    /// Write the number of entries of a list, at most 255. The list writers stop at the same count.
+    fn write_count_u8(data: &mut Vec<u8>, len: usize) {
+        Self::write_u8(data, len.min(u8::MAX.into()) as u8);
+    }
+    /// Write the number of entries of a list, at most 65535. The list writers stop at the same
+    /// count.
+    fn write_count_u16(data: &mut Vec<u8>, len: usize) {
+        Self::write_u16(data, len.min(u16::MAX.into()) as u16);
+    }
+    /// Write `val` after a one byte length, cut off at 255 characters.
+    ///
+    /// Padding spaces and NUL characters are written as they are, like the reader keeps them.
+    fn write_string_u8(data: &mut Vec<u8>, val: &str) {
+        let bytes = CharacterSet::Latin1.encode_lossy(val);
+        let len = bytes.len().min(u8::MAX.into());
+        Self::write_u8(data, len as u8);
+        data.extend(&bytes[..len]);
+    }
+    /// Write `val` after a two byte length, cut off at 65535 characters.
+    fn write_string_u16(data: &mut Vec<u8>, val: &str) {
+        let bytes = CharacterSet::Latin1.encode_lossy(val);
+        let len = bytes.len().min(u16::MAX.into());
+        Self::write_u16(data, len as u16);
+        data.extend(&bytes[..len]);
+    }
+    /// Write `val` as exactly `len` bytes, padded with spaces or cut off.
+    fn write_fixed_string(data: &mut Vec<u8>, val: &str, len: usize) {
+        let mut bytes = CharacterSet::Latin1.encode_lossy(val);
+        bytes.resize(len, b' ');
+        data.extend(bytes);
     }
     fn write_name(data: &mut Vec<u8>, val: impl Into<Name>) {
         let val: Name = val.into();
-        data.extend::<[u8; 8]>(val.into());
+        data.extend(u64::from(val).to_le_bytes());
+    }
+}
+
+/// Serialize a single object, including its id and type.
+macro_rules! impl_to_bytes {
+    ($($object:ident => $write:ident),* $(,)?) => {
+        $(
+            impl $object {
+                pub fn to_bytes(&self) -> Vec<u8> {
+                    let mut data = Vec::new();
+                    Object::$write(&mut data, self);
+                    data
+                }
+            }
+
+            impl From<&$object> for Vec<u8> {
+                fn from(value: &$object) -> Self {
+                    value.to_bytes()
+                }
+            }
+        )*
+    };
+}
+
+impl_to_bytes! {
+    WorkingSet => write_working_set,
+    DataMask => write_data_mask,
+    AlarmMask => write_alarm_mask,
+    Container => write_container,
+    SoftKeyMask => write_soft_key_mask,
+    Key => write_key,
+    Button => write_button,
+    InputBoolean => write_input_boolean,
+    InputString => write_input_string,
+    InputNumber => write_input_number,
+    InputList => write_input_list,
+    OutputString => write_output_string,
+    OutputNumber => write_output_number,
+    OutputLine => write_output_line,
+    OutputRectangle => write_output_rectangle,
+    OutputEllipse => write_output_ellipse,
+    OutputPolygon => write_output_polygon,
+    OutputMeter => write_output_meter,
+    OutputLinearBarGraph => write_output_linear_bar_graph,
+    OutputArchedBarGraph => write_output_arched_bar_graph,
+    PictureGraphic => write_picture_graphic,
+    NumberVariable => write_number_variable,
+    StringVariable => write_string_variable,
+    FontAttributes => write_font_attributes,
+    LineAttributes => write_line_attributes,
+    FillAttributes => write_fill_attributes,
+    InputAttributes => write_input_attributes,
+    ObjectPointer => write_object_pointer,
+    Macro => write_macro,
+    AuxiliaryFunctionType1 => write_auxiliary_function_type1,
+    AuxiliaryInputType1 => write_auxiliary_input_type1,
+    AuxiliaryFunctionType2 => write_auxiliary_function_type2,
+    AuxiliaryInputType2 => write_auxiliary_input_type2,
+    AuxiliaryControlDesignatorType2 => write_auxiliary_control_designator_type2,
+    WindowMask => write_window_mask,
+    KeyGroup => write_key_group,
+    GraphicsContext => write_graphics_context,
+    OutputList => write_output_list,
+    ExtendedInputAttributes => write_extended_input_attributes,
+    ColourMap => write_colour_map,
+    ObjectLabelReferenceList => write_object_label_reference_list,
+    ExternalObjectDefinition => write_external_object_definition,
+    ExternalReferenceName => write_external_reference_name,
+    ExternalObjectPointer => write_external_object_pointer,
+    Animation => write_animation,
+    ColourPalette => write_colour_palette,
+    GraphicData => write_graphic_data,
+    WorkingSetSpecialControls => write_working_set_special_controls,
+    ScalesGraphic => write_scales_graphic,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_bytes_round_trip() {
+        let data_mask = DataMask {
+            id: 1000.into(),
+            background_colour: 12,
+            soft_key_mask: ObjectId::NULL,
+            object_refs: vec![ObjectRef {
+                id: 12000.into(),
+                offset: Point { x: 10, y: 20 },
+            }],
+            macro_refs: vec![MacroRef {
+                macro_id: 1,
+                event_id: 2,
+            }],
+        };
+        let data = data_mask.to_bytes();
+        assert_eq!(Object::DataMask(data_mask).write(), data);
+        assert_eq!(
+            Object::DataMask(DataMask::try_from(&data[..]).unwrap()),
+            Object::read(&mut data.into_iter()).unwrap()
+        );
+
+        let number_variable = NumberVariable {
+            id: 21000.into(),
+            value: 0x1234_5678,
+        };
+        let data = Vec::from(&number_variable);
+        assert_eq!(vec![0x08, 0x52, 21, 0x78, 0x56, 0x34, 0x12], data);
+        assert_eq!(Ok(number_variable), NumberVariable::try_from(&data[..]));
+    }
+
+    #[test]
+    fn test_string_character_set_round_trip() {
+        let data: &[u8] = &[
+            0x08, 0x66, 22, // StringVariable 26120
+            5, 0, b'c', b'a', b'f', 0xE9, 0xA4, // "café€" in Latin 9
+        ];
+
+        let string_variable = StringVariable::try_from(data).unwrap();
+        assert_eq!("caf\u{E9}\u{A4}", string_variable.value);
+        assert_eq!(data, string_variable.to_bytes());
+        assert_eq!(
+            "café¤",
+            CharacterSet::Latin1.decode_str(&string_variable.value)
+        );
+        assert_eq!(
+            "café€",
+            CharacterSet::Latin9.decode_str(&string_variable.value)
+        );
+
+        let output_string = OutputString {
+            id: 11000.into(),
+            width: 50,
+            height: 20,
+            background_colour: 1,
+            font_attributes: 23000.into(),
+            options: 0,
+            variable_reference: ObjectId::NULL,
+            justification: 0,
+            value: CharacterSet::Latin9.encode_str("5 €").unwrap(),
+            macro_refs: Vec::new(),
+        };
+        let data = output_string.to_bytes();
+        assert_eq!([3, 0, b'5', b' ', 0xA4, 0], data[14..20]);
+        assert_eq!(Ok(output_string), OutputString::try_from(&data[..]));
+    }
+
+    #[test]
+    fn test_string_padding_round_trip() {
+        let data: &[u8] = &[
+            0x08, 0x66, 22, // StringVariable 26120
+            8, 0, b'O', b'K', b' ', b' ', b' ', 0, b' ', b' ', // "OK", padded
+        ];
+        let string_variable = StringVariable::try_from(data).unwrap();
+        assert_eq!("OK   \0  ", string_variable.value);
+        assert_eq!(data, string_variable.to_bytes());
+
+        let input_attributes = InputAttributes {
+            id: 27000.into(),
+            validation_type: 0,
+            validation_string: "0123456789".repeat(30),
+            macro_refs: Vec::new(),
+        };
+        let data = input_attributes.to_bytes();
+        assert_eq!(255, data[4]);
+        let read = InputAttributes::try_from(&data[..]).unwrap();
+        assert_eq!(
+            input_attributes.validation_string[..255],
+            read.validation_string
+        );
+
+        let working_set = WorkingSet {
+            id: 0.into(),
+            background_colour: 0,
+            selectable: true,
+            active_mask: 1000.into(),
+            object_refs: Vec::new(),
+            macro_refs: Vec::new(),
+            language_codes: vec!["en".into(), "d".into(), "fra".into()],
+        };
+        let data = working_set.to_bytes();
+        assert_eq!(b"end fr", &data[data.len() - 6..]);
+        assert_eq!(
+            vec!["en", "d ", "fr"],
+            WorkingSet::try_from(&data[..]).unwrap().language_codes
+        );
+    }
+
+    #[test]
+    fn test_list_count_clamped() {
+        let macro_refs: Vec<MacroRef> = (0..300)
+            .map(|i| MacroRef {
+                macro_id: i as u8,
+                event_id: 1,
+            })
+            .collect();
+        let data_mask = DataMask {
+            id: 1000.into(),
+            background_colour: 12,
+            soft_key_mask: ObjectId::NULL,
+            object_refs: Vec::new(),
+            macro_refs: macro_refs.clone(),
+        };
+        let data = data_mask.to_bytes();
+        assert_eq!(255, data[7]);
+        assert_eq!(8 + 2 * 255, data.len());
+        let read = DataMask::try_from(&data[..]).unwrap();
+        assert_eq!(macro_refs[..255], read.macro_refs);
     }
 }
@@ -0,0 +1,99 @@
+//! Conformance of the object pool reader and writer with the ISO 11783-6 object pool format.
+//!
+//! The fixture is hand-assembled, object by object, following the ISO 11783-6 attribute layout
+//! that the C++ AgIsoStack exports in its `.iop` files: objects are concatenated without any
+//! header or padding and all multi-byte values are little-endian. The second fixture is the same
+//! pool with a `PoolHeader` prepended.
+
+use ag_iso_stack::object_pool::{Object, ObjectId, ObjectPool, PoolHeader};
+
+const CONFORMANCE_IOP: &[u8] = include_bytes!("fixtures/conformance.iop");
+const CONFORMANCE_IOP_WITH_HEADER: &[u8] = include_bytes!("fixtures/conformance_with_header.iop");
+
+#[test]
+fn test_conformance_pool_parses() {
+    let pool = ObjectPool::from_iop(CONFORMANCE_IOP.iter().copied());
+
+    for id in [
+        0, 1000, 3000, 6000, 11000, 11001, 8000, 12000, 20000, 21000, 22000, 23000, 26000, 27000,
+        28, 29000,
+    ] {
+        assert!(
+            pool.object_by_id(ObjectId::from(id)).is_some(),
+            "object {id} missing"
+        );
+    }
+
+    match pool.object_by_id(ObjectId::from(8000)) {
+        Some(Object::InputString(o)) => assert_eq!("Name", o.value),
+        _ => panic!("InputString missing"),
+    }
+    match pool.object_by_id(ObjectId::from(22000)) {
+        Some(Object::StringVariable(o)) => assert_eq!("Hello", o.value),
+        _ => panic!("StringVariable missing"),
+    }
+    match pool.object_by_id(ObjectId::from(26000)) {
+        Some(Object::InputAttributes(o)) => assert_eq!("abc", o.validation_string),
+        _ => panic!("InputAttributes missing"),
+    }
+    match pool.object_by_id(ObjectId::from(29000)) {
+        Some(Object::ExtendedInputAttributes(o)) => {
+            assert_eq!(2, o.code_planes.len());
+            assert_eq!(
+                vec![0x20..=0x7E, 0xA0..=0xFF],
+                o.code_planes[0].character_ranges
+            );
+            assert_eq!(1, o.code_planes[1].number);
+        }
+        _ => panic!("ExtendedInputAttributes missing"),
+    }
+}
+
+#[test]
+fn test_conformance_pool_round_trip() {
+    let pool = ObjectPool::from_iop(CONFORMANCE_IOP.iter().copied());
+
+    assert_eq!(CONFORMANCE_IOP, pool.as_iop().as_slice());
+}
+
+#[test]
+fn test_conformance_pool_with_header() {
+    let with_header = ObjectPool::from_iop(CONFORMANCE_IOP_WITH_HEADER.iter().copied());
+    let without_header = ObjectPool::from_iop(CONFORMANCE_IOP.iter().copied());
+
+    assert_eq!(
+        Some(PoolHeader {
+            version: 1,
+            object_count: 16
+        }),
+        with_header.header()
+    );
+    assert_eq!(None, without_header.header());
+    assert_eq!(without_header.as_iop(), with_header.as_iop());
+}
+
+#[test]
+fn test_conformance_pool_scan_headers() {
+    let pool = ObjectPool::from_iop(CONFORMANCE_IOP.iter().copied());
+
+    let mut offset = 0;
+    let mut expected = Vec::new();
+    for obj in pool.objects() {
+        expected.push((obj.id(), obj.object_type(), offset));
+        offset += obj.write().len();
+    }
+
+    assert_eq!(
+        Ok(expected.clone()),
+        ObjectPool::scan_headers(CONFORMANCE_IOP)
+    );
+
+    let with_header: Vec<_> = expected
+        .into_iter()
+        .map(|(id, object_type, offset)| (id, object_type, offset + PoolHeader::SIZE))
+        .collect();
+    assert_eq!(
+        Ok(with_header),
+        ObjectPool::scan_headers(CONFORMANCE_IOP_WITH_HEADER)
+    );
+}